@@ -0,0 +1,128 @@
+//! Compares signal routing backends on the dense topology where every neuron
+//! of the layer is connected to every neuron of the next layer.
+//!
+//! Run with `cargo bench --bench routing_backends`.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use librunen::rnn::common::input_cfg::InputCfg;
+use librunen::rnn::common::network_cfg::NeuronKind;
+use librunen::rnn::common::runtime_cfg::{RoutingBackend, RuntimeCfg};
+use librunen::rnn::common::status::Status;
+use librunen::rnn::layouts::network::Network;
+use tokio::time::sleep;
+
+static LAYERS: usize = 3;
+static WIDTH: usize = 16;
+static ROUNDS: usize = 20;
+static ROUND_PERIOD: Duration = Duration::from_millis(1);
+static SETTLE_PERIOD: Duration = Duration::from_millis(20);
+
+async fn build_dense_network(routing: RoutingBackend) -> (Arc<Network>, Vec<String>) {
+    let net = Arc::new(
+        Network::with_runtime_cfg(RuntimeCfg {
+            routing,
+            ..RuntimeCfg::default()
+        })
+        .unwrap(),
+    );
+
+    let mut ids = vec![];
+    let mut prev_layer: Vec<String> = vec![];
+    for layer in 0..LAYERS {
+        let input_configs = if layer == 0 {
+            vec![]
+        } else {
+            (0..WIDTH)
+                .map(|_| InputCfg::new(1, 1, 1).unwrap())
+                .collect()
+        };
+
+        let mut current_layer = vec![];
+        for _ in 0..WIDTH {
+            let neuron = net
+                .create_neuron(
+                    net.clone(),
+                    1,
+                    input_configs.clone(),
+                    NeuronKind::Excitatory,
+                )
+                .await
+                .unwrap();
+            current_layer.push(neuron.get_id());
+        }
+
+        for (dst_port, src_id) in prev_layer.iter().enumerate() {
+            for dst_id in current_layer.iter() {
+                net.connect_neurons(src_id, dst_id, dst_port).await.unwrap();
+            }
+        }
+
+        ids.extend(current_layer.iter().cloned());
+        prev_layer = current_layer;
+    }
+
+    for (port, id) in ids.iter().take(WIDTH).enumerate() {
+        net.setup_input(port, id, 0).await.unwrap();
+    }
+    for (port, id) in prev_layer.iter().enumerate() {
+        net.setup_output(port, id).await.unwrap();
+    }
+
+    (net, ids)
+}
+
+async fn total_hits(net: &Network, ids: &[String]) -> u64 {
+    let mut total = 0;
+    for id in ids {
+        if let Status::Neuron(info) = net.get_current_neuron_status(id).await.unwrap() {
+            total += info.hit_count;
+        }
+    }
+    total
+}
+
+async fn bench(routing: RoutingBackend) {
+    let started = Instant::now();
+    let (net, ids) = build_dense_network(routing.clone()).await;
+    let build_time = started.elapsed();
+
+    let started = Instant::now();
+    for _ in 0..ROUNDS {
+        for port in 0..WIDTH {
+            net.input(1, port).await.unwrap();
+        }
+        // input ports are broadcast channels in both backends, so rounds are
+        // paced to not lose signals before they reach the inner layers
+        sleep(ROUND_PERIOD).await;
+    }
+
+    // wait until signals stop spreading through the network
+    let mut hits = total_hits(&net, &ids).await;
+    loop {
+        sleep(SETTLE_PERIOD).await;
+        let current_hits = total_hits(&net, &ids).await;
+        if current_hits == hits {
+            break;
+        }
+        hits = current_hits;
+    }
+    let propagation_time = started.elapsed() - SETTLE_PERIOD;
+
+    let expected_hits = (ROUNDS * (WIDTH + (LAYERS - 1) * WIDTH * WIDTH)) as u64;
+    println!(
+        "{:?}: build {:?}, propagation {:?}, hits {}/{}",
+        routing, build_time, propagation_time, hits, expected_hits
+    );
+}
+
+#[tokio::main(flavor = "multi_thread")]
+async fn main() {
+    println!(
+        "Dense topology: {} layers of {} neurons, {} rounds",
+        LAYERS, WIDTH, ROUNDS
+    );
+    bench(RoutingBackend::Broadcast).await;
+    bench(RoutingBackend::Mailbox).await;
+}
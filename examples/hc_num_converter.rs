@@ -6,13 +6,18 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use librunen::rnn::common::input_cfg::InputCfg;
+use librunen::rnn::common::network_cfg::NeuronKind;
 use librunen::rnn::layouts::network::Network;
 use tokio::task;
 use tokio::time::sleep;
+use tokio_stream::StreamExt;
 
 async fn generate_net(net: Arc<Network>) {
     // The M0Z0 neuron
-    let neuron0 = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+    let neuron0 = net
+        .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+        .await
+        .unwrap();
 
     // The M0Z1 neuron
     let neuron1 = net
@@ -23,6 +28,7 @@ async fn generate_net(net: Arc<Network>) {
                 InputCfg::new(2, 2, -1).unwrap(),
                 InputCfg::new(1, 1, 1).unwrap(),
             ],
+            NeuronKind::Excitatory,
         )
         .await
         .unwrap();
@@ -36,6 +42,7 @@ async fn generate_net(net: Arc<Network>) {
                 InputCfg::new(1, 1, -2).unwrap(),
                 InputCfg::new(2, 2, 1).unwrap(),
             ],
+            NeuronKind::Excitatory,
         )
         .await
         .unwrap();
@@ -63,23 +70,10 @@ async fn main() {
     let net = Arc::new(Network::new().unwrap());
     generate_net(net.clone()).await;
 
-    let net_clone = net.clone();
-
-    let _t1 = task::spawn(async move {
-        let zero_signal = net_clone.get_output_receiver(0).await.unwrap();
-        let mut rx = zero_signal.write().await;
-        while let Ok(signal) = rx.recv().await {
-            println!("-+= 0 =+- ({})", signal);
-        }
-    });
-
-    let net_clone = net.clone();
-
-    let _t2 = task::spawn(async move {
-        let one_signal = net_clone.get_output_receiver(1).await.unwrap();
-        let mut rx = one_signal.write().await;
-        while let Ok(signal) = rx.recv().await {
-            println!("-+= 1 =+- ({})", signal);
+    let mut output = net.output_stream().await.unwrap();
+    let _t = task::spawn(async move {
+        while let Some((port, signal)) = output.next().await {
+            println!("-+= {} =+- ({})", port, signal);
         }
     });
 
@@ -0,0 +1,295 @@
+//! Command-line runner of networks described by configs.
+//!
+//! Configs are NetworkCfg files in yaml (.yaml, .yml) or json format. Every line
+//! of the input file is the json array of signals: signal i goes into input port i.
+//! Output signals and statuses are printed as json lines.
+
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+use librunen::rnn::analysis::comparison::OutputEvent;
+use librunen::rnn::analysis::doctor::{self, DoctorReport};
+use librunen::rnn::common::network_cfg::NetworkCfg;
+use librunen::rnn::common::signal::Signal;
+use librunen::rnn::layouts::network::{MonitoringMode, Network};
+use serde::Serialize;
+use tokio::time::{self, Instant};
+use tokio_stream::{Stream, StreamExt};
+
+static USAGE: &str = "Usage:
+    runen validate <config>
+    runen run --config <config> [--input <signals.jsonl>] [--settle <ms>]
+    runen export-dot --config <config> [--output <file.dot>]
+    runen monitor --config <config> [--input <signals.jsonl>] [--settle <ms>]
+
+run      prints output signals, monitor prints statuses of neurons and ports.
+Both stop when inputs are sent and the network is silent for the settle period
+(100 ms by default), or on Ctrl-C.";
+
+/// The default period of silence after inputs which ends the run, ms.
+static DEFAULT_SETTLE: u64 = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    Validate {
+        config: PathBuf,
+    },
+    Run {
+        config: PathBuf,
+        input: Option<PathBuf>,
+        settle: Duration,
+    },
+    ExportDot {
+        config: PathBuf,
+        output: Option<PathBuf>,
+    },
+    Monitor {
+        config: PathBuf,
+        input: Option<PathBuf>,
+        settle: Duration,
+    },
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let (name, rest) = args.split_first().ok_or("missing command")?;
+
+    let mut positional = vec![];
+    let mut config = None;
+    let mut input = None;
+    let mut output = None;
+    let mut settle = Duration::from_millis(DEFAULT_SETTLE);
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        let mut value = || {
+            rest.next()
+                .cloned()
+                .ok_or_else(|| format!("missing value of {arg}"))
+        };
+        match arg.as_str() {
+            "--config" => config = Some(PathBuf::from(value()?)),
+            "--input" => input = Some(PathBuf::from(value()?)),
+            "--output" => output = Some(PathBuf::from(value()?)),
+            "--settle" => {
+                let ms = value()?
+                    .parse()
+                    .map_err(|_| format!("{arg} expects milliseconds"))?;
+                settle = Duration::from_millis(ms);
+            }
+            flag if flag.starts_with("--") => return Err(format!("unknown option {flag}")),
+            _ => positional.push(PathBuf::from(arg)),
+        }
+    }
+    let config = config
+        .or_else(|| positional.pop())
+        .ok_or("missing config")?;
+
+    match name.as_str() {
+        "validate" => Ok(Command::Validate { config }),
+        "run" => Ok(Command::Run {
+            config,
+            input,
+            settle,
+        }),
+        "export-dot" => Ok(Command::ExportDot { config, output }),
+        "monitor" => Ok(Command::Monitor {
+            config,
+            input,
+            settle,
+        }),
+        _ => Err(format!("unknown command {name}")),
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match execute(command).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn execute(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Validate { config } => {
+            let config = load_config(&config)?;
+            let report = DoctorReport::new(doctor::lint(&config, "M0"));
+            for finding in report.findings.iter() {
+                println!("{finding}");
+            }
+            if !report.is_healthy() {
+                return Err("config has errors".into());
+            }
+            println!(
+                "ok: {} neurons, {} links",
+                config.neurons.len(),
+                config.links.len()
+            );
+        }
+        Command::ExportDot { config, output } => {
+            let dot = load_config(&config)?.to_dot();
+            match output {
+                Some(path) => fs::write(path, dot)?,
+                None => print!("{dot}"),
+            }
+        }
+        Command::Run {
+            config,
+            input,
+            settle,
+        } => {
+            let network = start(&config).await?;
+            let clock = network.get_clock();
+            let outputs = network
+                .output_stream()
+                .await?
+                .map(move |(port, signal)| OutputEvent {
+                    timestamp: clock.now(),
+                    port,
+                    signal,
+                });
+            drive(&network, read_inputs(input.as_deref())?, outputs, settle).await?;
+        }
+        Command::Monitor {
+            config,
+            input,
+            settle,
+        } => {
+            let network = start(&config).await?;
+            network
+                .set_monitoring_mode(MonitoringMode::Monitoring)
+                .await;
+            let statuses = network.monitoring_stream();
+            drive(&network, read_inputs(input.as_deref())?, statuses, settle).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Read NetworkCfg from yaml or json file by its extension.
+fn load_config(path: &Path) -> Result<NetworkCfg, Box<dyn Error>> {
+    let file = File::open(path)?;
+    let config = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml" | "yml") => serde_yaml::from_reader(file)?,
+        _ => serde_json::from_reader(file)?,
+    };
+    Ok(config)
+}
+
+/// Signals of input ports by line. No inputs without the file.
+fn read_inputs(path: Option<&Path>) -> Result<Vec<Vec<Signal>>, Box<dyn Error>> {
+    let Some(path) = path else {
+        return Ok(vec![]);
+    };
+    let mut inputs = vec![];
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            inputs.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(inputs)
+}
+
+async fn start(config: &Path) -> Result<Arc<Network>, Box<dyn Error>> {
+    let config = load_config(config)?;
+    let network = Arc::new(Network::new()?);
+    network.restore_config(network.clone(), &config).await?;
+    Ok(network)
+}
+
+/// Send inputs one by one and print items of the stream as json lines until
+/// the stream is silent for the settle period after the last input.
+async fn drive<T: Serialize>(
+    network: &Network,
+    inputs: Vec<Vec<Signal>>,
+    mut stream: impl Stream<Item = T> + Unpin,
+    settle: Duration,
+) -> Result<(), Box<dyn Error>> {
+    let mut inputs = inputs.into_iter();
+    let mut inputs_ended = false;
+    let mut deadline = Instant::now() + settle;
+    loop {
+        if !inputs_ended {
+            match inputs.next() {
+                Some(signals) => network.input_vec(&signals).await?,
+                None => {
+                    inputs_ended = true;
+                    deadline = Instant::now() + settle;
+                }
+            }
+        }
+        tokio::select! {
+            biased;
+            _ = tokio::signal::ctrl_c() => break,
+            Some(item) = stream.next() => {
+                println!("{}", serde_json::to_string(&item)?);
+                deadline = Instant::now() + settle;
+            }
+            _ = time::sleep_until(deadline), if inputs_ended => break,
+            _ = std::future::ready(()), if !inputs_ended => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn should_parse_commands_with_options() {
+        assert_eq!(
+            parse_args(&args("validate net.yaml")),
+            Ok(Command::Validate {
+                config: PathBuf::from("net.yaml")
+            })
+        );
+        assert_eq!(
+            parse_args(&args(
+                "run --config net.yaml --input data.jsonl --settle 20"
+            )),
+            Ok(Command::Run {
+                config: PathBuf::from("net.yaml"),
+                input: Some(PathBuf::from("data.jsonl")),
+                settle: Duration::from_millis(20),
+            })
+        );
+        assert_eq!(
+            parse_args(&args("export-dot --config net.json")),
+            Ok(Command::ExportDot {
+                config: PathBuf::from("net.json"),
+                output: None,
+            })
+        );
+    }
+
+    #[test]
+    fn should_reject_incomplete_commands() {
+        assert!(parse_args(&[]).is_err());
+        assert!(parse_args(&args("run")).is_err());
+        assert!(parse_args(&args("run --config")).is_err());
+        assert!(parse_args(&args("run --config net.yaml --settle soon")).is_err());
+        assert!(parse_args(&args("train net.yaml")).is_err());
+    }
+}
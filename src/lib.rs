@@ -4,3 +4,5 @@
 //!
 
 pub mod rnn;
+
+pub use rnn::common::capabilities::capabilities;
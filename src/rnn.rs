@@ -1,4 +1,23 @@
+pub mod analysis;
 pub mod common;
+pub mod experiments;
+pub mod io;
 pub mod layouts;
 pub mod neural;
 pub mod tests;
+pub mod training;
+
+#[cfg(feature = "http")]
+pub mod http;
+
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+
+#[cfg(feature = "tui")]
+pub mod tui;
+
+#[cfg(feature = "viz")]
+pub mod viz;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,12 @@
+pub mod comparison;
+pub mod doctor;
+pub mod drawing;
+pub mod probe;
+pub mod raster;
+pub mod spectral;
+pub mod structure;
+pub mod tuning;
+pub mod weights;
+
+#[cfg(feature = "nwb")]
+pub mod nwb;
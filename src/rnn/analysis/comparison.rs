@@ -0,0 +1,217 @@
+//! A/B comparison of two networks under identical stimuli: every stimulus is sent
+//! into the same input port of both networks, signals of their output ports are
+//! collected with timestamps and compared (e.g. the network before and after pruning).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::time::{self, Instant};
+use tokio_stream::{Stream, StreamExt};
+
+use crate::rnn::common::signal::Signal;
+use crate::rnn::layouts::network::Network;
+
+/// The signal of the output port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputEvent {
+    pub timestamp: DateTime<Utc>,
+    pub port: usize,
+    pub signal: Signal,
+}
+
+/// Outputs of both networks in order of arrival.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Comparison {
+    pub a: Vec<OutputEvent>,
+    pub b: Vec<OutputEvent>,
+}
+
+impl Comparison {
+    /// The number of signals of B minus the number of signals of A by port.
+    pub fn count_difference(&self) -> BTreeMap<usize, i64> {
+        let mut difference = BTreeMap::new();
+        for event in self.a.iter() {
+            *difference.entry(event.port).or_default() -= 1;
+        }
+        for event in self.b.iter() {
+            *difference.entry(event.port).or_default() += 1;
+        }
+        difference
+    }
+
+    /// The fraction of (port, time bin) pairs with signals in one network only
+    /// among pairs with signals in any of them: 0.0 for the same timing,
+    /// 1.0 for disjoint outputs. Bins start at the earliest signal of both networks.
+    pub fn divergence(&self, bin: Duration) -> f64 {
+        let Some(start) = self
+            .a
+            .iter()
+            .chain(self.b.iter())
+            .map(|e| e.timestamp)
+            .min()
+        else {
+            return 0.0;
+        };
+        let bin_nanos = bin.as_nanos().max(1);
+        let bins = |events: &[OutputEvent]| -> BTreeSet<(usize, u128)> {
+            events
+                .iter()
+                .map(|event| {
+                    let offset = (event.timestamp - start).to_std().unwrap_or_default();
+                    (event.port, offset.as_nanos() / bin_nanos)
+                })
+                .collect()
+        };
+        let (bins_a, bins_b) = (bins(&self.a), bins(&self.b));
+        let union = bins_a.union(&bins_b).count();
+        let differing = bins_a.symmetric_difference(&bins_b).count();
+        differing as f64 / union as f64
+    }
+
+    /// The mean delay of B against A over signals paired by port and order of arrival.
+    /// None when there are no pairs.
+    pub fn mean_delay(&self) -> Option<TimeDelta> {
+        let by_port = |events: &[OutputEvent]| {
+            let mut by_port: BTreeMap<usize, Vec<DateTime<Utc>>> = BTreeMap::new();
+            for event in events {
+                by_port.entry(event.port).or_default().push(event.timestamp);
+            }
+            by_port
+        };
+        let (ports_a, ports_b) = (by_port(&self.a), by_port(&self.b));
+        let delays: Vec<TimeDelta> = ports_a
+            .iter()
+            .filter_map(|(port, times_a)| ports_b.get(port).map(|times_b| (times_a, times_b)))
+            .flat_map(|(times_a, times_b)| times_a.iter().zip(times_b.iter()))
+            .map(|(time_a, time_b)| *time_b - *time_a)
+            .collect();
+        if delays.is_empty() {
+            return None;
+        }
+        let total: TimeDelta = delays.iter().copied().sum();
+        Some(total / delays.len() as i32)
+    }
+}
+
+/// Send every stimulus (port, signal) into both networks and collect their outputs
+/// until the stimuli end and the networks stay silent for `settle` period.
+/// Only output ports connected before the call are observed.
+pub async fn compare<S>(
+    a: &Network,
+    b: &Network,
+    mut stimuli: S,
+    settle: Duration,
+) -> Result<Comparison, Box<dyn Error>>
+where
+    S: Stream<Item = (usize, Signal)> + Unpin,
+{
+    let mut outputs_a = a.output_stream().await?;
+    let mut outputs_b = b.output_stream().await?;
+    let (clock_a, clock_b) = (a.get_clock(), b.get_clock());
+    let mut comparison = Comparison::default();
+    let mut stimuli_ended = false;
+    let mut deadline = Instant::now() + settle;
+
+    loop {
+        tokio::select! {
+            stimulus = stimuli.next(), if !stimuli_ended => match stimulus {
+                Some((port, signal)) => {
+                    a.input(signal, port).await?;
+                    b.input(signal, port).await?;
+                }
+                None => {
+                    stimuli_ended = true;
+                    deadline = Instant::now() + settle;
+                }
+            },
+            Some((port, signal)) = outputs_a.next() => {
+                comparison.a.push(OutputEvent { timestamp: clock_a.now(), port, signal });
+                deadline = Instant::now() + settle;
+            }
+            Some((port, signal)) = outputs_b.next() => {
+                comparison.b.push(OutputEvent { timestamp: clock_b.now(), port, signal });
+                deadline = Instant::now() + settle;
+            }
+            _ = time::sleep_until(deadline), if stimuli_ended => break,
+        }
+    }
+
+    Ok(comparison)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+
+    fn event(ms: i64, port: usize) -> OutputEvent {
+        OutputEvent {
+            timestamp: DateTime::<Utc>::UNIX_EPOCH + TimeDelta::milliseconds(ms),
+            port,
+            signal: 1,
+        }
+    }
+
+    async fn relay_fixture() -> Arc<Network> {
+        let net = Arc::new(new_network_fixture());
+        let id = net
+            .create_neuron(
+                net.clone(),
+                1,
+                vec![InputCfg::new(10, 10, 1).unwrap()],
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap()
+            .get_id();
+        assert!(net.setup_input(0, &id, 0).await.is_ok());
+        assert!(net.setup_output(0, &id).await.is_ok());
+        net
+    }
+
+    #[test]
+    fn metrics_should_compare_timing_of_outputs() {
+        let comparison = Comparison {
+            a: vec![event(0, 0), event(10, 0)],
+            b: vec![event(12, 0), event(25, 0), event(3, 1)],
+        };
+
+        assert_eq!(
+            comparison.count_difference(),
+            BTreeMap::from([(0, 0), (1, 1)])
+        );
+        // bins of A: (0, 0), (0, 1); bins of B: (0, 1), (0, 2), (1, 0)
+        assert_eq!(comparison.divergence(Duration::from_millis(10)), 0.75);
+        assert_eq!(
+            comparison.mean_delay(),
+            Some(TimeDelta::microseconds(13_500))
+        );
+        assert_eq!(
+            Comparison::default().divergence(Duration::from_millis(10)),
+            0.0
+        );
+        assert_eq!(Comparison::default().mean_delay(), None);
+    }
+
+    #[tokio::test]
+    async fn same_networks_should_produce_the_same_signals() {
+        let (a, b) = (relay_fixture().await, relay_fixture().await);
+        let stimuli = tokio_stream::iter(vec![(0, 2), (0, 3)]);
+
+        let comparison = compare(&a, &b, stimuli, Duration::from_millis(50))
+            .await
+            .unwrap();
+
+        assert!(!comparison.a.is_empty());
+        assert_eq!(comparison.count_difference(), BTreeMap::from([(0, 0)]));
+        let signals = |events: &[OutputEvent]| events.iter().map(|e| e.signal).collect::<Vec<_>>();
+        assert_eq!(signals(&comparison.a), signals(&comparison.b));
+    }
+}
@@ -0,0 +1,228 @@
+//! Lint of the network: findings of config validation and structural analysis
+//! (see Network::doctor() which adds runtime checks of the live network).
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::analysis::structure;
+use crate::rnn::common::network_cfg::{LinkCfg, NetworkCfg};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Severity {
+    /// Noticeable but legal: recurrent loops, free dendrites.
+    Info,
+
+    /// The network works but probably not as intended.
+    Warning,
+
+    /// Signals are lost or the config can not be installed.
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Finding {
+    pub severity: Severity,
+
+    /// The id of the neuron or port, or the network id for network wide findings.
+    pub subject: String,
+
+    pub message: String,
+}
+
+impl Finding {
+    pub fn new(severity: Severity, subject: &str, message: &str) -> Self {
+        Finding {
+            severity,
+            subject: subject.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {}: {}", self.severity, self.subject, self.message)
+    }
+}
+
+/// Findings ordered from the most severe.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DoctorReport {
+    pub findings: Vec<Finding>,
+}
+
+impl DoctorReport {
+    pub fn new(mut findings: Vec<Finding>) -> Self {
+        findings.sort_by(|a, b| b.severity.cmp(&a.severity));
+        DoctorReport { findings }
+    }
+
+    /// No findings of Error severity.
+    pub fn is_healthy(&self) -> bool {
+        self.count(Severity::Error) == 0
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity == severity)
+            .count()
+    }
+
+    /// Findings of the severity and more severe ones.
+    pub fn at_least(&self, severity: Severity) -> Vec<&Finding> {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity >= severity)
+            .collect()
+    }
+}
+
+/// Findings of the config alone: validation errors, dead-end axons, neurons
+/// unreachable from input ports, recurrent loops and free dendrites.
+pub fn lint(config: &NetworkCfg, network_id: &str) -> Vec<Finding> {
+    let mut findings = vec![];
+    if let Err(err) = config.validate() {
+        findings.push(Finding::new(
+            Severity::Error,
+            network_id,
+            &format!("invalid config: {err}"),
+        ));
+        return findings;
+    }
+
+    match structure::analyze(config) {
+        Ok(report) => {
+            for id in report.dead_ends.iter() {
+                findings.push(Finding::new(
+                    Severity::Error,
+                    id,
+                    "axon is connected neither to neurons nor to output ports",
+                ));
+            }
+            for id in report.unreachable.iter() {
+                findings.push(Finding::new(
+                    Severity::Warning,
+                    id,
+                    "no path from input ports",
+                ));
+            }
+            for cycle in report.cycles.iter() {
+                findings.push(Finding::new(
+                    Severity::Info,
+                    &cycle[0],
+                    &format!("recurrent loop {}", cycle.join(" -> ")),
+                ));
+            }
+        }
+        Err(err) => findings.push(Finding::new(
+            Severity::Error,
+            network_id,
+            &format!("structure analysis failed: {err}"),
+        )),
+    }
+
+    for neuron in config.neurons.iter() {
+        let connected = config
+            .links
+            .iter()
+            .filter(|link| match link {
+                LinkCfg::Input { dst_id, .. } | LinkCfg::Inner { dst_id, .. } => {
+                    *dst_id == neuron.id
+                }
+                LinkCfg::Output { .. } => false,
+            })
+            .count();
+        let free = neuron.input_configs.len().saturating_sub(connected);
+        if free > 0 {
+            findings.push(Finding::new(
+                Severity::Info,
+                &neuron.id,
+                &format!(
+                    "{free} of {} dendrites are not connected",
+                    neuron.input_configs.len()
+                ),
+            ));
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::{LabelMap, NeuronCfg, NeuronKind};
+
+    /// I0 -> Z0 -> O0, Z0 -> Z1 (dead end) with the free dendrite of Z1
+    fn config_fixture() -> NetworkCfg {
+        NetworkCfg {
+            inputs: 1,
+            outputs: 1,
+            neurons: vec![
+                NeuronCfg {
+                    id: String::from("M0Z0"),
+                    bias: 1,
+                    input_configs: vec![InputCfg::new(1, 1, 1).unwrap()],
+                    lineage: None,
+                    kind: NeuronKind::Excitatory,
+                },
+                NeuronCfg {
+                    id: String::from("M0Z1"),
+                    bias: 1,
+                    input_configs: vec![InputCfg::new(1, 1, 1).unwrap(); 2],
+                    lineage: None,
+                    kind: NeuronKind::Excitatory,
+                },
+            ],
+            links: vec![
+                LinkCfg::Input {
+                    input_port: 0,
+                    dst_id: String::from("M0Z0"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Inner {
+                    src_id: String::from("M0Z0"),
+                    dst_id: String::from("M0Z1"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Output {
+                    src_id: String::from("M0Z0"),
+                    output_port: 0,
+                },
+            ],
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn lint_should_report_dead_ends_and_free_dendrites() {
+        let report = DoctorReport::new(lint(&config_fixture(), "M0"));
+
+        assert!(!report.is_healthy());
+        assert_eq!(report.findings[0].severity, Severity::Error);
+        assert_eq!(report.findings[0].subject, "M0Z1");
+        assert_eq!(report.count(Severity::Info), 1);
+        assert_eq!(report.at_least(Severity::Warning).len(), 1);
+    }
+
+    #[test]
+    fn lint_should_stop_at_invalid_config() {
+        let mut config = config_fixture();
+        config.links.push(LinkCfg::Output {
+            src_id: String::from("M0Z9"),
+            output_port: 0,
+        });
+
+        let findings = lint(&config, "M0");
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].subject, "M0");
+        assert!(findings[0].message.starts_with("invalid config"));
+    }
+}
@@ -0,0 +1,257 @@
+//! Coordinates of network's ports and neurons for external visualizers.
+//! The layered layout is deterministic, so the same topology is drawn
+//! in the same way across sessions.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::network_cfg::{LinkCfg, NetworkCfg};
+
+/// The distance between neighbour layers and neighbour nodes within the layer.
+static SPACING: f64 = 1.0;
+
+/// The element of the network drawing.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Node {
+    Input(usize),
+    Neuron(String),
+    Output(usize),
+}
+
+/// Ports are named like I0 and O0, neurons by their ids.
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Node::Input(port) => write!(f, "I{port}"),
+            Node::Neuron(id) => write!(f, "{id}"),
+            Node::Output(port) => write!(f, "O{port}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodePosition {
+    pub node: Node,
+
+    /// The layer index multiplied by spacing
+    pub x: f64,
+
+    /// The shift from the layer center
+    pub y: f64,
+}
+
+/// Positions of all network's nodes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DrawingLayout {
+    pub positions: Vec<NodePosition>,
+}
+
+impl DrawingLayout {
+    pub fn get_position(&self, node: &Node) -> Option<(f64, f64)> {
+        self.positions
+            .iter()
+            .find(|position| position.node == *node)
+            .map(|position| (position.x, position.y))
+    }
+}
+
+/// Topology with its layout as it is exported to visualizers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Drawing {
+    pub topology: NetworkCfg,
+    pub layout: DrawingLayout,
+}
+
+impl Drawing {
+    pub fn new(topology: NetworkCfg) -> Self {
+        let layout = layered_layout(&topology);
+        Drawing { topology, layout }
+    }
+
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+/// Place input ports on the first layer, every neuron on the layer next to its
+/// shortest path from input ports and output ports on the last layer.
+/// Neurons unreachable from inputs are placed on the layer before outputs.
+/// Nodes are ordered by port index or neuron id within the layer.
+pub fn layered_layout(config: &NetworkCfg) -> DrawingLayout {
+    let mut successors: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let mut depths: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut queue: VecDeque<&str> = VecDeque::new();
+    let mut input_ports = vec![];
+    let mut output_ports = vec![];
+
+    for link in config.links.iter() {
+        match link {
+            LinkCfg::Input {
+                input_port, dst_id, ..
+            } => {
+                input_ports.push(*input_port);
+                if depths.insert(dst_id, 1).is_none() {
+                    queue.push_back(dst_id);
+                }
+            }
+            LinkCfg::Inner { src_id, dst_id, .. } => {
+                successors.entry(src_id).or_default().push(dst_id);
+            }
+            LinkCfg::Output { output_port, .. } => output_ports.push(*output_port),
+        }
+    }
+    input_ports.extend(0..config.inputs);
+    output_ports.extend(0..config.outputs);
+
+    while let Some(id) = queue.pop_front() {
+        let depth = depths[id];
+        for successor in successors.get(id).into_iter().flatten() {
+            if !depths.contains_key(successor) {
+                depths.insert(successor, depth + 1);
+                queue.push_back(successor);
+            }
+        }
+    }
+
+    // neurons unreachable from inputs share the last layer of neurons
+    let last_depth = depths.values().copied().max().unwrap_or(1);
+    let mut layers: BTreeMap<usize, Vec<Node>> = BTreeMap::new();
+    for neuron in config.neurons.iter() {
+        let depth = depths
+            .get(neuron.id.as_str())
+            .copied()
+            .unwrap_or(last_depth);
+        layers
+            .entry(depth)
+            .or_default()
+            .push(Node::Neuron(neuron.id.clone()));
+    }
+
+    input_ports.sort();
+    input_ports.dedup();
+    output_ports.sort();
+    output_ports.dedup();
+    layers.insert(0, input_ports.into_iter().map(Node::Input).collect());
+    layers.insert(
+        last_depth + 1,
+        output_ports.into_iter().map(Node::Output).collect(),
+    );
+
+    let positions = layers
+        .into_iter()
+        .flat_map(|(depth, mut nodes)| {
+            nodes.sort();
+            let center = (nodes.len() as f64 - 1.0) / 2.0;
+            nodes
+                .into_iter()
+                .enumerate()
+                .map(move |(idx, node)| NodePosition {
+                    node,
+                    x: depth as f64 * SPACING,
+                    y: (idx as f64 - center) * SPACING,
+                })
+        })
+        .collect();
+
+    DrawingLayout { positions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::{LabelMap, NeuronCfg, NeuronKind};
+
+    fn neuron_cfg(id: &str) -> NeuronCfg {
+        NeuronCfg {
+            id: id.to_string(),
+            bias: 1,
+            input_configs: vec![InputCfg::new(1, 1, 1).unwrap()],
+            lineage: None,
+            kind: NeuronKind::Excitatory,
+        }
+    }
+
+    /// I0 -> Z0 -> Z1, Z0 -> Z2, Z1 -> O0, Z2 -> O1 and unreachable Z3
+    fn config_fixture() -> NetworkCfg {
+        NetworkCfg {
+            inputs: 1,
+            outputs: 2,
+            neurons: ["M0Z0", "M0Z1", "M0Z2", "M0Z3"]
+                .into_iter()
+                .map(neuron_cfg)
+                .collect(),
+            links: vec![
+                LinkCfg::Input {
+                    input_port: 0,
+                    dst_id: String::from("M0Z0"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Inner {
+                    src_id: String::from("M0Z0"),
+                    dst_id: String::from("M0Z1"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Inner {
+                    src_id: String::from("M0Z0"),
+                    dst_id: String::from("M0Z2"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Output {
+                    src_id: String::from("M0Z1"),
+                    output_port: 0,
+                },
+                LinkCfg::Output {
+                    src_id: String::from("M0Z2"),
+                    output_port: 1,
+                },
+            ],
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn should_place_nodes_by_layers() {
+        let layout = layered_layout(&config_fixture());
+
+        assert_eq!(layout.positions.len(), 7);
+        assert_eq!(layout.get_position(&Node::Input(0)), Some((0.0, 0.0)));
+        assert_eq!(
+            layout.get_position(&Node::Neuron(String::from("M0Z0"))),
+            Some((1.0, 0.0))
+        );
+        assert_eq!(
+            layout.get_position(&Node::Neuron(String::from("M0Z1"))),
+            Some((2.0, -1.0))
+        );
+        assert_eq!(
+            layout.get_position(&Node::Neuron(String::from("M0Z3"))),
+            Some((2.0, 1.0))
+        );
+        assert_eq!(layout.get_position(&Node::Output(1)), Some((3.0, 0.5)));
+    }
+
+    #[test]
+    fn layout_should_not_depend_on_links_order() {
+        let mut config = config_fixture();
+        config.links.reverse();
+
+        assert_eq!(layered_layout(&config), layered_layout(&config_fixture()));
+    }
+
+    #[test]
+    fn drawing_should_survive_json_round_trip() {
+        let drawing = Drawing::new(config_fixture());
+
+        let drawing_json = drawing.to_json().unwrap();
+        let restored: Drawing = serde_json::from_str(&drawing_json).unwrap();
+
+        assert_eq!(restored, drawing);
+    }
+}
@@ -0,0 +1,227 @@
+//! Export of recordings in the layout following NeuroData Without Borders, so spike
+//! trains and probe series can be loaded by pandas, pynwb or NEO converters.
+//! The session directory holds:
+//!
+//! | File                  | Columns                                      |
+//! |-----------------------|----------------------------------------------|
+//! | session.json          | SessionInfo                                  |
+//! | units.parquet         | unit_id, spike_time                          |
+//! | probes.parquet        | unit_id, timestamp, accumulator, firing_rate |
+//! | probe_weights.parquet | unit_id, timestamp, port, weight             |
+//!
+//! Units are neurons, times are seconds since the session start as in NWB.
+
+use std::error::Error;
+use std::fs::{self, File};
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Float64Array, Int16Array, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{Field, Schema};
+use chrono::{DateTime, Utc};
+use parquet::arrow::ArrowWriter;
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::analysis::probe::ProbeSeries;
+use crate::rnn::analysis::raster::Raster;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    pub identifier: String,
+    pub session_description: String,
+    pub session_start_time: DateTime<Utc>,
+}
+
+/// The content of session.json.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionInfo {
+    #[serde(flatten)]
+    pub session: Session,
+
+    /// Ids of neurons with spikes or probe series
+    pub units: Vec<String>,
+
+    /// The crate version which wrote the session
+    pub source: String,
+}
+
+/// Write spikes of the raster and probe series into the session directory.
+pub fn export(
+    dir: &Path,
+    session: &Session,
+    raster: &Raster,
+    probes: &ProbeSeries,
+) -> Result<SessionInfo, Box<dyn Error>> {
+    fs::create_dir_all(dir)?;
+    let seconds = |timestamp: &DateTime<Utc>| {
+        (*timestamp - session.session_start_time)
+            .num_microseconds()
+            .unwrap_or(i64::MAX) as f64
+            / 1e6
+    };
+
+    let (mut unit_ids, mut spike_times) = (vec![], vec![]);
+    for id in raster.get_ids() {
+        for timestamp in raster.get_spikes(&id) {
+            unit_ids.push(id.clone());
+            spike_times.push(seconds(timestamp));
+        }
+    }
+    write_table(
+        &dir.join("units.parquet"),
+        vec![
+            ("unit_id", Arc::new(StringArray::from(unit_ids)) as ArrayRef),
+            (
+                "spike_time",
+                Arc::new(Float64Array::from(spike_times)) as ArrayRef,
+            ),
+        ],
+    )?;
+
+    let (mut unit_ids, mut timestamps, mut accumulators, mut firing_rates) =
+        (vec![], vec![], vec![], vec![]);
+    let (mut weight_ids, mut weight_timestamps, mut ports, mut weights) =
+        (vec![], vec![], vec![], vec![]);
+    for (id, points) in probes.iter() {
+        for point in points {
+            unit_ids.push(id.clone());
+            timestamps.push(seconds(&point.timestamp));
+            accumulators.push(point.accumulator);
+            firing_rates.push(point.firing_rate);
+            for (port, weight) in point.weights.iter().enumerate() {
+                weight_ids.push(id.clone());
+                weight_timestamps.push(seconds(&point.timestamp));
+                ports.push(port as u64);
+                weights.push(*weight);
+            }
+        }
+    }
+    write_table(
+        &dir.join("probes.parquet"),
+        vec![
+            ("unit_id", Arc::new(StringArray::from(unit_ids)) as ArrayRef),
+            (
+                "timestamp",
+                Arc::new(Float64Array::from(timestamps)) as ArrayRef,
+            ),
+            (
+                "accumulator",
+                Arc::new(Int16Array::from(accumulators)) as ArrayRef,
+            ),
+            (
+                "firing_rate",
+                Arc::new(Float64Array::from(firing_rates)) as ArrayRef,
+            ),
+        ],
+    )?;
+    write_table(
+        &dir.join("probe_weights.parquet"),
+        vec![
+            (
+                "unit_id",
+                Arc::new(StringArray::from(weight_ids)) as ArrayRef,
+            ),
+            (
+                "timestamp",
+                Arc::new(Float64Array::from(weight_timestamps)) as ArrayRef,
+            ),
+            ("port", Arc::new(UInt64Array::from(ports)) as ArrayRef),
+            ("weight", Arc::new(Int16Array::from(weights)) as ArrayRef),
+        ],
+    )?;
+
+    let mut units = raster.get_ids();
+    units.extend(probes.keys().cloned());
+    units.sort();
+    units.dedup();
+    let info = SessionInfo {
+        session: session.clone(),
+        units,
+        source: format!("runen {}", env!("CARGO_PKG_VERSION")),
+    };
+    serde_json::to_writer_pretty(File::create(dir.join("session.json"))?, &info)?;
+    Ok(info)
+}
+
+fn write_table(path: &Path, columns: Vec<(&str, ArrayRef)>) -> Result<(), Box<dyn Error>> {
+    let schema = Arc::new(Schema::new(
+        columns
+            .iter()
+            .map(|(name, column)| Field::new(*name, column.data_type().clone(), false))
+            .collect::<Vec<Field>>(),
+    ));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        columns.into_iter().map(|(_, column)| column).collect(),
+    )?;
+    let mut writer = ArrowWriter::try_new(File::create(path)?, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use arrow_array::Array;
+    use chrono::TimeDelta;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+    use crate::rnn::analysis::probe::ProbePoint;
+
+    fn read_table(path: &Path) -> RecordBatch {
+        let mut reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        reader.next().unwrap().unwrap()
+    }
+
+    #[test]
+    fn recordings_should_be_exported_as_session_tables() {
+        let start = DateTime::<Utc>::UNIX_EPOCH;
+        let mut raster = Raster::default();
+        raster.record("M0Z1", start + TimeDelta::milliseconds(250));
+        raster.record("M0Z1", start + TimeDelta::milliseconds(500));
+        let probes: ProbeSeries = BTreeMap::from([(
+            String::from("M0Z2"),
+            vec![ProbePoint {
+                timestamp: start + TimeDelta::seconds(1),
+                accumulator: -3,
+                firing_rate: 2.0,
+                weights: vec![4, 5],
+            }],
+        )]);
+        let session = Session {
+            identifier: String::from("relay"),
+            session_description: String::from("two spikes"),
+            session_start_time: start,
+        };
+        let dir = std::env::temp_dir().join(format!("runen-nwb-{}", std::process::id()));
+
+        let info = export(&dir, &session, &raster, &probes).unwrap();
+
+        assert_eq!(info.units, vec!["M0Z1", "M0Z2"]);
+        let units = read_table(&dir.join("units.parquet"));
+        assert_eq!(units.num_rows(), 2);
+        let spike_times = units
+            .column_by_name("spike_time")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(spike_times.values().to_vec(), vec![0.25, 0.5]);
+        let weights = read_table(&dir.join("probe_weights.parquet"));
+        assert_eq!(weights.num_rows(), 2);
+        assert_eq!(
+            weights.schema().field(2).data_type(),
+            &arrow_schema::DataType::UInt64
+        );
+        let saved: SessionInfo =
+            serde_json::from_reader(File::open(dir.join("session.json")).unwrap()).unwrap();
+        assert_eq!(saved, info);
+        let _remove_result = fs::remove_dir_all(&dir);
+    }
+}
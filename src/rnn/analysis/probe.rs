@@ -0,0 +1,301 @@
+//! Probes sampling the state of selected neurons at the constant interval, like
+//! the oscilloscope. Every probe runs as the tokio task asking neurons for their
+//! samples through the command channel and keeps time series for plotting.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time;
+use tokio_util::sync::CancellationToken;
+
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::signal::Weight;
+use crate::rnn::layouts::network::Network;
+
+/// The neuron state replied to NeuronCommand::GetSample.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbeSample {
+    pub timestamp: DateTime<Utc>,
+
+    /// The neuron id
+    pub id: String,
+
+    /// The accumulator value
+    pub accumulator: Weight,
+
+    /// The number of fires since the neuron start or reset.
+    pub reset_count: u64,
+
+    /// Dendrite weights ordered by port.
+    pub weights: Vec<Weight>,
+}
+
+/// The point of the neuron's series.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbePoint {
+    pub timestamp: DateTime<Utc>,
+
+    pub accumulator: Weight,
+
+    /// Fires per second since the previous point. Zero for the first point.
+    pub firing_rate: f64,
+
+    /// Dendrite weights ordered by port.
+    pub weights: Vec<Weight>,
+}
+
+impl ProbePoint {
+    /// The point of the sample following the previous sample of the same neuron.
+    pub fn from_samples(previous: Option<&ProbeSample>, sample: &ProbeSample) -> ProbePoint {
+        let firing_rate = previous
+            .map(|previous| {
+                let seconds = (sample.timestamp - previous.timestamp)
+                    .to_std()
+                    .unwrap_or_default()
+                    .as_secs_f64();
+                if seconds > 0.0 {
+                    sample.reset_count.saturating_sub(previous.reset_count) as f64 / seconds
+                } else {
+                    0.0
+                }
+            })
+            .unwrap_or(0.0);
+
+        ProbePoint {
+            timestamp: sample.timestamp,
+            accumulator: sample.accumulator,
+            firing_rate,
+            weights: sample.weights.clone(),
+        }
+    }
+}
+
+/// Series of points by neuron id.
+pub type ProbeSeries = BTreeMap<String, Vec<ProbePoint>>;
+
+/// The running probe.
+#[derive(Debug)]
+pub struct Probe {
+    series: Arc<RwLock<ProbeSeries>>,
+    cancel_token: CancellationToken,
+    task_handler: JoinHandle<()>,
+}
+
+impl Probe {
+    /// Spawn the probe sampling listed neurons after every interval until it is
+    /// stopped or the network is dropped. Neurons which do not reply (removed
+    /// ones for example) are skipped in the round.
+    pub fn spawn(
+        network: &Arc<Network>,
+        ids: &[&str],
+        interval: Duration,
+    ) -> Result<Probe, Box<dyn Error>> {
+        if interval.is_zero() {
+            return Err(Box::new(RnnError::NotSupportedArgValue));
+        }
+        let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        let series = Arc::new(RwLock::new(
+            ids.iter().map(|id| (id.clone(), vec![])).collect(),
+        ));
+        let cancel_token = CancellationToken::new();
+        let network: Weak<Network> = Arc::downgrade(network);
+
+        let series_cloned = series.clone();
+        let token_cloned = cancel_token.clone();
+        let task_handler = tokio::spawn(async move {
+            let mut previous: BTreeMap<String, ProbeSample> = BTreeMap::new();
+            let mut ticks = time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = token_cloned.cancelled() => return,
+                    _ = ticks.tick() => {}
+                }
+                let Some(network) = network.upgrade() else {
+                    return;
+                };
+                for id in ids.iter() {
+                    let Ok(sample) = network.query_sample(id).await else {
+                        continue;
+                    };
+                    let point = ProbePoint::from_samples(previous.get(id), &sample);
+                    if let Some(points) = series_cloned.write().await.get_mut(id) {
+                        points.push(point);
+                    }
+                    previous.insert(id.clone(), sample);
+                }
+            }
+        });
+
+        Ok(Probe {
+            series,
+            cancel_token,
+            task_handler,
+        })
+    }
+
+    /// All points collected so far.
+    pub async fn get_series(&self) -> ProbeSeries {
+        self.series.read().await.clone()
+    }
+
+    /// Points of the neuron collected so far.
+    pub async fn get_points(&self, id: &str) -> Vec<ProbePoint> {
+        self.series
+            .read()
+            .await
+            .get(id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub async fn accumulator_series(&self, id: &str) -> Vec<(DateTime<Utc>, Weight)> {
+        self.map_points(id, |point| point.accumulator).await
+    }
+
+    pub async fn firing_rate_series(&self, id: &str) -> Vec<(DateTime<Utc>, f64)> {
+        self.map_points(id, |point| point.firing_rate).await
+    }
+
+    /// The weight of the dendrite connected to the port. Points of the neuron
+    /// without such dendrite are skipped.
+    pub async fn weight_series(&self, id: &str, port: usize) -> Vec<(DateTime<Utc>, Weight)> {
+        self.series
+            .read()
+            .await
+            .get(id)
+            .map(|points| {
+                points
+                    .iter()
+                    .filter_map(|point| {
+                        point
+                            .weights
+                            .get(port)
+                            .map(|weight| (point.timestamp, *weight))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Drop collected points keeping the probe running.
+    pub async fn clear(&self) {
+        for points in self.series.write().await.values_mut() {
+            points.clear();
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.task_handler.is_finished()
+    }
+
+    /// Stop sampling and return all collected points.
+    pub async fn stop(self) -> Result<ProbeSeries, Box<dyn Error>> {
+        self.cancel_token.cancel();
+        self.task_handler.await?;
+        Ok(self.series.read().await.clone())
+    }
+
+    async fn map_points<T>(
+        &self,
+        id: &str,
+        value: impl Fn(&ProbePoint) -> T,
+    ) -> Vec<(DateTime<Utc>, T)> {
+        self.series
+            .read()
+            .await
+            .get(id)
+            .map(|points| {
+                points
+                    .iter()
+                    .map(|point| (point.timestamp, value(point)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+
+    fn sample_fixture(timestamp: DateTime<Utc>, reset_count: u64) -> ProbeSample {
+        ProbeSample {
+            timestamp,
+            id: "M0Z0".to_string(),
+            accumulator: 1,
+            reset_count,
+            weights: vec![3, 2],
+        }
+    }
+
+    #[test]
+    fn firing_rate_should_count_fires_per_second_between_samples() {
+        let now = Utc::now();
+        let first = sample_fixture(now, 4);
+        let second = sample_fixture(now + TimeDelta::milliseconds(500), 9);
+
+        assert_eq!(ProbePoint::from_samples(None, &first).firing_rate, 0.0);
+        assert_eq!(
+            ProbePoint::from_samples(Some(&first), &second).firing_rate,
+            10.0
+        );
+        // the neuron was reset between samples
+        assert_eq!(
+            ProbePoint::from_samples(Some(&second), &first).firing_rate,
+            0.0
+        );
+    }
+
+    #[tokio::test]
+    async fn probe_should_sample_listed_neurons_at_interval() {
+        let net = Arc::new(new_network_fixture());
+        let n = net
+            .create_neuron(
+                net.clone(),
+                5,
+                vec![InputCfg::new(3, 3, 2).unwrap()],
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap();
+        let other = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+        assert!(Probe::spawn(&net, &[&n.get_id()], Duration::ZERO).is_err());
+
+        let probe = Probe::spawn(&net, &[&n.get_id()], Duration::from_millis(10)).unwrap();
+        assert!(net.input(1, 0).await.is_ok());
+        time::sleep(Duration::from_millis(55)).await;
+
+        let weights = probe.weight_series(&n.get_id(), 0).await;
+        assert!(weights.len() >= 3);
+        assert!(weights.iter().all(|(_, weight)| *weight == 2));
+        assert!(probe.weight_series(&n.get_id(), 1).await.is_empty());
+        assert_eq!(
+            probe
+                .accumulator_series(&n.get_id())
+                .await
+                .last()
+                .unwrap()
+                .1,
+            n.sample().await.accumulator
+        );
+
+        let series = probe.stop().await.unwrap();
+        assert!(!series.contains_key(&other.get_id()));
+        assert!(series[&n.get_id()].len() >= weights.len());
+    }
+}
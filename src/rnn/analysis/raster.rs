@@ -0,0 +1,200 @@
+//! Text raster plot of spikes for headless environments: a row per neuron,
+//! a column per time bin.
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use chrono::{TimeDelta, Utc};
+//! use librunen::rnn::analysis::raster::{Raster, RasterStyle};
+//!
+//! let start = Utc::now();
+//! let mut raster = Raster::default();
+//! raster.record("M0Z0", start);
+//! raster.record("M0Z1", start + TimeDelta::milliseconds(25));
+//!
+//! let plot = raster.render(Duration::from_millis(10), RasterStyle::Ascii);
+//! assert_eq!(plot, "M0Z0 |..\nM0Z1 ..|\n");
+//! ```
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::rnn::common::status::Status;
+
+/// Characters of bins with and without spikes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RasterStyle {
+    /// `|` and `.`
+    Ascii,
+
+    /// `┃` and `·`
+    Unicode,
+}
+
+impl RasterStyle {
+    fn marks(&self) -> (char, char) {
+        match self {
+            RasterStyle::Ascii => ('|', '.'),
+            RasterStyle::Unicode => ('┃', '·'),
+        }
+    }
+}
+
+/// Recorded spikes of neurons.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Raster {
+    /// Times of spikes by neuron id
+    spikes: BTreeMap<String, Vec<DateTime<Utc>>>,
+
+    /// The latest known number of fires by neuron id (see apply())
+    reset_counts: BTreeMap<String, u64>,
+}
+
+impl Raster {
+    pub fn record(&mut self, id: &str, timestamp: DateTime<Utc>) {
+        self.spikes
+            .entry(id.to_string())
+            .or_default()
+            .push(timestamp);
+    }
+
+    /// Record the spike when the number of the neuron's fires grows since its
+    /// previous status. The first status of the neuron only sets the base.
+    pub fn apply(&mut self, status: &Status) {
+        if let Status::Neuron(info) = status {
+            let previous = self.reset_counts.insert(info.id.clone(), info.reset_count);
+            if previous.is_some_and(|count| count < info.reset_count) {
+                self.record(&info.id, info.timestamp);
+            }
+        }
+    }
+
+    pub fn from_statuses(statuses: &[Status]) -> Self {
+        let mut raster = Raster::default();
+        for status in statuses {
+            raster.apply(status);
+        }
+        raster
+    }
+
+    /// Ids of recorded neurons in order of ids.
+    pub fn get_ids(&self) -> Vec<String> {
+        self.spikes.keys().cloned().collect()
+    }
+
+    pub fn get_spikes(&self, id: &str) -> &[DateTime<Utc>] {
+        self.spikes.get(id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Rows of neurons sorted by id with ids padded to the same width.
+    /// Columns start at the earliest spike and end with the bin of the latest one.
+    pub fn render(&self, bin: Duration, style: RasterStyle) -> String {
+        let (spike_mark, empty_mark) = style.marks();
+        let Some(start) = self.spikes.values().flatten().min() else {
+            return String::new();
+        };
+        let bin_nanos = bin.as_nanos().max(1);
+        let bin_of = |timestamp: &DateTime<Utc>| {
+            let offset = (*timestamp - *start).to_std().unwrap_or_default();
+            (offset.as_nanos() / bin_nanos) as usize
+        };
+        let columns = self
+            .spikes
+            .values()
+            .flatten()
+            .map(bin_of)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let width = self
+            .spikes
+            .keys()
+            .map(|id| id.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut plot = String::new();
+        for (id, spikes) in self.spikes.iter() {
+            let mut row = vec![empty_mark; columns];
+            for timestamp in spikes {
+                row[bin_of(timestamp)] = spike_mark;
+            }
+            plot += &format!("{id:<width$} ");
+            plot.extend(row);
+            plot.push('\n');
+        }
+        plot
+    }
+
+    pub fn print(&self, bin: Duration, style: RasterStyle) {
+        print!("{}", self.render(bin, style));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::common::status::NeuronInfo;
+
+    fn neuron_status(id: &str, timestamp: DateTime<Utc>, reset_count: u64) -> Status {
+        Status::Neuron(NeuronInfo {
+            timestamp,
+            id: id.to_string(),
+            dendrite_count: 1,
+            dendrite_connected_count: 1,
+            dendrite_hit_count: 0,
+            reset_count,
+            hit_count: reset_count,
+            accumulator: 0,
+            receiver_count: 1,
+            total_weight: 1,
+            dropped_signals: BTreeMap::new(),
+            refractory_count: 0,
+            kind: NeuronKind::Excitatory,
+        })
+    }
+
+    #[test]
+    fn spikes_should_be_taken_from_growing_fire_counts() {
+        let start = Utc::now();
+        let statuses: Vec<Status> = [(0, 0), (10, 1), (20, 1), (30, 2)]
+            .into_iter()
+            .map(|(ms, count)| neuron_status("M0Z0", start + TimeDelta::milliseconds(ms), count))
+            .collect();
+
+        let raster = Raster::from_statuses(&statuses);
+
+        assert_eq!(
+            raster.get_spikes("M0Z0"),
+            &[
+                start + TimeDelta::milliseconds(10),
+                start + TimeDelta::milliseconds(30)
+            ]
+        );
+        assert!(raster.get_spikes("M0Z1").is_empty());
+    }
+
+    #[test]
+    fn render_should_pad_ids_and_mark_bins_with_spikes() {
+        let start = Utc::now();
+        let mut raster = Raster::default();
+        raster.record("M0Z10", start + TimeDelta::milliseconds(5));
+        raster.record("M0Z10", start + TimeDelta::milliseconds(7));
+        raster.record("M0Z2", start);
+        raster.record("M0Z2", start + TimeDelta::milliseconds(31));
+
+        assert_eq!(
+            raster.render(Duration::from_millis(10), RasterStyle::Unicode),
+            "M0Z10 ┃···\nM0Z2  ┃··┃\n"
+        );
+        assert_eq!(
+            Raster::default().render(Duration::from_millis(10), RasterStyle::Ascii),
+            ""
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Oscillation analysis of the network activity recorded in monitoring mode.
+//! Neuron fires are found as increments of the reset counter in monitoring records,
+//! binned into the population firing rate series and its power spectrum is computed
+//! with the plain DFT.
+
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::status::Status;
+
+/// The population firing rate time series.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateSeries {
+    /// The start moment of the first bin
+    pub start: DateTime<Utc>,
+
+    /// The bin width
+    pub bin: Duration,
+
+    /// Fires per second of all neurons within every bin
+    pub rates: Vec<f64>,
+}
+
+/// The power spectrum of rate series. Constant component is excluded.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Spectrum {
+    /// Frequencies in Hz
+    pub frequencies: Vec<f64>,
+
+    pub power: Vec<f64>,
+}
+
+/// The result of oscillation analysis.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OscillationReport {
+    /// The frequency with the highest power. None when activity is constant.
+    pub dominant_frequency: Option<f64>,
+
+    /// The share of total spectral power at the dominant frequency
+    pub peak_share: f64,
+
+    /// The activity is considered as synchronous oscillation
+    pub synchronous: bool,
+}
+
+/// Build population firing rate series from monitoring records.
+/// The first record of every neuron is the baseline of its reset counter.
+pub fn population_rate(records: &[Status], bin: Duration) -> Option<RateSeries> {
+    let mut neuron_records: BTreeMap<&str, Vec<(DateTime<Utc>, u64)>> = BTreeMap::new();
+    for record in records.iter() {
+        if let Status::Neuron(info) = record {
+            neuron_records
+                .entry(&info.id)
+                .or_default()
+                .push((info.timestamp, info.reset_count));
+        }
+    }
+
+    let mut fires: Vec<(DateTime<Utc>, u64)> = vec![];
+    for records in neuron_records.values_mut() {
+        records.sort();
+        for pair in records.windows(2) {
+            let count = pair[1].1.saturating_sub(pair[0].1);
+            if count > 0 {
+                fires.push((pair[1].0, count));
+            }
+        }
+    }
+
+    let start = neuron_records
+        .values()
+        .filter_map(|records| records.first())
+        .map(|(timestamp, _)| *timestamp)
+        .min()?;
+    let bin_nanos = bin.as_nanos().max(1) as i64;
+    let bin_idx = |timestamp: DateTime<Utc>| {
+        ((timestamp - start).num_nanoseconds().unwrap_or(i64::MAX) / bin_nanos) as usize
+    };
+
+    let bins_count = fires
+        .iter()
+        .map(|(timestamp, _)| bin_idx(*timestamp) + 1)
+        .max()
+        .unwrap_or(0);
+    let mut rates = vec![0.0; bins_count];
+    for (timestamp, count) in fires {
+        rates[bin_idx(timestamp)] += count as f64 / bin.as_secs_f64();
+    }
+
+    Some(RateSeries { start, bin, rates })
+}
+
+/// Power spectrum of rate series for frequencies up to Nyquist one.
+pub fn spectrum(series: &RateSeries) -> Spectrum {
+    let n = series.rates.len();
+    if n < 2 {
+        return Spectrum::default();
+    }
+
+    let mean = series.rates.iter().sum::<f64>() / n as f64;
+    let duration = series.bin.as_secs_f64() * n as f64;
+    let (frequencies, power) = (1..=n / 2)
+        .map(|k| {
+            let (re, im) =
+                series
+                    .rates
+                    .iter()
+                    .enumerate()
+                    .fold((0.0, 0.0), |(re, im), (t, rate)| {
+                        let angle = 2.0 * PI * (k * t) as f64 / n as f64;
+                        (
+                            re + (rate - mean) * angle.cos(),
+                            im - (rate - mean) * angle.sin(),
+                        )
+                    });
+            (k as f64 / duration, (re * re + im * im) / n as f64)
+        })
+        .unzip();
+
+    Spectrum { frequencies, power }
+}
+
+/// Find the dominant oscillation of the population rate. The activity is considered
+/// as synchronous when the dominant frequency takes at least `threshold` share of total power.
+pub fn detect_oscillation(series: &RateSeries, threshold: f64) -> OscillationReport {
+    let spectrum = spectrum(series);
+    let total: f64 = spectrum.power.iter().sum();
+    let peak = spectrum
+        .power
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+    match peak {
+        Some((idx, power)) if total > 0.0 => {
+            let peak_share = power / total;
+            OscillationReport {
+                dominant_frequency: Some(spectrum.frequencies[idx]),
+                peak_share,
+                synchronous: peak_share >= threshold,
+            }
+        }
+        _ => OscillationReport {
+            dominant_frequency: None,
+            peak_share: 0.0,
+            synchronous: false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::common::status::NeuronInfo;
+
+    fn record_fixture(id: &str, start: DateTime<Utc>, millis: i64, reset_count: u64) -> Status {
+        Status::Neuron(NeuronInfo {
+            timestamp: start + TimeDelta::milliseconds(millis),
+            id: id.to_string(),
+            dendrite_count: 1,
+            dendrite_connected_count: 1,
+            dendrite_hit_count: 0,
+            reset_count,
+            hit_count: reset_count,
+            accumulator: 0,
+            receiver_count: 0,
+            total_weight: 1,
+            dropped_signals: BTreeMap::new(),
+            refractory_count: 0,
+            kind: NeuronKind::Excitatory,
+        })
+    }
+
+    /// Two neurons fire together every 40 ms during 320 ms
+    fn synchronous_records_fixture() -> Vec<Status> {
+        let start = Utc::now();
+        let mut records = vec![];
+        for id in ["M0Z0", "M0Z1"] {
+            records.push(record_fixture(id, start, 0, 0));
+            for fire in 1..=8 {
+                records.push(record_fixture(id, start, fire * 40 - 5, fire as u64));
+            }
+        }
+        records
+    }
+
+    #[test]
+    fn should_bin_fires_of_all_neurons() {
+        let series =
+            population_rate(&synchronous_records_fixture(), Duration::from_millis(10)).unwrap();
+
+        assert_eq!(series.rates.len(), 32);
+        assert_eq!(series.rates[3], 200.0);
+        assert_eq!(series.rates[4], 0.0);
+        assert_eq!(series.rates.iter().sum::<f64>(), 1600.0);
+    }
+
+    #[test]
+    fn should_not_build_series_without_neuron_records() {
+        assert!(population_rate(&[], Duration::from_millis(10)).is_none());
+    }
+
+    #[test]
+    fn should_find_frequency_of_synchronous_fires() {
+        let series =
+            population_rate(&synchronous_records_fixture(), Duration::from_millis(10)).unwrap();
+
+        let report = detect_oscillation(&series, 0.2);
+
+        assert!((report.dominant_frequency.unwrap() - 25.0).abs() < 1e-9);
+        assert!(report.synchronous);
+    }
+
+    #[test]
+    fn constant_activity_should_not_be_oscillation() {
+        let series = RateSeries {
+            start: Utc::now(),
+            bin: Duration::from_millis(10),
+            rates: vec![100.0; 16],
+        };
+
+        let report = detect_oscillation(&series, 0.2);
+
+        assert_eq!(report.dominant_frequency, None);
+        assert!(!report.synchronous);
+    }
+}
@@ -0,0 +1,304 @@
+//! Structural analysis of the topology: recurrent components and cycles, neurons
+//! which never receive signals from input ports and axons which lead nowhere.
+//! Problems are reported from the config, before any signal is injected.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::network_cfg::{LinkCfg, NetworkCfg};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructureReport {
+    /// Strongly connected components of neurons. Ids are sorted within the
+    /// component, components are ordered by their first id.
+    pub components: Vec<Vec<String>>,
+
+    /// The shortest cycle through the first neuron of every recurrent component
+    /// (the component of several neurons or the self connected neuron).
+    /// The path starts at that neuron and does not repeat it at the end.
+    pub cycles: Vec<Vec<String>>,
+
+    /// Neurons without path from any input port.
+    pub unreachable: Vec<String>,
+
+    /// Neurons whose axon is connected neither to neurons nor to output ports
+    /// (signals fail with RnnError::DeadEndAxon).
+    pub dead_ends: Vec<String>,
+}
+
+impl StructureReport {
+    pub fn is_acyclic(&self) -> bool {
+        self.cycles.is_empty()
+    }
+
+    /// Every neuron is fed from input ports and passes its signals further.
+    pub fn is_sound(&self) -> bool {
+        self.unreachable.is_empty() && self.dead_ends.is_empty()
+    }
+}
+
+/// Analyze the topology. Composites are flattened into their inner neurons first.
+pub fn analyze(config: &NetworkCfg) -> Result<StructureReport, Box<dyn Error>> {
+    let config = config.flatten()?;
+    let ids: Vec<&str> = config
+        .neurons
+        .iter()
+        .map(|neuron| neuron.id.as_str())
+        .collect::<BTreeSet<&str>>()
+        .into_iter()
+        .collect();
+
+    let mut successors: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    let mut predecessors: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    let mut fed: Vec<&str> = vec![];
+    let mut connected_axons: BTreeSet<&str> = BTreeSet::new();
+    for link in config.links.iter() {
+        match link {
+            LinkCfg::Input { dst_id, .. } => fed.push(dst_id),
+            LinkCfg::Inner { src_id, dst_id, .. } => {
+                successors.entry(src_id).or_default().insert(dst_id);
+                predecessors.entry(dst_id).or_default().insert(src_id);
+                connected_axons.insert(src_id);
+            }
+            LinkCfg::Output { src_id, .. } => {
+                connected_axons.insert(src_id);
+            }
+        }
+    }
+
+    let components = strongly_connected_components(&ids, &successors, &predecessors);
+    let cycles = components
+        .iter()
+        .filter_map(|component| shortest_cycle(component, &successors))
+        .collect();
+
+    let reachable = reachable_from(fed, &successors);
+    let unreachable = ids
+        .iter()
+        .filter(|id| !reachable.contains(*id))
+        .map(|id| id.to_string())
+        .collect();
+    let dead_ends = ids
+        .iter()
+        .filter(|id| !connected_axons.contains(*id))
+        .map(|id| id.to_string())
+        .collect();
+
+    Ok(StructureReport {
+        components,
+        cycles,
+        unreachable,
+        dead_ends,
+    })
+}
+
+/// Kosaraju's algorithm with explicit stacks, so deep chains do not overflow the stack.
+fn strongly_connected_components(
+    ids: &[&str],
+    successors: &BTreeMap<&str, BTreeSet<&str>>,
+    predecessors: &BTreeMap<&str, BTreeSet<&str>>,
+) -> Vec<Vec<String>> {
+    // finishing order of depth first search by successors
+    let mut visited: BTreeSet<&str> = BTreeSet::new();
+    let mut order: Vec<&str> = vec![];
+    for root in ids.iter().copied() {
+        if !visited.insert(root) {
+            continue;
+        }
+        let mut stack = vec![(root, neighbours(successors, root))];
+        while let Some((id, pending)) = stack.last_mut() {
+            if let Some(next) = pending.pop() {
+                if visited.insert(next) {
+                    let next_pending = neighbours(successors, next);
+                    stack.push((next, next_pending));
+                }
+            } else {
+                order.push(*id);
+                stack.pop();
+            }
+        }
+    }
+
+    // components are collected by predecessors in reverse finishing order
+    let mut assigned: BTreeSet<&str> = BTreeSet::new();
+    let mut components = vec![];
+    for root in order.into_iter().rev() {
+        if !assigned.insert(root) {
+            continue;
+        }
+        let mut component = vec![root.to_string()];
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            for previous in neighbours(predecessors, id) {
+                if assigned.insert(previous) {
+                    component.push(previous.to_string());
+                    stack.push(previous);
+                }
+            }
+        }
+        component.sort();
+        components.push(component);
+    }
+    components.sort();
+    components
+}
+
+fn neighbours<'a>(graph: &BTreeMap<&'a str, BTreeSet<&'a str>>, id: &str) -> Vec<&'a str> {
+    graph
+        .get(id)
+        .map(|ids| ids.iter().copied().collect())
+        .unwrap_or_default()
+}
+
+/// Breadth first search from the first neuron of the component back to it.
+fn shortest_cycle(
+    component: &[String],
+    successors: &BTreeMap<&str, BTreeSet<&str>>,
+) -> Option<Vec<String>> {
+    let start = component.first()?.as_str();
+    let members: BTreeSet<&str> = component.iter().map(String::as_str).collect();
+    let mut parents: BTreeMap<&str, &str> = BTreeMap::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(id) = queue.pop_front() {
+        for next in successors.get(id).into_iter().flatten().copied() {
+            if next == start {
+                let mut path = vec![id.to_string()];
+                let mut current = id;
+                while let Some(parent) = parents.get(current) {
+                    path.push(parent.to_string());
+                    current = *parent;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if members.contains(next) && !parents.contains_key(next) {
+                parents.insert(next, id);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+fn reachable_from<'a>(
+    roots: Vec<&'a str>,
+    successors: &BTreeMap<&'a str, BTreeSet<&'a str>>,
+) -> BTreeSet<&'a str> {
+    let mut reachable: BTreeSet<&str> = roots.iter().copied().collect();
+    let mut stack = roots;
+    while let Some(id) = stack.pop() {
+        for next in successors.get(id).into_iter().flatten().copied() {
+            if reachable.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+    reachable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::{LabelMap, NeuronCfg, NeuronKind};
+
+    fn neuron_cfg(id: &str) -> NeuronCfg {
+        NeuronCfg {
+            id: id.to_string(),
+            bias: 1,
+            input_configs: vec![InputCfg::new(1, 1, 1).unwrap(); 2],
+            lineage: None,
+            kind: NeuronKind::Excitatory,
+        }
+    }
+
+    fn inner(src_id: &str, dst_id: &str, dst_synapse_idx: usize) -> LinkCfg {
+        LinkCfg::Inner {
+            src_id: src_id.to_string(),
+            dst_id: dst_id.to_string(),
+            dst_synapse_idx,
+        }
+    }
+
+    /// I0 -> Z0 -> Z1 -> Z2 -> Z0 (loop), Z2 -> O0, Z3 -> Z3 (self loop), Z0 -> Z4 (dead end)
+    fn config_fixture() -> NetworkCfg {
+        NetworkCfg {
+            inputs: 1,
+            outputs: 1,
+            neurons: ["M0Z0", "M0Z1", "M0Z2", "M0Z3", "M0Z4"]
+                .into_iter()
+                .map(neuron_cfg)
+                .collect(),
+            links: vec![
+                LinkCfg::Input {
+                    input_port: 0,
+                    dst_id: String::from("M0Z0"),
+                    dst_synapse_idx: 0,
+                },
+                inner("M0Z0", "M0Z1", 0),
+                inner("M0Z1", "M0Z2", 0),
+                inner("M0Z2", "M0Z0", 1),
+                inner("M0Z3", "M0Z3", 0),
+                inner("M0Z0", "M0Z4", 0),
+                LinkCfg::Output {
+                    src_id: String::from("M0Z2"),
+                    output_port: 0,
+                },
+            ],
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn should_find_components_and_their_cycles() {
+        let report = analyze(&config_fixture()).unwrap();
+
+        assert_eq!(
+            report.components,
+            vec![vec!["M0Z0", "M0Z1", "M0Z2"], vec!["M0Z3"], vec!["M0Z4"]]
+        );
+        assert_eq!(
+            report.cycles,
+            vec![vec!["M0Z0", "M0Z1", "M0Z2"], vec!["M0Z3"]]
+        );
+        assert!(!report.is_acyclic());
+    }
+
+    #[test]
+    fn should_report_unreachable_neurons_and_dead_end_axons() {
+        let report = analyze(&config_fixture()).unwrap();
+
+        assert_eq!(report.unreachable, vec!["M0Z3"]);
+        assert_eq!(report.dead_ends, vec!["M0Z4"]);
+        assert!(!report.is_sound());
+    }
+
+    #[test]
+    fn chain_should_be_acyclic_and_sound() {
+        let mut config = config_fixture();
+        config.neurons.truncate(2);
+        config.links = vec![
+            LinkCfg::Input {
+                input_port: 0,
+                dst_id: String::from("M0Z0"),
+                dst_synapse_idx: 0,
+            },
+            inner("M0Z0", "M0Z1", 0),
+            LinkCfg::Output {
+                src_id: String::from("M0Z1"),
+                output_port: 0,
+            },
+        ];
+
+        let report = analyze(&config).unwrap();
+
+        assert!(report.is_acyclic());
+        assert!(report.is_sound());
+        assert_eq!(report.components.len(), 2);
+    }
+}
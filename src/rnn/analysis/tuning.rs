@@ -0,0 +1,276 @@
+//! Stimulus-response characterization of the network: the stimulus parameter
+//! is swept across the range and responses of every output port are measured.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+use tokio::time::{self, Instant};
+
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::signal::Signal;
+use crate::rnn::layouts::network::Network;
+
+/// The swept stimulus parameter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Stimulus {
+    /// The signal value sent once into every stimulated port.
+    Amplitude(Vec<Signal>),
+
+    /// The number of signals with the same value sent into every stimulated port
+    /// evenly within the window.
+    Rate { signal: Signal, counts: Vec<usize> },
+}
+
+impl Stimulus {
+    fn values(&self) -> Vec<f64> {
+        match self {
+            Stimulus::Amplitude(signals) => signals.iter().map(|signal| *signal as f64).collect(),
+            Stimulus::Rate { counts, .. } => counts.iter().map(|count| *count as f64).collect(),
+        }
+    }
+}
+
+/// The sweep settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TuningSweep {
+    /// Stimulated input ports
+    pub input_ports: Vec<usize>,
+
+    pub stimulus: Stimulus,
+
+    /// The time of response collection for every trial
+    pub window: Duration,
+
+    /// The number of trials for every stimulus value. Responses are averaged.
+    pub trials: usize,
+}
+
+/// The response of output port averaged over trials.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PortResponse {
+    /// Mean number of signals per trial
+    pub rate: f64,
+
+    /// Mean value of received signals. Zero when nothing received.
+    pub mean_signal: f64,
+}
+
+/// Responses of all output ports to the stimulus value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TuningPoint {
+    pub stimulus: f64,
+    pub responses: BTreeMap<usize, PortResponse>,
+}
+
+/// The result of the sweep.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TuningCurves {
+    pub points: Vec<TuningPoint>,
+}
+
+impl TuningCurves {
+    /// Pairs (stimulus, rate) of the output port in sweep order.
+    pub fn curve(&self, output_port: usize) -> Vec<(f64, f64)> {
+        self.points
+            .iter()
+            .filter_map(|point| {
+                point
+                    .responses
+                    .get(&output_port)
+                    .map(|response| (point.stimulus, response.rate))
+            })
+            .collect()
+    }
+
+    /// The stimulus value which evokes the highest rate of the output port.
+    pub fn preferred_stimulus(&self, output_port: usize) -> Option<f64> {
+        self.curve(output_port)
+            .into_iter()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(stimulus, _)| stimulus)
+    }
+}
+
+/// Sweep the stimulus across its values and measure responses of all connected output ports.
+pub async fn measure_tuning_curves(
+    network: &Network,
+    sweep: &TuningSweep,
+) -> Result<TuningCurves, Box<dyn Error>> {
+    if sweep.trials == 0 || sweep.input_ports.is_empty() {
+        return Err(Box::new(RnnError::NotSupportedArgValue));
+    }
+
+    let mut receivers = BTreeMap::new();
+    for port in network.get_output_ports().await {
+        receivers.insert(port, network.subscribe_output(port).await?);
+    }
+
+    let mut curves = TuningCurves::default();
+    for (idx, stimulus) in sweep.stimulus.values().into_iter().enumerate() {
+        let mut sums: BTreeMap<usize, (usize, u64)> = BTreeMap::new();
+        for _ in 0..sweep.trials {
+            let collectors: Vec<_> = std::mem::take(&mut receivers)
+                .into_iter()
+                .map(|(port, receiver)| {
+                    tokio::spawn(collect(port, receiver, Instant::now() + sweep.window))
+                })
+                .collect();
+
+            stimulate(network, sweep, idx).await?;
+
+            for collector in collectors {
+                let (port, receiver, signals) = collector.await?;
+                let sum = sums.entry(port).or_default();
+                sum.0 += signals.len();
+                sum.1 += signals.iter().map(|signal| *signal as u64).sum::<u64>();
+                receivers.insert(port, receiver);
+            }
+        }
+
+        let responses = sums
+            .into_iter()
+            .map(|(port, (count, total))| {
+                let response = PortResponse {
+                    rate: count as f64 / sweep.trials as f64,
+                    mean_signal: if count > 0 {
+                        total as f64 / count as f64
+                    } else {
+                        0.0
+                    },
+                };
+                (port, response)
+            })
+            .collect();
+        curves.points.push(TuningPoint {
+            stimulus,
+            responses,
+        });
+    }
+
+    Ok(curves)
+}
+
+async fn stimulate(
+    network: &Network,
+    sweep: &TuningSweep,
+    idx: usize,
+) -> Result<(), Box<dyn Error>> {
+    match &sweep.stimulus {
+        Stimulus::Amplitude(signals) => {
+            for port in sweep.input_ports.iter() {
+                network.input(signals[idx], *port).await?;
+            }
+        }
+        Stimulus::Rate { signal, counts } => {
+            let count = counts[idx];
+            // the last signal also needs time to pass through the network
+            let period = sweep.window / (count as u32 + 1);
+            for _ in 0..count {
+                for port in sweep.input_ports.iter() {
+                    network.input(*signal, *port).await?;
+                }
+                time::sleep(period).await;
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn collect(
+    port: usize,
+    mut receiver: Receiver<Signal>,
+    deadline: Instant,
+) -> (usize, Receiver<Signal>, Vec<Signal>) {
+    let mut signals = vec![];
+    loop {
+        match time::timeout_at(deadline, receiver.recv()).await {
+            Ok(Ok(signal)) => signals.push(signal),
+            Ok(Err(RecvError::Lagged(_))) => continue,
+            Ok(Err(RecvError::Closed)) | Err(_) => break,
+        }
+    }
+    (port, receiver, signals)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+
+    /// I0 -> Z0 -> O0, the neuron passes signals up to 3 without changes
+    async fn relay_network_fixture() -> Arc<Network> {
+        let net = Arc::new(new_network_fixture());
+        let id = net
+            .create_neuron(
+                net.clone(),
+                0,
+                vec![InputCfg::new(3, 3, 1).unwrap()],
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap()
+            .get_id();
+        assert!(net.setup_input(0, &id, 0).await.is_ok());
+        assert!(net.setup_output(0, &id).await.is_ok());
+        net
+    }
+
+    #[tokio::test]
+    async fn should_measure_response_to_amplitude() {
+        let net = relay_network_fixture().await;
+        let sweep = TuningSweep {
+            input_ports: vec![0],
+            stimulus: Stimulus::Amplitude(vec![2, 0]),
+            window: Duration::from_millis(20),
+            trials: 2,
+        };
+
+        let curves = measure_tuning_curves(&net, &sweep).await.unwrap();
+
+        // zero signals are suppressed by neuron
+        assert_eq!(curves.curve(0), vec![(2.0, 1.0), (0.0, 0.0)]);
+        assert_eq!(curves.preferred_stimulus(0), Some(2.0));
+        assert!(curves.points[0].responses[&0].mean_signal >= 2.0);
+        assert!(curves.curve(1).is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_measure_response_to_rate() {
+        let net = relay_network_fixture().await;
+        let sweep = TuningSweep {
+            input_ports: vec![0],
+            stimulus: Stimulus::Rate {
+                signal: 2,
+                counts: vec![1, 3],
+            },
+            window: Duration::from_millis(40),
+            trials: 1,
+        };
+
+        let curves = measure_tuning_curves(&net, &sweep).await.unwrap();
+
+        assert_eq!(curves.curve(0), vec![(1.0, 1.0), (3.0, 3.0)]);
+        assert_eq!(curves.preferred_stimulus(0), Some(3.0));
+    }
+
+    #[tokio::test]
+    async fn should_not_sweep_without_trials() {
+        let net = relay_network_fixture().await;
+        let sweep = TuningSweep {
+            input_ports: vec![0],
+            stimulus: Stimulus::Amplitude(vec![1]),
+            window: Duration::from_millis(1),
+            trials: 0,
+        };
+
+        assert!(measure_tuning_curves(&net, &sweep).await.is_err());
+    }
+}
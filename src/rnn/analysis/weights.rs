@@ -0,0 +1,310 @@
+//! Export of weight matrices between consecutive layers of the network.
+//! The weight of the link is the weight of the destination neuron's dendrite.
+//! Layers are taken from the layered layout (see drawing::layered_layout()).
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::network_cfg::{LinkCfg, NetworkCfg};
+use crate::rnn::common::signal::Weight;
+
+use super::drawing::{layered_layout, Node};
+
+/// Weights of links from the source layer (columns) into the destination layer (rows).
+/// Row of the matrix is the receptive field of the destination neuron.
+/// Missed links have zero weight.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeightMatrix {
+    /// Neuron ids or input port names ("I0", "I1", ...)
+    pub src_ids: Vec<String>,
+    pub dst_ids: Vec<String>,
+    pub weights: Vec<Vec<Weight>>,
+}
+
+impl WeightMatrix {
+    /// Collect weights of links between specified sources and destinations.
+    pub fn between(config: &NetworkCfg, src_ids: &[String], dst_ids: &[String]) -> Self {
+        let mut weights = vec![vec![0; src_ids.len()]; dst_ids.len()];
+        let neurons: BTreeMap<&str, _> = config
+            .neurons
+            .iter()
+            .map(|neuron| (neuron.id.as_str(), neuron))
+            .collect();
+
+        for link in config.links.iter() {
+            let (src_id, dst_id, dst_synapse_idx) = match link {
+                LinkCfg::Input {
+                    input_port,
+                    dst_id,
+                    dst_synapse_idx,
+                } => (input_name(*input_port), dst_id, dst_synapse_idx),
+                LinkCfg::Inner {
+                    src_id,
+                    dst_id,
+                    dst_synapse_idx,
+                } => (src_id.clone(), dst_id, dst_synapse_idx),
+                LinkCfg::Output { .. } => continue,
+            };
+            let column = src_ids.iter().position(|id| *id == src_id);
+            let row = dst_ids.iter().position(|id| id == dst_id);
+            let weight = neurons
+                .get(dst_id.as_str())
+                .and_then(|neuron| neuron.input_configs.get(*dst_synapse_idx))
+                .map(|input_cfg| input_cfg.weight);
+            if let (Some(column), Some(row), Some(weight)) = (column, row, weight) {
+                weights[row][column] = weights[row][column].saturating_add(weight);
+            }
+        }
+
+        WeightMatrix {
+            src_ids: src_ids.to_vec(),
+            dst_ids: dst_ids.to_vec(),
+            weights,
+        }
+    }
+
+    /// The header row contains source ids, the first column contains destination ids.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("dst");
+        for src_id in self.src_ids.iter() {
+            let _ = write!(csv, ",{src_id}");
+        }
+        csv.push('\n');
+        for (dst_id, row) in self.dst_ids.iter().zip(self.weights.iter()) {
+            csv.push_str(dst_id);
+            for weight in row {
+                let _ = write!(csv, ",{weight}");
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Grayscale image with pixel per weight: the minimal weight is black, the maximal one is white.
+    #[cfg(feature = "png")]
+    pub fn to_png(&self) -> Vec<u8> {
+        let min = self.weights.iter().flatten().copied().min().unwrap_or(0);
+        let max = self.weights.iter().flatten().copied().max().unwrap_or(0);
+        let range = (max as i32 - min as i32).max(1);
+        let pixels: Vec<Vec<u8>> = self
+            .weights
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|weight| ((*weight as i32 - min as i32) * 255 / range) as u8)
+                    .collect()
+            })
+            .collect();
+        png::encode_grayscale(self.src_ids.len(), &pixels)
+    }
+}
+
+/// Matrices between every pair of consecutive layers starting from input ports.
+pub fn layer_matrices(config: &NetworkCfg) -> Vec<WeightMatrix> {
+    let mut layers: BTreeMap<i64, Vec<String>> = BTreeMap::new();
+    for position in layered_layout(config).positions {
+        let id = match position.node {
+            Node::Input(port) => input_name(port),
+            Node::Neuron(id) => id,
+            Node::Output(_) => continue,
+        };
+        layers.entry(position.x as i64).or_default().push(id);
+    }
+
+    let layers: Vec<Vec<String>> = layers.into_values().collect();
+    layers
+        .windows(2)
+        .map(|pair| WeightMatrix::between(config, &pair[0], &pair[1]))
+        .collect()
+}
+
+fn input_name(port: usize) -> String {
+    format!("I{port}")
+}
+
+/// Minimal PNG encoder: 8 bit grayscale, deflate stream of stored (not compressed) blocks.
+#[cfg(feature = "png")]
+mod png {
+    static SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    static MAX_STORED_BLOCK: usize = 0xffff;
+
+    pub fn encode_grayscale(width: usize, rows: &[Vec<u8>]) -> Vec<u8> {
+        let mut header = vec![];
+        header.extend((width as u32).to_be_bytes());
+        header.extend((rows.len() as u32).to_be_bytes());
+        // bit depth 8, grayscale, deflate, adaptive filtering, no interlace
+        header.extend([8, 0, 0, 0, 0]);
+
+        // every scanline starts with filter type "None"
+        let raw: Vec<u8> = rows
+            .iter()
+            .flat_map(|row| std::iter::once(0).chain(row.iter().copied()))
+            .collect();
+
+        let mut png = SIGNATURE.to_vec();
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+        png.extend((data.len() as u32).to_be_bytes());
+        let start = png.len();
+        png.extend(kind);
+        png.extend(data);
+        let crc = crc32(&png[start..]);
+        png.extend(crc.to_be_bytes());
+    }
+
+    fn zlib_stored(data: &[u8]) -> Vec<u8> {
+        let mut stream = vec![0x78, 0x01];
+        let blocks: Vec<&[u8]> = if data.is_empty() {
+            vec![&[]]
+        } else {
+            data.chunks(MAX_STORED_BLOCK).collect()
+        };
+        for (idx, block) in blocks.iter().enumerate() {
+            stream.push((idx + 1 == blocks.len()) as u8);
+            let len = block.len() as u16;
+            stream.extend(len.to_le_bytes());
+            stream.extend((!len).to_le_bytes());
+            stream.extend(*block);
+        }
+        stream.extend(adler32(data).to_be_bytes());
+        stream
+    }
+
+    fn crc32(data: &[u8]) -> u32 {
+        !data.iter().fold(0xffffffff_u32, |crc, byte| {
+            (0..8).fold(crc ^ *byte as u32, |crc, _| {
+                if crc & 1 == 1 {
+                    (crc >> 1) ^ 0xedb88320
+                } else {
+                    crc >> 1
+                }
+            })
+        })
+    }
+
+    fn adler32(data: &[u8]) -> u32 {
+        let (a, b) = data.iter().fold((1_u32, 0_u32), |(a, b), byte| {
+            let a = (a + *byte as u32) % 65521;
+            (a, (b + a) % 65521)
+        });
+        (b << 16) | a
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn should_calculate_known_checksums() {
+            assert_eq!(crc32(b"IEND"), 0xae426082);
+            assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::layouts::layered_builder::{ConnectionStrategy, Layer, LayeredNetworkBuilder};
+
+    /// 2 inputs -> 2 neurons dense -> 1 neuron dense, weights are growing with dendrite index
+    fn config_fixture() -> NetworkCfg {
+        let mut config = LayeredNetworkBuilder::new(2)
+            .layer(
+                Layer {
+                    width: 2,
+                    bias: 0,
+                    input_cfg: InputCfg::new(1, 1, 1).unwrap(),
+                },
+                ConnectionStrategy::Dense,
+            )
+            .layer(
+                Layer {
+                    width: 1,
+                    bias: 0,
+                    input_cfg: InputCfg::new(1, 1, 1).unwrap(),
+                },
+                ConnectionStrategy::Dense,
+            )
+            .build()
+            .unwrap();
+        for neuron in config.neurons.iter_mut() {
+            for (idx, input_cfg) in neuron.input_configs.iter_mut().enumerate() {
+                input_cfg.weight = idx as Weight - 1;
+            }
+        }
+        config
+    }
+
+    #[test]
+    fn should_build_matrices_between_consecutive_layers() {
+        let matrices = layer_matrices(&config_fixture());
+
+        assert_eq!(matrices.len(), 2);
+        assert_eq!(matrices[0].src_ids, vec!["I0", "I1"]);
+        assert_eq!(matrices[0].dst_ids, vec!["M0Z0", "M0Z1"]);
+        assert_eq!(matrices[0].weights, vec![vec![-1, 0], vec![-1, 0]]);
+        assert_eq!(matrices[1].dst_ids, vec!["M0Z2"]);
+        assert_eq!(matrices[1].weights, vec![vec![-1, 0]]);
+    }
+
+    #[test]
+    fn parallel_links_should_saturate_sum_of_weights() {
+        // I0 feeds both dendrites of M0Z0
+        let mut config = config_fixture();
+        for link in config.links.iter_mut() {
+            if let LinkCfg::Input {
+                input_port, dst_id, ..
+            } = link
+            {
+                if dst_id == "M0Z0" {
+                    *input_port = 0;
+                }
+            }
+        }
+        for input_cfg in config.neurons[0].input_configs.iter_mut() {
+            input_cfg.weight = Weight::MAX;
+        }
+
+        let matrix = WeightMatrix::between(
+            &config,
+            &[String::from("I0"), String::from("I1")],
+            &[String::from("M0Z0")],
+        );
+
+        assert_eq!(matrix.weights, vec![vec![Weight::MAX, 0]]);
+    }
+
+    #[test]
+    fn should_export_matrix_into_csv() {
+        let matrices = layer_matrices(&config_fixture());
+
+        assert_eq!(matrices[0].to_csv(), "dst,I0,I1\nM0Z0,-1,0\nM0Z1,-1,0\n");
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn should_export_matrix_into_png() {
+        let matrix = WeightMatrix {
+            src_ids: vec![String::from("I0"), String::from("I1")],
+            dst_ids: vec![String::from("M0Z0")],
+            weights: vec![vec![-2, 2]],
+        };
+
+        let png = matrix.to_png();
+
+        assert_eq!(png[..8], [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]);
+        assert_eq!(png[16..24], [0, 0, 0, 2, 0, 0, 0, 1]);
+        // filter byte and pixels inside the stored block of IDAT chunk
+        let idat = png.windows(4).position(|w| w == b"IDAT").unwrap();
+        assert_eq!(png[idat + 11..idat + 14], [0, 0, 255]);
+    }
+}
@@ -5,6 +5,15 @@ pub mod utils;
 
 pub mod spec_type;
 
+pub mod capabilities;
+pub mod clock;
 pub mod command;
+pub mod diagnostics;
 pub mod input_cfg;
 pub mod network_cfg;
+pub mod rng;
+pub mod runtime_cfg;
+pub mod snapshot;
+
+#[cfg(feature = "alloc-audit")]
+pub mod alloc_audit;
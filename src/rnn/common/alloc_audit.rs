@@ -0,0 +1,78 @@
+//! Allocation audit of the signal processing hot path.
+//! The test or bench binary registers the counting allocator:
+//!
+//! ```ignore
+//! use librunen::rnn::common::alloc_audit::CountingAllocator;
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//! ```
+//!
+//! and measures allocations of the workload with AllocationAudit.
+//! Counters are process wide, so audited workloads should not run concurrently.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator which counts allocations.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Counters snapshot taken at start of audited workload.
+#[derive(Debug, Clone)]
+pub struct AllocationAudit {
+    allocations: u64,
+    allocated_bytes: u64,
+}
+
+impl AllocationAudit {
+    pub fn start() -> Self {
+        AllocationAudit {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// The number of allocations since start. Always zero if CountingAllocator is not registered.
+    pub fn allocations(&self) -> u64 {
+        ALLOCATIONS.load(Ordering::Relaxed) - self.allocations
+    }
+
+    pub fn allocated_bytes(&self) -> u64 {
+        ALLOCATED_BYTES.load(Ordering::Relaxed) - self.allocated_bytes
+    }
+
+    /// Mean number of allocations per processed signal.
+    pub fn allocs_per_signal(&self, signals: u64) -> f64 {
+        self.allocations() as f64 / signals.max(1) as f64
+    }
+
+    /// Panics when workload made more than `max` allocations per processed signal.
+    pub fn assert_max_allocs_per_signal(&self, signals: u64, max: u64) {
+        let allocs_per_signal = self.allocs_per_signal(signals);
+        assert!(
+            allocs_per_signal <= max as f64,
+            "{allocs_per_signal:.1} allocations per signal exceed limit {max}"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use super::runtime_cfg::RoutingBackend;
+
+/// What this build of the library supports. Serializable, so it can be sent
+/// to remote parties which have to adapt to differently built nodes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// The library version
+    pub version: String,
+
+    /// Cargo features compiled in
+    pub features: Vec<String>,
+
+    /// Available signal routing backends
+    pub routing_backends: Vec<RoutingBackend>,
+}
+
+impl Capabilities {
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|name| name == feature)
+    }
+}
+
+/// Capability report of the current build.
+pub fn capabilities() -> Capabilities {
+    let features: &[(&str, bool)] = &[
+        ("alloc-audit", cfg!(feature = "alloc-audit")),
+        ("http", cfg!(feature = "http")),
+        ("mqtt", cfg!(feature = "mqtt")),
+        ("nwb", cfg!(feature = "nwb")),
+        ("petgraph", cfg!(feature = "petgraph")),
+        ("png", cfg!(feature = "png")),
+        ("tokio-console", cfg!(feature = "tokio-console")),
+        ("tui", cfg!(feature = "tui")),
+        ("viz", cfg!(feature = "viz")),
+        ("wasm", cfg!(feature = "wasm")),
+        ("wav", cfg!(feature = "wav")),
+    ];
+
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        features: features
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| name.to_string())
+            .collect(),
+        routing_backends: vec![
+            RoutingBackend::Broadcast,
+            RoutingBackend::Mailbox,
+            RoutingBackend::EventDriven,
+            RoutingBackend::Sharded,
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_report_version_backends_and_features() {
+        let report = capabilities();
+
+        assert_eq!(report.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(report.routing_backends.len(), 4);
+        assert_eq!(report.has_feature("png"), cfg!(feature = "png"));
+        assert!(!report.has_feature("unknown"));
+    }
+
+    #[test]
+    fn report_should_survive_json_round_trip() {
+        let report = capabilities();
+
+        let report_json = serde_json::to_string(&report).unwrap();
+        let restored: Capabilities = serde_json::from_str(&report_json).unwrap();
+
+        assert_eq!(restored, report);
+    }
+}
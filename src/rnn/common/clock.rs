@@ -0,0 +1,63 @@
+//! The source of timestamps of statuses and log records. Tests inject `TokioClock`
+//! into the network and pause tokio time to get reproducible timestamps.
+
+use std::fmt::Debug;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use tokio::time::Instant;
+
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// The clock driven by tokio time: it starts at the origin and advances as
+/// `tokio::time::Instant` does, so it stands still while tokio time is paused
+/// and jumps with `tokio::time::advance()`.
+#[derive(Debug, Clone)]
+pub struct TokioClock {
+    origin: DateTime<Utc>,
+    start: Instant,
+}
+
+impl TokioClock {
+    pub fn new(origin: DateTime<Utc>) -> Self {
+        TokioClock {
+            origin,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Clock for TokioClock {
+    fn now(&self) -> DateTime<Utc> {
+        let elapsed = TimeDelta::from_std(self.start.elapsed()).unwrap_or(TimeDelta::MAX);
+        self.origin + elapsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn tokio_clock_should_follow_paused_time() {
+        let origin = DateTime::<Utc>::UNIX_EPOCH;
+        let clock = TokioClock::new(origin);
+
+        assert_eq!(clock.now(), origin);
+        tokio::time::advance(Duration::from_millis(1500)).await;
+        assert_eq!(clock.now(), origin + TimeDelta::milliseconds(1500));
+    }
+}
@@ -1,4 +1,9 @@
-use crate::rnn::layouts::network::{self, MonitoringMode};
+use tokio::sync::mpsc;
+
+use crate::rnn::analysis::probe::ProbeSample;
+use crate::rnn::common::network_cfg::NeuronCfg;
+use crate::rnn::common::signal::{Signal, Weight};
+use crate::rnn::layouts::network::{self, MonitoringFilter, MonitoringMode};
 
 /// Commands set to control network state
 pub enum NetCommand {
@@ -23,4 +28,49 @@ pub enum NetCommand {
 #[derive(Debug, Clone)]
 pub enum NeuronCommand {
     SwitchMonitoringMode(MonitoringMode),
+
+    /// Send only statuses passing the filter in MonitoringMode::Monitoring.
+    SetMonitoringFilter(MonitoringFilter),
+
+    /// Clear runtime state: accumulator, counters, input hits and synapse capacities.
+    Reset,
+
+    /// Stop processing of incoming signals. Signals received while paused are buffered.
+    Pause,
+
+    /// Process buffered signals and continue processing of incoming ones.
+    Resume,
+
+    /// Change parameters of the dendrite connected to the port keeping its connection.
+    /// None keeps the current value (see Neuron::update_synapse()).
+    UpdateSynapse {
+        port: usize,
+        weight: Option<Weight>,
+        capacity: Option<Signal>,
+    },
+
+    /// Change the neuron's bias (the firing threshold).
+    UpdateBias(Weight),
+
+    /// The command addressed to the single neuron with specified id.
+    /// Other neurons ignore it.
+    For(String, Box<NeuronCommand>),
+
+    /// Every neuron which receives the command replies with its current config.
+    GetConfig {
+        reply: mpsc::Sender<NeuronCfg>,
+    },
+
+    /// Every neuron which receives the command replies with its current sample
+    /// (see analysis::probe::Probe).
+    GetSample {
+        reply: mpsc::Sender<ProbeSample>,
+    },
+
+    /// Apply the command and reply with the neuron id to confirm it.
+    /// Neurons which skip the addressed command do not reply.
+    Confirmed {
+        command: Box<NeuronCommand>,
+        reply: mpsc::Sender<String>,
+    },
 }
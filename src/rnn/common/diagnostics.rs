@@ -0,0 +1,21 @@
+//! Subscribers of the tracing events of the library. Neurons record events into
+//! their `neuron` spans: lifecycle and connections at debug level, received
+//! signals and fires at trace level.
+//!
+//! With the `tokio-console` feature tasks can be inspected by tokio-console.
+//! The binary has to be built with `RUSTFLAGS="--cfg tokio_unstable"`.
+
+use std::error::Error;
+
+use tracing::Level;
+
+/// Print events up to the level into stdout.
+pub fn init_tracing(level: Level) -> Result<(), Box<dyn Error + Send + Sync>> {
+    tracing_subscriber::fmt().with_max_level(level).try_init()
+}
+
+/// Serve task states for tokio-console on the default address 127.0.0.1:6669.
+#[cfg(feature = "tokio-console")]
+pub fn init_console() {
+    console_subscriber::init();
+}
@@ -8,7 +8,7 @@ use super::{
 };
 
 /// Input configuration
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct InputCfg {
     /// High limit of synapse (input) capacity
     pub capacity_max: Signal,
@@ -87,7 +87,7 @@ mod tests {
     #[test]
     fn config_shoud_deserialize_from_yaml_string() {
         let cfg_yaml = "capacity_max: 3\nregeneration: 2\nweight: 1\n";
-        let cfg: InputCfg = serde_yaml::from_str(&cfg_yaml).unwrap();
+        let cfg: InputCfg = serde_yaml::from_str(cfg_yaml).unwrap();
 
         assert_eq!(cfg.capacity_max, 3);
         assert_eq!(cfg.regeneration, 2);
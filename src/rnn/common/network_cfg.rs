@@ -1,11 +1,18 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
 use serde::{Deserialize, Serialize};
 
 use super::{
     input_cfg::InputCfg,
+    rnn_error::RnnError,
     signal::{Signal, Weight},
+    spec_type::SpecificationType,
+    utils::{extract_network_id_from, gen_id_by_spec_type, get_component_id_fraction},
 };
+use crate::rnn::analysis::drawing::Node;
 
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LinkCfg {
     Input {
         input_port: usize,
@@ -23,20 +30,705 @@ pub enum LinkCfg {
     },
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// The sign of the neuron's outbound connections (Dale's law).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NeuronKind {
+    /// Weights of outbound connections are not restricted.
+    #[default]
+    Excitatory,
+
+    /// Weights of outbound connections are forced negative when neurons are connected.
+    Inhibitory,
+}
+
+impl NeuronKind {
+    pub fn is_excitatory(&self) -> bool {
+        *self == NeuronKind::Excitatory
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NeuronCfg {
     pub id: String,
     pub bias: Weight,
     pub input_configs: Vec<InputCfg>,
+
+    /// The id of the neuron which this one originates from when config was
+    /// installed into another network. Used to align neurons of different configs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lineage: Option<String>,
+
+    #[serde(default, skip_serializing_if = "NeuronKind::is_excitatory")]
+    pub kind: NeuronKind,
+}
+
+impl NeuronCfg {
+    /// The lineage marker of the neuron: origin id or own id for the original neuron.
+    pub fn lineage_marker(&self) -> &str {
+        self.lineage.as_deref().unwrap_or(&self.id)
+    }
+}
+
+/// The whole network embedded into another one as a single node (composite neuron).
+/// Links of the outer network refer the composite by its id: the signal coming into
+/// composite's synapse with index p goes into inner input port p, and the composite's
+/// axon is driven by inner output port `axon_port`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompositeCfg {
+    pub id: String,
+    pub network: NetworkCfg,
+    pub axon_port: usize,
+}
+
+/// Inner neurons which the composite's synapses and axon are mapped to.
+struct CompositeInterface {
+    /// synapse idx -> (inner neuron id, dendrite idx) of every neuron fed by the inner input port
+    synapses: BTreeMap<usize, Vec<(String, usize)>>,
+    axon: Option<String>,
+}
+
+/// The origin of the network config: the operator which produced it and its parents.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkLineage {
+    /// Fingerprints of parent configs (see NetworkCfg::fingerprint())
+    pub parents: Vec<String>,
+
+    /// The name of trainer or operator which produced the config
+    pub trainer: String,
+
+    /// The number of generations since the first ancestor
+    pub generation: u64,
+
+    /// Short description of applied mutations
+    pub mutations: Vec<String>,
+}
+
+impl NetworkLineage {
+    /// Lineage of the config produced from parents: the generation is next to the latest parent's one.
+    pub fn descend(parents: &[&NetworkCfg], trainer: &str, mutations: Vec<String>) -> Self {
+        let generation = parents
+            .iter()
+            .filter_map(|parent| parent.lineage.as_ref())
+            .map(|lineage| lineage.generation)
+            .max()
+            .unwrap_or(0);
+
+        NetworkLineage {
+            parents: parents.iter().map(|parent| parent.fingerprint()).collect(),
+            trainer: trainer.to_string(),
+            generation: generation + 1,
+            mutations,
+        }
+    }
+}
+
+/// Mapping between dataset labels (solution variants) and sets of output ports.
+/// It is stored with the config, so the trained network is served with the same
+/// assignment of classes to ports.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LabelMap {
+    ports: BTreeMap<String, BTreeSet<usize>>,
+}
+
+impl LabelMap {
+    /// Label with index i is assigned to output port i.
+    pub fn one_hot<L: ToString>(labels: &[L]) -> Self {
+        LabelMap {
+            ports: labels
+                .iter()
+                .enumerate()
+                .map(|(port, label)| (label.to_string(), BTreeSet::from([port])))
+                .collect(),
+        }
+    }
+
+    /// Assign the label to the set of output ports. Sets of different labels
+    /// have to differ and must not be empty.
+    pub fn insert(&mut self, label: &str, ports: BTreeSet<usize>) -> Result<(), Box<dyn Error>> {
+        if ports.is_empty()
+            || self
+                .ports
+                .iter()
+                .any(|(other, other_ports)| other != label && *other_ports == ports)
+        {
+            return Err(Box::new(RnnError::NotSupportedArgValue));
+        }
+        self.ports.insert(label.to_string(), ports);
+        Ok(())
+    }
+
+    pub fn get_ports(&self, label: &str) -> Option<&BTreeSet<usize>> {
+        self.ports.get(label)
+    }
+
+    pub fn get_labels(&self) -> Vec<String> {
+        self.ports.keys().cloned().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ports.is_empty()
+    }
+
+    /// The label whose port set is exactly the set of ports with non-zero signal.
+    /// Signals are ordered by output port index.
+    pub fn decode(&self, signals: &[Signal]) -> Option<String> {
+        let fired: BTreeSet<usize> = signals
+            .iter()
+            .enumerate()
+            .filter(|(_, signal)| **signal > 0)
+            .map(|(port, _)| port)
+            .collect();
+        self.ports
+            .iter()
+            .find(|(_, ports)| **ports == fired)
+            .map(|(label, _)| label.clone())
+    }
+
+    /// Output signals expected for the label: max signal on its ports, zero on others.
+    pub fn encode(&self, label: &str, outputs: usize) -> Option<Vec<Signal>> {
+        self.ports.get(label).map(|ports| {
+            (0..outputs)
+                .map(|port| {
+                    if ports.contains(&port) {
+                        Signal::MAX
+                    } else {
+                        0
+                    }
+                })
+                .collect()
+        })
+    }
+
+    /// Check that labels refer existing output ports.
+    pub(crate) fn validate(&self, outputs: usize) -> Result<(), Box<dyn Error>> {
+        match self.ports.values().flatten().find(|port| **port >= outputs) {
+            Some(port) => Err(Box::new(RnnError::PortNotFound(*port))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The edge of the network graph (see NetworkCfg::graph_elements()).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphLink {
+    /// The dendrite index of the destination neuron
+    pub synapse: Option<usize>,
+
+    /// The dendrite weight. Absent for links into composites.
+    pub weight: Option<Weight>,
 }
 
 /// The network config structure used to describe neuron set and connections between them.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NetworkCfg {
-    inputs: usize,
-    outputs: usize,
-    neurons: Vec<NeuronCfg>,
-    links: Vec<LinkCfg>,
+    /// The number of network's input ports
+    pub inputs: usize,
+
+    /// The number of network's output ports
+    pub outputs: usize,
+
+    pub neurons: Vec<NeuronCfg>,
+    pub links: Vec<LinkCfg>,
+
+    /// The origin of config. Absent for configs built by hand.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lineage: Option<NetworkLineage>,
+
+    /// Embedded subnetworks. They are flattened into plain neurons when config is installed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub composites: Vec<CompositeCfg>,
+
+    /// Assignment of dataset labels to output ports (see NetworkCfg::bind_labels()).
+    #[serde(default, skip_serializing_if = "LabelMap::is_empty")]
+    pub labels: LabelMap,
+
+    /// The root seed of network's random generators (see RngProvider).
+    /// Absent when the network is seeded from entropy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+impl NetworkCfg {
+    /// The hash of config's json representation (64 bit FNV-1a in hex).
+    /// It is stable between builds so it can be stored as parent reference.
+    pub fn fingerprint(&self) -> String {
+        let json = serde_json::to_string(self).unwrap_or_default();
+        let hash = json.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        format!("{hash:016x}")
+    }
+
+    /// Bind labels of the training data to output ports of the config.
+    pub fn bind_labels(&mut self, labels: LabelMap) -> Result<(), Box<dyn Error>> {
+        labels.validate(self.outputs)?;
+        self.labels = labels;
+        Ok(())
+    }
+
+    /// Check that config can be installed into the network: neuron ids are unique,
+    /// links refer existing neurons, dendrites and ports, every dendrite
+    /// or output port is occupied at most once and labels refer existing output ports.
+    /// An input port may feed dendrites of several neurons.
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        if !self.composites.is_empty() {
+            return self.flatten()?.validate();
+        }
+        self.labels.validate(self.outputs)?;
+
+        let mut dendrites_count: BTreeMap<&str, usize> = BTreeMap::new();
+        for neuron in self.neurons.iter() {
+            if dendrites_count
+                .insert(&neuron.id, neuron.input_configs.len())
+                .is_some()
+            {
+                return Err(Box::new(RnnError::NeuronAlreadyExists(neuron.id.clone())));
+            }
+        }
+
+        let check_neuron = |id: &str| {
+            dendrites_count
+                .get(id)
+                .copied()
+                .ok_or_else(|| Box::new(RnnError::NeuronNotFound(id.to_string())))
+        };
+        let mut busy_dendrites: BTreeSet<(String, usize)> = BTreeSet::new();
+        let mut occupy_dendrite = |id: &str, idx: usize| {
+            if idx >= check_neuron(id)? {
+                return Err(Box::new(RnnError::DendriteNotFound(idx)));
+            }
+            if !busy_dendrites.insert((id.to_string(), idx)) {
+                return Err(Box::new(RnnError::PortBusy(format!("{id}:{idx}"))));
+            }
+            Ok(())
+        };
+        let mut busy_outputs: BTreeSet<usize> = BTreeSet::new();
+
+        for link in self.links.iter() {
+            match link {
+                LinkCfg::Input {
+                    input_port,
+                    dst_id,
+                    dst_synapse_idx,
+                } => {
+                    if *input_port >= self.inputs {
+                        return Err(Box::new(RnnError::PortNotFound(*input_port)));
+                    }
+                    occupy_dendrite(dst_id, *dst_synapse_idx)?;
+                }
+                LinkCfg::Inner {
+                    src_id,
+                    dst_id,
+                    dst_synapse_idx,
+                } => {
+                    check_neuron(src_id)?;
+                    if src_id == dst_id && check_neuron(dst_id)? < 2 {
+                        return Err(Box::new(RnnError::ClosedLoop));
+                    }
+                    occupy_dendrite(dst_id, *dst_synapse_idx)?;
+                }
+                LinkCfg::Output {
+                    src_id,
+                    output_port,
+                } => {
+                    check_neuron(src_id)?;
+                    if *output_port >= self.outputs {
+                        return Err(Box::new(RnnError::PortNotFound(*output_port)));
+                    }
+                    if !busy_outputs.insert(*output_port) {
+                        return Err(Box::new(RnnError::PortBusy(format!("O{output_port}"))));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replace every composite (recursively) by its inner neurons. Inner neurons get
+    /// ids in the network of the outer ones numbered after them and lineage markers
+    /// prefixed by composite's id.
+    /// Inner input ports which are not fed through composite's synapses stay free and
+    /// inner output ports other than the axon one are dropped.
+    pub fn flatten(&self) -> Result<NetworkCfg, Box<dyn Error>> {
+        if self.composites.is_empty() {
+            return Ok(self.clone());
+        }
+
+        let outer_ids: Vec<&str> = self
+            .neurons
+            .iter()
+            .map(|neuron| neuron.id.as_str())
+            .chain(
+                self.composites
+                    .iter()
+                    .map(|composite| composite.id.as_str()),
+            )
+            .collect();
+        // inner neurons belong to the network of the outer ones
+        let network_id = outer_ids
+            .first()
+            .and_then(|id| extract_network_id_from(id))
+            .ok_or_else(|| Box::new(RnnError::NotSupportedArgValue))?;
+        let mut next_num = outer_ids
+            .iter()
+            .map(|id| get_component_id_fraction(id, &SpecificationType::Neuron))
+            .collect::<Result<Vec<usize>, _>>()?
+            .into_iter()
+            .max()
+            .map_or(0, |num| num + 1);
+
+        let mut neurons = self.neurons.clone();
+        let mut links = vec![];
+        let mut interfaces: BTreeMap<&str, CompositeInterface> = BTreeMap::new();
+
+        for composite in self.composites.iter() {
+            let inner = composite.network.flatten()?;
+            let mut ids: BTreeMap<&str, String> = BTreeMap::new();
+            for neuron in inner.neurons.iter() {
+                let id = gen_id_by_spec_type(&network_id, next_num, &SpecificationType::Neuron)?;
+                next_num += 1;
+                ids.insert(&neuron.id, id.clone());
+                neurons.push(NeuronCfg {
+                    id,
+                    lineage: Some(format!("{}/{}", composite.id, neuron.lineage_marker())),
+                    ..neuron.clone()
+                });
+            }
+            let map_id = |id: &str| {
+                ids.get(id)
+                    .cloned()
+                    .ok_or_else(|| Box::new(RnnError::NeuronNotFound(id.to_string())))
+            };
+
+            let mut synapses: BTreeMap<usize, Vec<(String, usize)>> = BTreeMap::new();
+            let mut axon = None;
+            for link in inner.links.iter() {
+                match link {
+                    LinkCfg::Input {
+                        input_port,
+                        dst_id,
+                        dst_synapse_idx,
+                    } => {
+                        synapses
+                            .entry(*input_port)
+                            .or_default()
+                            .push((map_id(dst_id)?, *dst_synapse_idx));
+                    }
+                    LinkCfg::Inner {
+                        src_id,
+                        dst_id,
+                        dst_synapse_idx,
+                    } => links.push(LinkCfg::Inner {
+                        src_id: map_id(src_id)?,
+                        dst_id: map_id(dst_id)?,
+                        dst_synapse_idx: *dst_synapse_idx,
+                    }),
+                    LinkCfg::Output {
+                        src_id,
+                        output_port,
+                    } => {
+                        if *output_port == composite.axon_port {
+                            axon = Some(map_id(src_id)?);
+                        }
+                    }
+                }
+            }
+            if interfaces
+                .insert(&composite.id, CompositeInterface { synapses, axon })
+                .is_some()
+            {
+                return Err(Box::new(RnnError::NeuronAlreadyExists(
+                    composite.id.clone(),
+                )));
+            }
+        }
+
+        let resolve_dst =
+            |dst_id: &String, dst_synapse_idx: usize| match interfaces.get(dst_id.as_str()) {
+                Some(interface) => interface
+                    .synapses
+                    .get(&dst_synapse_idx)
+                    .cloned()
+                    .ok_or_else(|| Box::new(RnnError::DendriteNotFound(dst_synapse_idx))),
+                None => Ok(vec![(dst_id.clone(), dst_synapse_idx)]),
+            };
+        let resolve_src = |src_id: &String| match interfaces.get(src_id.as_str()) {
+            Some(interface) => interface
+                .axon
+                .clone()
+                .ok_or_else(|| Box::new(RnnError::NeuronNotFound(src_id.clone()))),
+            None => Ok(src_id.clone()),
+        };
+        // The link into composite's synapse is expanded into links to every inner
+        // neuron fed by the inner input port.
+        for link in self.links.iter() {
+            match link {
+                LinkCfg::Input {
+                    input_port,
+                    dst_id,
+                    dst_synapse_idx,
+                } => {
+                    for (dst_id, dst_synapse_idx) in resolve_dst(dst_id, *dst_synapse_idx)? {
+                        links.push(LinkCfg::Input {
+                            input_port: *input_port,
+                            dst_id,
+                            dst_synapse_idx,
+                        });
+                    }
+                }
+                LinkCfg::Inner {
+                    src_id,
+                    dst_id,
+                    dst_synapse_idx,
+                } => {
+                    let src_id = resolve_src(src_id)?;
+                    for (dst_id, dst_synapse_idx) in resolve_dst(dst_id, *dst_synapse_idx)? {
+                        links.push(LinkCfg::Inner {
+                            src_id: src_id.clone(),
+                            dst_id,
+                            dst_synapse_idx,
+                        });
+                    }
+                }
+                LinkCfg::Output {
+                    src_id,
+                    output_port,
+                } => links.push(LinkCfg::Output {
+                    src_id: resolve_src(src_id)?,
+                    output_port: *output_port,
+                }),
+            }
+        }
+
+        Ok(NetworkCfg {
+            inputs: self.inputs,
+            outputs: self.outputs,
+            neurons,
+            links,
+            lineage: self.lineage.clone(),
+            composites: vec![],
+            labels: self.labels.clone(),
+            seed: self.seed,
+        })
+    }
+
+    /// GraphViz DOT graph of the topology. Neurons are labelled by id and bias,
+    /// inhibitory ones are filled, edges are labelled by the destination
+    /// dendrite index and its weight. Composites are drawn as single nodes.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph network {\n    rankdir=LR;\n");
+        for port in 0..self.inputs {
+            dot += &format!("    \"I{port}\" [shape=invhouse];\n");
+        }
+        for neuron in self.neurons.iter() {
+            let style = match neuron.kind {
+                NeuronKind::Excitatory => "",
+                NeuronKind::Inhibitory => ", style=filled, fillcolor=lightgray",
+            };
+            dot += &format!(
+                "    \"{}\" [shape=circle, label=\"{}\\nbias {}\"{style}];\n",
+                neuron.id, neuron.id, neuron.bias
+            );
+        }
+        for composite in self.composites.iter() {
+            dot += &format!("    \"{}\" [shape=box3d];\n", composite.id);
+        }
+        for port in 0..self.outputs {
+            let label = self
+                .labels
+                .get_labels()
+                .into_iter()
+                .find(|label| {
+                    self.labels
+                        .get_ports(label)
+                        .is_some_and(|ports| ports.contains(&port))
+                })
+                .map(|label| format!(", xlabel=\"{}\"", dot_escape(&label)))
+                .unwrap_or_default();
+            dot += &format!("    \"O{port}\" [shape=house{label}];\n");
+        }
+
+        let weight = |dst_id: &str, dst_synapse_idx: usize| {
+            self.neurons
+                .iter()
+                .find(|neuron| neuron.id == dst_id)
+                .and_then(|neuron| neuron.input_configs.get(dst_synapse_idx))
+                .map(|input_cfg| format!(": {}", input_cfg.weight))
+                .unwrap_or_default()
+        };
+        for link in self.links.iter() {
+            dot += &match link {
+                LinkCfg::Input {
+                    input_port,
+                    dst_id,
+                    dst_synapse_idx,
+                } => format!(
+                    "    \"I{input_port}\" -> \"{dst_id}\" [label=\"{dst_synapse_idx}{}\"];\n",
+                    weight(dst_id, *dst_synapse_idx)
+                ),
+                LinkCfg::Inner {
+                    src_id,
+                    dst_id,
+                    dst_synapse_idx,
+                } => format!(
+                    "    \"{src_id}\" -> \"{dst_id}\" [label=\"{dst_synapse_idx}{}\"];\n",
+                    weight(dst_id, *dst_synapse_idx)
+                ),
+                LinkCfg::Output {
+                    src_id,
+                    output_port,
+                } => format!("    \"{src_id}\" -> \"O{output_port}\";\n"),
+            };
+        }
+        dot += "}\n";
+        dot
+    }
+
+    /// Nodes of ports, neurons and composites with links between them.
+    /// Links into output ports have no synapse and weight.
+    pub fn graph_elements(&self) -> (Vec<Node>, Vec<(Node, Node, GraphLink)>) {
+        let mut nodes: Vec<Node> = (0..self.inputs).map(Node::Input).collect();
+        nodes.extend(
+            self.neurons
+                .iter()
+                .map(|neuron| Node::Neuron(neuron.id.clone())),
+        );
+        nodes.extend(
+            self.composites
+                .iter()
+                .map(|composite| Node::Neuron(composite.id.clone())),
+        );
+        nodes.extend((0..self.outputs).map(Node::Output));
+
+        let link = |dst_id: &str, dst_synapse_idx: usize| GraphLink {
+            synapse: Some(dst_synapse_idx),
+            weight: self
+                .neurons
+                .iter()
+                .find(|neuron| neuron.id == dst_id)
+                .and_then(|neuron| neuron.input_configs.get(dst_synapse_idx))
+                .map(|input_cfg| input_cfg.weight),
+        };
+        let edges = self
+            .links
+            .iter()
+            .map(|cfg_link| match cfg_link {
+                LinkCfg::Input {
+                    input_port,
+                    dst_id,
+                    dst_synapse_idx,
+                } => (
+                    Node::Input(*input_port),
+                    Node::Neuron(dst_id.clone()),
+                    link(dst_id, *dst_synapse_idx),
+                ),
+                LinkCfg::Inner {
+                    src_id,
+                    dst_id,
+                    dst_synapse_idx,
+                } => (
+                    Node::Neuron(src_id.clone()),
+                    Node::Neuron(dst_id.clone()),
+                    link(dst_id, *dst_synapse_idx),
+                ),
+                LinkCfg::Output {
+                    src_id,
+                    output_port,
+                } => (
+                    Node::Neuron(src_id.clone()),
+                    Node::Output(*output_port),
+                    GraphLink::default(),
+                ),
+            })
+            .collect();
+
+        (nodes, edges)
+    }
+
+    /// GraphML document of the topology. Nodes have kind and bias (neurons only)
+    /// attributes, edges have synapse and weight attributes (links into neurons only).
+    pub fn to_graphml(&self) -> String {
+        let (nodes, edges) = self.graph_elements();
+        let mut xml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+            "  <key id=\"bias\" for=\"node\" attr.name=\"bias\" attr.type=\"int\"/>\n",
+            "  <key id=\"synapse\" for=\"edge\" attr.name=\"synapse\" attr.type=\"int\"/>\n",
+            "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"int\"/>\n",
+            "  <graph id=\"network\" edgedefault=\"directed\">\n",
+        ));
+        for node in nodes.iter() {
+            let neuron = match node {
+                Node::Neuron(id) => self.neurons.iter().find(|neuron| neuron.id == *id),
+                _ => None,
+            };
+            let kind = match (node, neuron) {
+                (Node::Input(_), _) => "input",
+                (Node::Output(_), _) => "output",
+                (Node::Neuron(_), Some(neuron)) if neuron.kind == NeuronKind::Inhibitory => {
+                    "inhibitory"
+                }
+                (Node::Neuron(_), Some(_)) => "excitatory",
+                (Node::Neuron(_), None) => "composite",
+            };
+            xml += &format!(
+                "    <node id=\"{}\"><data key=\"kind\">{kind}</data>",
+                xml_escape(&node.to_string())
+            );
+            if let Some(neuron) = neuron {
+                xml += &format!("<data key=\"bias\">{}</data>", neuron.bias);
+            }
+            xml += "</node>\n";
+        }
+        for (src, dst, link) in edges.iter() {
+            xml += &format!(
+                "    <edge source=\"{}\" target=\"{}\">",
+                xml_escape(&src.to_string()),
+                xml_escape(&dst.to_string())
+            );
+            if let Some(synapse) = link.synapse {
+                xml += &format!("<data key=\"synapse\">{synapse}</data>");
+            }
+            if let Some(weight) = link.weight {
+                xml += &format!("<data key=\"weight\">{weight}</data>");
+            }
+            xml += "</edge>\n";
+        }
+        xml += "  </graph>\n</graphml>\n";
+        xml
+    }
+
+    /// Directed graph of the topology for petgraph algorithms (see graph_elements()).
+    #[cfg(feature = "petgraph")]
+    pub fn to_graph(&self) -> petgraph::graph::DiGraph<Node, GraphLink> {
+        let (nodes, edges) = self.graph_elements();
+        let mut graph = petgraph::graph::DiGraph::new();
+        let indices: BTreeMap<Node, petgraph::graph::NodeIndex> = nodes
+            .into_iter()
+            .map(|node| (node.clone(), graph.add_node(node)))
+            .collect();
+        for (src, dst, link) in edges {
+            // links of invalid configs may refer missed nodes
+            if let (Some(src), Some(dst)) = (indices.get(&src), indices.get(&dst)) {
+                graph.add_edge(*src, *dst, link);
+            }
+        }
+        graph
+    }
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
 }
 
 #[cfg(test)]
@@ -45,6 +737,22 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn neuron_kind_should_be_serialized_for_inhibitory_neurons_only() {
+        let mut neuron_cfg: NeuronCfg =
+            serde_json::from_value(json!({"id": "M0Z0", "bias": 0, "input_configs": []})).unwrap();
+        assert_eq!(neuron_cfg.kind, NeuronKind::Excitatory);
+        assert!(!serde_json::to_string(&neuron_cfg).unwrap().contains("kind"));
+
+        neuron_cfg.kind = NeuronKind::Inhibitory;
+        let neuron_json = serde_json::to_string(&neuron_cfg).unwrap();
+        assert!(neuron_json.contains("\"kind\":\"Inhibitory\""));
+        assert_eq!(
+            serde_json::from_str::<NeuronCfg>(&neuron_json).unwrap(),
+            neuron_cfg
+        );
+    }
+
     #[test]
     fn should_serialize_config_into_json_string() {
         let neuron_cfgs = vec![
@@ -55,16 +763,22 @@ mod tests {
                     InputCfg::new(2, 2, 1).unwrap(),
                     InputCfg::new(1, 1, 2).unwrap(),
                 ],
+                lineage: None,
+                kind: NeuronKind::Excitatory,
             },
             NeuronCfg {
                 id: String::from("M0Z1"),
                 bias: 1,
                 input_configs: vec![InputCfg::new(1, 1, 1).unwrap()],
+                lineage: None,
+                kind: NeuronKind::Excitatory,
             },
             NeuronCfg {
                 id: String::from("M0Z2"),
                 bias: 1,
                 input_configs: vec![InputCfg::new(3, 2, 1).unwrap()],
+                lineage: None,
+                kind: NeuronKind::Excitatory,
             },
             NeuronCfg {
                 id: String::from("M0Z3"),
@@ -73,6 +787,8 @@ mod tests {
                     InputCfg::new(1, 1, 1).unwrap(),
                     InputCfg::new(3, 1, 2).unwrap(),
                 ],
+                lineage: None,
+                kind: NeuronKind::Excitatory,
             },
         ];
         let cfg = NetworkCfg {
@@ -119,6 +835,10 @@ mod tests {
                     output_port: 1,
                 },
             ],
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
         };
 
         let cfg_json = serde_json::to_string(&cfg).unwrap();
@@ -137,11 +857,15 @@ mod tests {
                     InputCfg::new(3, 2, 1).unwrap(),
                     InputCfg::new(1, 1, 2).unwrap(),
                 ],
+                lineage: None,
+                kind: NeuronKind::Excitatory,
             },
             NeuronCfg {
                 id: String::from("M0Z1"),
                 bias: 1,
                 input_configs: vec![InputCfg::new(1, 1, 1).unwrap()],
+                lineage: None,
+                kind: NeuronKind::Excitatory,
             },
         ];
         let cfg = NetworkCfg {
@@ -169,6 +893,10 @@ mod tests {
                     output_port: 0,
                 },
             ],
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
         };
 
         let cfg_yaml = serde_yaml::to_string(&cfg).unwrap();
@@ -246,10 +974,10 @@ mod tests {
         assert_eq!(cfg.inputs, 2);
         assert_eq!(cfg.outputs, 1);
         assert_eq!(cfg.neurons.len(), 2);
-        let neuron_cfg_0 = cfg.neurons.iter().nth(0).unwrap();
+        let neuron_cfg_0 = cfg.neurons.first().unwrap();
         assert_eq!(neuron_cfg_0.id, "M0Z0");
         assert_eq!(neuron_cfg_0.input_configs.len(), 2);
-        let neuron_cfg_1 = cfg.neurons.iter().nth(1).unwrap();
+        let neuron_cfg_1 = cfg.neurons.get(1).unwrap();
         assert_eq!(neuron_cfg_1.id, "M0Z1");
         assert_eq!(neuron_cfg_1.input_configs.len(), 1);
     }
@@ -293,15 +1021,430 @@ mod tests {
               output_port: 0
         ";
 
-        let cfg: NetworkCfg = serde_yaml::from_str(&cfg_yaml).unwrap();
+        let cfg: NetworkCfg = serde_yaml::from_str(cfg_yaml).unwrap();
         assert_eq!(cfg.inputs, 2);
         assert_eq!(cfg.outputs, 1);
         assert_eq!(cfg.neurons.len(), 2);
-        let neuron_cfg_0 = cfg.neurons.iter().nth(0).unwrap();
+        let neuron_cfg_0 = cfg.neurons.first().unwrap();
         assert_eq!(neuron_cfg_0.id, "M0Z0");
         assert_eq!(neuron_cfg_0.input_configs.len(), 2);
-        let neuron_cfg_1 = cfg.neurons.iter().nth(1).unwrap();
+        let neuron_cfg_1 = cfg.neurons.get(1).unwrap();
         assert_eq!(neuron_cfg_1.id, "M0Z1");
         assert_eq!(neuron_cfg_1.input_configs.len(), 1);
     }
+
+    fn valid_config_fixture() -> NetworkCfg {
+        NetworkCfg {
+            inputs: 1,
+            outputs: 1,
+            neurons: vec![
+                NeuronCfg {
+                    id: String::from("M0Z0"),
+                    bias: 1,
+                    input_configs: vec![InputCfg::new(1, 1, 1).unwrap()],
+                    lineage: None,
+                    kind: NeuronKind::Excitatory,
+                },
+                NeuronCfg {
+                    id: String::from("M0Z1"),
+                    bias: 1,
+                    input_configs: vec![InputCfg::new(1, 1, 1).unwrap()],
+                    lineage: None,
+                    kind: NeuronKind::Excitatory,
+                },
+            ],
+            links: vec![
+                LinkCfg::Input {
+                    input_port: 0,
+                    dst_id: String::from("M0Z0"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Inner {
+                    src_id: String::from("M0Z0"),
+                    dst_id: String::from("M0Z1"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Output {
+                    src_id: String::from("M0Z1"),
+                    output_port: 0,
+                },
+            ],
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn dot_should_describe_ports_neurons_and_weighted_links() {
+        let mut cfg = valid_config_fixture();
+        cfg.neurons[1].kind = NeuronKind::Inhibitory;
+        cfg.neurons[1].input_configs[0].weight = 3;
+
+        let dot = cfg.to_dot();
+
+        assert!(dot.starts_with("digraph network {"));
+        assert!(dot.contains("\"I0\" [shape=invhouse];"));
+        assert!(dot.contains("\"M0Z0\" [shape=circle, label=\"M0Z0\\nbias 1\"];"));
+        assert!(dot.contains("\"M0Z1\" [shape=circle, label=\"M0Z1\\nbias 1\", style=filled"));
+        assert!(dot.contains("\"I0\" -> \"M0Z0\" [label=\"0: 1\"];"));
+        assert!(dot.contains("\"M0Z0\" -> \"M0Z1\" [label=\"0: 3\"];"));
+        assert!(dot.contains("\"M0Z1\" -> \"O0\";"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn dot_should_escape_output_labels() {
+        let mut cfg = valid_config_fixture();
+        cfg.labels = LabelMap::one_hot(&["say \"hi\" \\o/"]);
+
+        let dot = cfg.to_dot();
+
+        assert!(dot.contains("\"O0\" [shape=house, xlabel=\"say \\\"hi\\\" \\\\o/\"];"));
+    }
+
+    #[test]
+    fn graphml_should_describe_nodes_and_edges_with_attributes() {
+        let graphml = valid_config_fixture().to_graphml();
+
+        assert!(graphml.starts_with("<?xml"));
+        assert!(graphml.contains("<graph id=\"network\" edgedefault=\"directed\">"));
+        assert!(graphml.contains("<node id=\"I0\"><data key=\"kind\">input</data></node>"));
+        assert!(graphml.contains(
+            "<node id=\"M0Z1\"><data key=\"kind\">excitatory</data><data key=\"bias\">1</data></node>"
+        ));
+        assert!(graphml.contains(
+            "<edge source=\"M0Z0\" target=\"M0Z1\"><data key=\"synapse\">0</data><data key=\"weight\">1</data></edge>"
+        ));
+        assert!(graphml.contains("<edge source=\"M0Z1\" target=\"O0\"></edge>"));
+        assert!(graphml.trim_end().ends_with("</graphml>"));
+    }
+
+    #[cfg(feature = "petgraph")]
+    #[test]
+    fn graph_should_keep_nodes_and_links_of_config() {
+        let cfg = valid_config_fixture();
+
+        let graph = cfg.to_graph();
+
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), cfg.links.len());
+        assert!(!petgraph::algo::is_cyclic_directed(&graph));
+        let (src, dst) = graph
+            .edge_indices()
+            .map(|edge| graph.edge_endpoints(edge).unwrap())
+            .find(|(_, dst)| graph[*dst] == Node::Neuron(String::from("M0Z1")))
+            .unwrap();
+        assert_eq!(graph[src], Node::Neuron(String::from("M0Z0")));
+        assert_eq!(
+            graph[graph.find_edge(src, dst).unwrap()],
+            GraphLink {
+                synapse: Some(0),
+                weight: Some(1)
+            }
+        );
+    }
+
+    #[test]
+    fn should_validate_correct_config() {
+        assert!(valid_config_fixture().validate().is_ok());
+    }
+
+    #[test]
+    fn should_not_validate_config_with_dangling_links() {
+        let mut cfg = valid_config_fixture();
+        cfg.neurons.pop();
+        assert!(cfg.validate().is_err());
+
+        let mut cfg = valid_config_fixture();
+        cfg.links.push(LinkCfg::Input {
+            input_port: 0,
+            dst_id: String::from("M0Z1"),
+            dst_synapse_idx: 1,
+        });
+        assert!(cfg.validate().is_err());
+
+        let mut cfg = valid_config_fixture();
+        cfg.links.push(LinkCfg::Output {
+            src_id: String::from("M0Z0"),
+            output_port: 1,
+        });
+        assert!(cfg.validate().is_err());
+    }
+
+    #[test]
+    fn should_not_validate_config_with_busy_dendrite() {
+        let mut cfg = valid_config_fixture();
+        cfg.links.push(LinkCfg::Inner {
+            src_id: String::from("M0Z0"),
+            dst_id: String::from("M0Z1"),
+            dst_synapse_idx: 0,
+        });
+        assert!(cfg.validate().is_err());
+    }
+
+    #[test]
+    fn should_validate_config_with_fanned_out_input_port() {
+        let mut cfg = valid_config_fixture();
+        cfg.neurons.push(NeuronCfg {
+            id: String::from("M0Z2"),
+            bias: 1,
+            input_configs: vec![InputCfg::new(1, 1, 1).unwrap()],
+            lineage: None,
+            kind: NeuronKind::Excitatory,
+        });
+        cfg.links.push(LinkCfg::Input {
+            input_port: 0,
+            dst_id: String::from("M0Z2"),
+            dst_synapse_idx: 0,
+        });
+        assert!(cfg.validate().is_ok());
+    }
+
+    #[test]
+    fn lineage_marker_should_fall_back_to_own_id() {
+        let mut cfg = valid_config_fixture();
+        assert_eq!(cfg.neurons[0].lineage_marker(), "M0Z0");
+
+        cfg.neurons[0].lineage = Some(String::from("M5Z3"));
+        assert_eq!(cfg.neurons[0].lineage_marker(), "M5Z3");
+    }
+
+    #[test]
+    fn fingerprint_should_depend_on_config_content() {
+        let cfg = valid_config_fixture();
+        assert_eq!(cfg.fingerprint(), valid_config_fixture().fingerprint());
+        assert_eq!(cfg.fingerprint().len(), 16);
+
+        let mut changed = valid_config_fixture();
+        changed.neurons[1].bias = 2;
+        assert_ne!(cfg.fingerprint(), changed.fingerprint());
+    }
+
+    #[test]
+    fn descendant_should_be_next_generation_of_latest_parent() {
+        let first = valid_config_fixture();
+        let mut second = valid_config_fixture();
+        second.lineage = Some(NetworkLineage {
+            generation: 4,
+            ..NetworkLineage::default()
+        });
+
+        let lineage = NetworkLineage::descend(
+            &[&first, &second],
+            "crossover",
+            vec![String::from("2 genes taken from other parent")],
+        );
+
+        assert_eq!(lineage.generation, 5);
+        assert_eq!(
+            lineage.parents,
+            vec![first.fingerprint(), second.fingerprint()]
+        );
+        assert_eq!(lineage.trainer, "crossover");
+    }
+
+    #[test]
+    fn lineage_should_survive_json_round_trip() {
+        let mut cfg = valid_config_fixture();
+        assert!(!serde_json::to_string(&cfg).unwrap().contains("lineage"));
+
+        cfg.lineage = Some(NetworkLineage::descend(
+            &[&valid_config_fixture()],
+            "manual",
+            vec![],
+        ));
+        let cfg_json = serde_json::to_string(&cfg).unwrap();
+        let restored: NetworkCfg = serde_json::from_str(&cfg_json).unwrap();
+
+        assert_eq!(restored, cfg);
+    }
+
+    /// valid_config_fixture() where neuron M0Z1 is replaced by the composite of the same shape
+    fn composite_config_fixture() -> NetworkCfg {
+        let mut cfg = valid_config_fixture();
+        cfg.neurons.truncate(1);
+        cfg.composites.push(CompositeCfg {
+            id: String::from("M0Z1"),
+            network: valid_config_fixture(),
+            axon_port: 0,
+        });
+        cfg
+    }
+
+    #[test]
+    fn should_flatten_composite_into_inner_neurons() {
+        let flat = composite_config_fixture().flatten().unwrap();
+
+        assert!(flat.composites.is_empty());
+        let ids: Vec<&str> = flat
+            .neurons
+            .iter()
+            .map(|neuron| neuron.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["M0Z0", "M0Z2", "M0Z3"]);
+        assert_eq!(flat.neurons[1].lineage_marker(), "M0Z1/M0Z0");
+        assert_eq!(
+            flat.links,
+            vec![
+                LinkCfg::Inner {
+                    src_id: String::from("M0Z2"),
+                    dst_id: String::from("M0Z3"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Input {
+                    input_port: 0,
+                    dst_id: String::from("M0Z0"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Inner {
+                    src_id: String::from("M0Z0"),
+                    dst_id: String::from("M0Z2"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Output {
+                    src_id: String::from("M0Z3"),
+                    output_port: 0,
+                },
+            ]
+        );
+        assert!(composite_config_fixture().validate().is_ok());
+    }
+
+    #[test]
+    fn should_number_inner_neurons_in_network_of_outer_ones() {
+        let mut cfg = composite_config_fixture();
+        cfg.neurons[0].id = String::from("M3Z0");
+        cfg.composites[0].id = String::from("M3Z1");
+        let links_json = serde_json::to_string(&cfg.links).unwrap();
+        cfg.links = serde_json::from_str(&links_json.replace("M0Z", "M3Z")).unwrap();
+
+        let flat = cfg.flatten().unwrap();
+
+        let ids: Vec<&str> = flat
+            .neurons
+            .iter()
+            .map(|neuron| neuron.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["M3Z0", "M3Z2", "M3Z3"]);
+    }
+
+    #[test]
+    fn should_flatten_composite_synapse_into_every_fed_neuron() {
+        // inner input port 0 feeds both inner neurons
+        let mut cfg = composite_config_fixture();
+        let inner = &mut cfg.composites[0].network;
+        inner.neurons[1]
+            .input_configs
+            .push(InputCfg::new(1, 1, 1).unwrap());
+        inner.links.push(LinkCfg::Input {
+            input_port: 0,
+            dst_id: String::from("M0Z1"),
+            dst_synapse_idx: 1,
+        });
+
+        let flat = cfg.flatten().unwrap();
+
+        for (dst_id, dst_synapse_idx) in [("M0Z2", 0), ("M0Z3", 1)] {
+            assert!(flat.links.contains(&LinkCfg::Inner {
+                src_id: String::from("M0Z0"),
+                dst_id: String::from(dst_id),
+                dst_synapse_idx,
+            }));
+        }
+        assert!(flat.validate().is_ok());
+    }
+
+    #[test]
+    fn should_flatten_input_port_into_every_fed_neuron() {
+        // the outer input port feeds the composite whose input port feeds both inner neurons
+        let mut cfg = composite_config_fixture();
+        let inner = &mut cfg.composites[0].network;
+        inner.neurons[1]
+            .input_configs
+            .push(InputCfg::new(1, 1, 1).unwrap());
+        inner.links.push(LinkCfg::Input {
+            input_port: 0,
+            dst_id: String::from("M0Z1"),
+            dst_synapse_idx: 1,
+        });
+        cfg.links[1] = LinkCfg::Input {
+            input_port: 0,
+            dst_id: String::from("M0Z1"),
+            dst_synapse_idx: 0,
+        };
+
+        let flat = cfg.flatten().unwrap();
+
+        for (dst_id, dst_synapse_idx) in [("M0Z0", 0), ("M0Z2", 0), ("M0Z3", 1)] {
+            assert!(flat.links.contains(&LinkCfg::Input {
+                input_port: 0,
+                dst_id: String::from(dst_id),
+                dst_synapse_idx,
+            }));
+        }
+        assert!(flat.validate().is_ok());
+    }
+
+    #[test]
+    fn should_not_flatten_link_to_missed_composite_synapse() {
+        let mut cfg = composite_config_fixture();
+        cfg.links[1] = LinkCfg::Inner {
+            src_id: String::from("M0Z0"),
+            dst_id: String::from("M0Z1"),
+            dst_synapse_idx: 1,
+        };
+
+        assert!(cfg.flatten().is_err());
+        assert!(cfg.validate().is_err());
+    }
+
+    #[test]
+    fn composite_should_survive_json_round_trip() {
+        let cfg = composite_config_fixture();
+        let cfg_json = serde_json::to_string(&cfg).unwrap();
+        let restored: NetworkCfg = serde_json::from_str(&cfg_json).unwrap();
+
+        assert_eq!(restored, cfg);
+        assert!(!serde_json::to_string(&valid_config_fixture())
+            .unwrap()
+            .contains("composites"));
+    }
+
+    #[test]
+    fn label_map_should_decode_fired_port_set() {
+        let mut labels = LabelMap::one_hot(&["cat", "dog"]);
+        assert!(labels.insert("both", BTreeSet::from([0, 1])).is_ok());
+        assert!(labels.insert("none", BTreeSet::new()).is_err());
+        assert!(labels.insert("kitten", BTreeSet::from([0])).is_err());
+
+        assert_eq!(labels.decode(&[3, 0]), Some(String::from("cat")));
+        assert_eq!(labels.decode(&[0, 1]), Some(String::from("dog")));
+        assert_eq!(labels.decode(&[2, 2]), Some(String::from("both")));
+        assert_eq!(labels.decode(&[0, 0]), None);
+        assert_eq!(labels.encode("dog", 2), Some(vec![0, Signal::MAX]));
+    }
+
+    #[test]
+    fn should_not_bind_labels_to_missed_output_ports() {
+        let mut cfg = valid_config_fixture();
+
+        assert!(cfg.bind_labels(LabelMap::one_hot(&["yes", "no"])).is_err());
+        assert!(cfg.bind_labels(LabelMap::one_hot(&["yes"])).is_ok());
+        assert!(cfg.validate().is_ok());
+        cfg.outputs = 0;
+        assert!(cfg.validate().is_err());
+    }
+
+    #[test]
+    fn labels_should_survive_json_round_trip() {
+        let mut cfg = valid_config_fixture();
+        cfg.bind_labels(LabelMap::one_hot(&["yes"])).unwrap();
+        let cfg_json = serde_json::to_string(&cfg).unwrap();
+        let restored: NetworkCfg = serde_json::from_str(&cfg_json).unwrap();
+
+        assert_eq!(restored, cfg);
+        assert!(cfg_json.contains("\"labels\":{\"yes\":[0]}"));
+    }
 }
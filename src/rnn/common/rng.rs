@@ -0,0 +1,116 @@
+use crate::rnn::common::clock::{Clock, SystemClock};
+
+/// Small deterministic pseudo random generator (SplitMix64).
+/// It is used where generated topology has to be reproducible by seed.
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        SeededRng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniformly distributed value in range [0, 1).
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Returns true with probability p.
+    pub fn gen_bool(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    /// Uniformly distributed index in range [0, len). The len should be positive.
+    pub fn gen_index(&mut self, len: usize) -> usize {
+        (self.next_f64() * len as f64) as usize
+    }
+}
+
+/// The source of seeded generators for the network and tools working with it.
+/// Every consumer takes its own stream by name, so a new consumer does not
+/// shift sequences of others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RngProvider {
+    seed: u64,
+}
+
+impl RngProvider {
+    pub fn new(seed: u64) -> Self {
+        RngProvider { seed }
+    }
+
+    /// The provider seeded by current time.
+    pub fn from_entropy() -> Self {
+        let nanos = SystemClock.now().timestamp_nanos_opt().unwrap_or(0) as u64;
+        RngProvider::new(SeededRng::new(nanos).next_u64())
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The seed of the named stream, for APIs which take the seed (e.g. TopologyParams).
+    pub fn seed_for(&self, stream: &str) -> u64 {
+        let hash = stream.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        SeededRng::new(self.seed ^ hash).next_u64()
+    }
+
+    /// The generator of the named stream.
+    pub fn stream(&self, stream: &str) -> SeededRng {
+        SeededRng::new(self.seed_for(stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_should_produce_same_sequence() {
+        let mut a = SeededRng::new(42);
+        let mut b = SeededRng::new(42);
+        let mut c = SeededRng::new(43);
+
+        let sequence: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        assert_eq!(sequence, (0..5).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(sequence, (0..5).map(|_| c.next_u64()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn values_should_be_within_range() {
+        let mut rng = SeededRng::new(7);
+        for _ in 0..1000 {
+            assert!((0.0..1.0).contains(&rng.next_f64()));
+            assert!(rng.gen_index(3) < 3);
+        }
+        assert!(!rng.gen_bool(0.0));
+        assert!(rng.gen_bool(1.0));
+    }
+
+    #[test]
+    fn provider_streams_should_depend_on_seed_and_name() {
+        let provider = RngProvider::new(42);
+
+        assert_eq!(
+            provider.stream("noise").next_u64(),
+            RngProvider::new(42).stream("noise").next_u64()
+        );
+        assert_ne!(provider.seed_for("noise"), provider.seed_for("topology"));
+        assert_ne!(
+            provider.seed_for("noise"),
+            RngProvider::new(43).seed_for("noise")
+        );
+    }
+}
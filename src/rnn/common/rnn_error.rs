@@ -33,17 +33,47 @@ pub enum RnnError {
     /// Indicate then signal sending into channel whit no one receivers
     SignalSendError,
 
+    /// The signal channel is full with BackpressurePolicy::Error.
+    ChannelFull(String),
+
     /// Not connected axon
     DeadEndAxon,
 
     /// Port is not connected to any neurons.
     PortAlreadyFree,
 
+    /// The neuron's accumulator overflowed with OverflowPolicy::Error.
+    AccumulatorOverflow(String),
+
+    /// Synapse is connected to another party than specified one.
+    NotConnected(String),
+
     /// When attempt to send neuron status but channel has been closed.
     MonitoringChannelClosed(String),
 
     /// When attempt to send neuron status but channel is full.
     MonitoringChannelFull(String),
+
+    /// Network does not have checkpoint with specified name.
+    CheckpointNotFound(String),
+
+    /// Network does not have neuron group with specified name.
+    GroupNotFound(String),
+
+    /// Neuron group with specified name already exists.
+    GroupAlreadyExists(String),
+
+    /// Registry does not have network with specified name.
+    NetworkNotFound(String),
+
+    /// Network with specified name already registered.
+    NetworkAlreadyExists(String),
+
+    /// Model file was written in newer format version than supported one.
+    UnsupportedModelVersion(u32),
+
+    /// Record of ingested data can't be parsed or lacks mapped fields.
+    MalformedRecord(String),
 }
 
 impl std::fmt::Display for RnnError {
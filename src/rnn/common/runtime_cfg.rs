@@ -0,0 +1,333 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::signal::Weight;
+
+static DEFAULT_MAILBOX_CAPACITY: usize = 64;
+static DEFAULT_EVENT_WORKERS: usize = 4;
+static DEFAULT_SHARDS: usize = 8;
+static DEFAULT_CHANNEL_CAPACITY: usize = 5;
+static DEFAULT_MONITORING_STREAM_CAPACITY: usize = 64;
+
+/// The way signals are delivered from the axon of one neuron to synapses of others.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoutingBackend {
+    /// Each axon is a broadcast channel and every connected synapse
+    /// subscribes to it and listens in a separate task.
+    #[default]
+    Broadcast,
+
+    /// Each neuron owns one mpsc mailbox, and the network keeps a routing table
+    /// which maps the neuron's axon to the mailboxes of connected neurons.
+    Mailbox,
+
+    /// Signals are events of the network's queue ordered by logical time.
+    /// The queue is partitioned by destination neuron onto `event_workers` tasks
+    /// which deliver them into synapses.
+    EventDriven,
+
+    /// Neurons are grouped by id hash onto `shards` worker tasks,
+    /// each of them reads its own mpsc queue of signals.
+    Sharded,
+}
+
+/// What a neuron does with its status when the monitoring channel is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MonitoringOverflow {
+    /// Wait for free space in the channel.
+    #[default]
+    Block,
+
+    /// Drop the status and count it.
+    Drop,
+
+    /// Keep only the latest status of the neuron until the channel has free space.
+    /// Replaced statuses are counted.
+    Coalesce,
+}
+
+/// Which statuses the bounded monitoring store keeps when it is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StoreRetention {
+    /// Drop the oldest status to keep the new one.
+    #[default]
+    KeepLatest,
+
+    /// Drop new statuses until the store is popped.
+    KeepEarliest,
+}
+
+/// The in-memory store of monitoring statuses and the stream of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonitoringStoreCfg {
+    /// The maximal number of kept statuses. None keeps all of them,
+    /// zero keeps nothing, so statuses are available through the stream only.
+    pub capacity: Option<usize>,
+
+    pub retention: StoreRetention,
+
+    /// The broadcast channel of the monitoring stream
+    pub stream_capacity: usize,
+}
+
+impl Default for MonitoringStoreCfg {
+    fn default() -> Self {
+        MonitoringStoreCfg {
+            capacity: None,
+            retention: StoreRetention::default(),
+            stream_capacity: DEFAULT_MONITORING_STREAM_CAPACITY,
+        }
+    }
+}
+
+/// What a neuron does when the accumulator or the weighted signal overflows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverflowPolicy {
+    /// Clamp the value to the bounds of Weight.
+    #[default]
+    Saturate,
+
+    /// Wrap around the bounds (two's complement).
+    Wrap,
+
+    /// Reject the signal with RnnError::AccumulatorOverflow keeping the accumulator.
+    Error,
+}
+
+impl OverflowPolicy {
+    pub fn add(&self, a: Weight, b: Weight) -> Option<Weight> {
+        match self {
+            OverflowPolicy::Saturate => Some(a.saturating_add(b)),
+            OverflowPolicy::Wrap => Some(a.wrapping_add(b)),
+            OverflowPolicy::Error => a.checked_add(b),
+        }
+    }
+
+    pub fn mul(&self, a: Weight, b: Weight) -> Option<Weight> {
+        match self {
+            OverflowPolicy::Saturate => Some(a.saturating_mul(b)),
+            OverflowPolicy::Wrap => Some(a.wrapping_mul(b)),
+            OverflowPolicy::Error => a.checked_mul(b),
+        }
+    }
+}
+
+/// Capacities of the network's channels.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChannelCapacities {
+    /// The broadcast channel of network commands
+    pub commands: usize,
+
+    /// The channel of statuses sent by neurons and ports
+    pub monitoring: usize,
+
+    /// The broadcast channel of every input port
+    pub input_port: usize,
+
+    /// The broadcast channel of every neuron's axon
+    pub axon: usize,
+}
+
+impl ChannelCapacities {
+    pub fn is_valid(&self) -> bool {
+        self.commands > 0 && self.monitoring > 0 && self.input_port > 0 && self.axon > 0
+    }
+}
+
+impl Default for ChannelCapacities {
+    fn default() -> Self {
+        ChannelCapacities {
+            commands: DEFAULT_CHANNEL_CAPACITY,
+            monitoring: DEFAULT_CHANNEL_CAPACITY,
+            input_port: DEFAULT_CHANNEL_CAPACITY,
+            axon: DEFAULT_CHANNEL_CAPACITY,
+        }
+    }
+}
+
+/// What the sender does when the signal channel is full: the input port, the axon,
+/// the neuron's mailbox, the shard's queue or the event queue's partition.
+/// Mailboxes and queues can't overwrite their oldest signal, so they drop the new one
+/// instead and count it in RoutingCounters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackpressurePolicy {
+    /// Overwrite the oldest signal. Lagging receivers lose it.
+    #[default]
+    DropOldest,
+
+    /// Wait for free space. Only input ports wait, without holding locks of the network.
+    /// Axons, mailboxes and queues are fed by neurons, which drop signals as with
+    /// DropOldest: a neuron waiting for its own (indirect) receiver would deadlock
+    /// cyclic topologies.
+    Block,
+
+    /// Reject the signal with RnnError::ChannelFull.
+    Error,
+}
+
+/// What the lagging synapse does after it lost signals of the broadcast channel.
+/// Lost signals are counted and reported as Status::PortLagged in any case.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LagRecovery {
+    /// Continue with the oldest signal still kept in the channel.
+    #[default]
+    Resume,
+
+    /// Skip all kept signals except the latest one.
+    SkipToLatest,
+}
+
+/// How the network advances in time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SimulationMode {
+    /// Signals are processed as soon as they come.
+    #[default]
+    Async,
+
+    /// Input signals are queued until Network::step() delivers them and waits
+    /// until all neurons settle. Requires RoutingBackend::Mailbox or RoutingBackend::EventDriven.
+    Stepped,
+}
+
+/// The runtime configuration of the network.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeCfg {
+    /// Signal routing backend between inner neurons.
+    pub routing: RoutingBackend,
+
+    /// Capacity of the neuron's mailbox (RoutingBackend::Mailbox), the shard's queue
+    /// (RoutingBackend::Sharded) or the event queue's partition (RoutingBackend::EventDriven).
+    pub mailbox_capacity: usize,
+
+    /// The number of event queue's partitions, each of them is processed by its own task.
+    /// Used with RoutingBackend::EventDriven only.
+    #[serde(default = "default_event_workers")]
+    pub event_workers: usize,
+
+    /// The number of shards. Used with RoutingBackend::Sharded only.
+    #[serde(default = "default_shards")]
+    pub shards: usize,
+
+    /// Overflow policy of the monitoring channel.
+    #[serde(default)]
+    pub monitoring_overflow: MonitoringOverflow,
+
+    #[serde(default)]
+    pub simulation: SimulationMode,
+
+    /// Overflow policy of neurons' accumulators.
+    #[serde(default)]
+    pub accumulator_overflow: OverflowPolicy,
+
+    #[serde(default)]
+    pub channels: ChannelCapacities,
+
+    /// Policy applied when the channel of input port, axon, mailbox or queue is full.
+    #[serde(default)]
+    pub backpressure: BackpressurePolicy,
+
+    #[serde(default)]
+    pub lag_recovery: LagRecovery,
+
+    /// The time after firing while neurons ignore incoming signals.
+    /// Ignored signals are counted in NeuronInfo::refractory_count. Zero disables the period.
+    #[serde(default)]
+    pub refractory_period: Duration,
+
+    /// The time constant of the accumulator's exponential decay toward the bias
+    /// (leaky integrate-and-fire). None keeps the accumulator until the neuron fires.
+    #[serde(default)]
+    pub leak_time_constant: Option<Duration>,
+
+    /// The in-memory monitoring store and the monitoring stream.
+    #[serde(default)]
+    pub monitoring_store: MonitoringStoreCfg,
+}
+
+fn default_event_workers() -> usize {
+    DEFAULT_EVENT_WORKERS
+}
+
+fn default_shards() -> usize {
+    DEFAULT_SHARDS
+}
+
+impl Default for RuntimeCfg {
+    fn default() -> Self {
+        RuntimeCfg {
+            routing: RoutingBackend::default(),
+            mailbox_capacity: DEFAULT_MAILBOX_CAPACITY,
+            event_workers: DEFAULT_EVENT_WORKERS,
+            shards: DEFAULT_SHARDS,
+            monitoring_overflow: MonitoringOverflow::default(),
+            simulation: SimulationMode::default(),
+            accumulator_overflow: OverflowPolicy::default(),
+            channels: ChannelCapacities::default(),
+            backpressure: BackpressurePolicy::default(),
+            lag_recovery: LagRecovery::default(),
+            refractory_period: Duration::ZERO,
+            leak_time_constant: None,
+            monitoring_store: MonitoringStoreCfg::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_should_use_broadcast_routing() {
+        let cfg = RuntimeCfg::default();
+
+        assert_eq!(cfg.routing, RoutingBackend::Broadcast);
+        assert_eq!(cfg.mailbox_capacity, DEFAULT_MAILBOX_CAPACITY);
+    }
+
+    #[test]
+    fn channel_capacities_should_be_partially_configured() {
+        let cfg_yaml = "routing: Broadcast\nmailbox_capacity: 16\nchannels:\n  axon: 32\n";
+        let cfg: RuntimeCfg = serde_yaml::from_str(cfg_yaml).unwrap();
+
+        assert_eq!(cfg.channels.axon, 32);
+        assert_eq!(cfg.channels.input_port, DEFAULT_CHANNEL_CAPACITY);
+        assert!(cfg.channels.is_valid());
+        assert!(!ChannelCapacities {
+            commands: 0,
+            ..ChannelCapacities::default()
+        }
+        .is_valid());
+    }
+
+    #[test]
+    fn config_should_deserialize_from_yaml_string() {
+        let cfg_yaml = "routing: Mailbox\nmailbox_capacity: 16\n";
+        let cfg: RuntimeCfg = serde_yaml::from_str(cfg_yaml).unwrap();
+
+        assert_eq!(cfg.routing, RoutingBackend::Mailbox);
+        assert_eq!(cfg.mailbox_capacity, 16);
+        assert_eq!(cfg.event_workers, DEFAULT_EVENT_WORKERS);
+        assert_eq!(cfg.shards, DEFAULT_SHARDS);
+        assert_eq!(cfg.monitoring_overflow, MonitoringOverflow::Block);
+        assert_eq!(cfg.simulation, SimulationMode::Async);
+        assert_eq!(cfg.accumulator_overflow, OverflowPolicy::Saturate);
+        assert_eq!(cfg.channels, ChannelCapacities::default());
+        assert_eq!(cfg.backpressure, BackpressurePolicy::DropOldest);
+        assert_eq!(cfg.lag_recovery, LagRecovery::Resume);
+        assert_eq!(cfg.refractory_period, Duration::ZERO);
+        assert_eq!(cfg.leak_time_constant, None);
+        assert_eq!(cfg.monitoring_store, MonitoringStoreCfg::default());
+    }
+
+    #[test]
+    fn overflow_policy_should_define_result_of_overflowed_operation() {
+        assert_eq!(OverflowPolicy::Saturate.add(i16::MAX, 1), Some(i16::MAX));
+        assert_eq!(OverflowPolicy::Wrap.add(i16::MAX, 1), Some(i16::MIN));
+        assert_eq!(OverflowPolicy::Error.add(i16::MAX, 1), None);
+        assert_eq!(OverflowPolicy::Saturate.mul(255, -200), Some(i16::MIN));
+        assert_eq!(OverflowPolicy::Error.mul(255, 2), Some(510));
+    }
+}
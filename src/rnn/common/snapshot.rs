@@ -0,0 +1,189 @@
+//! Dynamic state of the running network which is not described by its config,
+//! so long simulations can be suspended and resumed (see Network::snapshot()
+//! and Network::restore()). Deltas between snapshots keep warm standby replicas
+//! in sync with the primary network (see Network::apply_delta()).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::network_cfg::NetworkCfg;
+use super::signal::{Signal, Weight};
+
+/// Runtime state of the neuron captured by Neuron::get_state().
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NeuronState {
+    pub id: String,
+
+    pub accumulator: Weight,
+
+    pub reset_count: u64,
+
+    pub hit_count: u64,
+
+    pub refractory_count: u64,
+
+    pub fired_at: Option<DateTime<Utc>>,
+
+    /// The time of the latest accumulation (used by the leak)
+    pub accumulated_at: Option<DateTime<Utc>>,
+
+    /// Ports which already received signals in the current accumulation cycle
+    pub input_hits: BTreeSet<usize>,
+
+    /// Current capacities of synapses by port
+    pub synapse_capacities: BTreeMap<usize, Signal>,
+
+    /// Signals lost by lagging synapses by port
+    pub dropped_signals: BTreeMap<usize, u64>,
+
+    /// Signals buffered by the paused neuron as (port, signal).
+    /// None when the neuron is not paused.
+    pub paused_signals: Option<Vec<(usize, Signal)>>,
+}
+
+/// Topology and dynamic state of all neurons of the network.
+/// Signals in flight between neurons are not captured (only signals buffered
+/// by paused neurons), so pause the network first to get the consistent state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkSnapshot {
+    pub timestamp: DateTime<Utc>,
+
+    pub config: NetworkCfg,
+
+    /// States of neurons ordered by id
+    pub neurons: Vec<NeuronState>,
+}
+
+impl NetworkSnapshot {
+    pub fn get_neuron_state(&self, id: &str) -> Option<&NeuronState> {
+        self.neurons.iter().find(|state| state.id == id)
+    }
+
+    /// Write the snapshot into file in json format.
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer(File::create(path)?, self)?;
+        Ok(())
+    }
+
+    /// Read the snapshot from json file.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_reader(File::open(path)?)?)
+    }
+
+    /// Changes of the snapshot against the base one. When the config differs
+    /// (topology or weights) states of all neurons are included, because the
+    /// config installation resets them.
+    pub fn delta(&self, base: &NetworkSnapshot) -> SnapshotDelta {
+        let config = (self.config != base.config).then(|| self.config.clone());
+        let neurons = self
+            .neurons
+            .iter()
+            .filter(|state| config.is_some() || base.get_neuron_state(&state.id) != Some(state))
+            .cloned()
+            .collect();
+        SnapshotDelta {
+            timestamp: self.timestamp,
+            config,
+            neurons,
+        }
+    }
+}
+
+/// Changes of the network between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotDelta {
+    pub timestamp: DateTime<Utc>,
+
+    /// The new config when topology or parameters have changed
+    pub config: Option<NetworkCfg>,
+
+    /// Changed states of neurons ordered by id
+    pub neurons: Vec<NeuronState>,
+}
+
+impl SnapshotDelta {
+    pub fn is_empty(&self) -> bool {
+        self.config.is_none() && self.neurons.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rnn::common::network_cfg::LabelMap;
+
+    #[test]
+    fn snapshot_should_survive_save_and_load() {
+        let snapshot = NetworkSnapshot {
+            timestamp: Utc::now(),
+            config: NetworkCfg {
+                inputs: 0,
+                outputs: 0,
+                neurons: vec![],
+                links: vec![],
+                lineage: None,
+                composites: vec![],
+                labels: LabelMap::default(),
+                seed: Some(7),
+            },
+            neurons: vec![NeuronState {
+                id: String::from("M0Z0"),
+                accumulator: -3,
+                reset_count: 2,
+                input_hits: BTreeSet::from([1]),
+                synapse_capacities: BTreeMap::from([(0, 4), (1, 0)]),
+                paused_signals: Some(vec![(0, 5)]),
+                ..NeuronState::default()
+            }],
+        };
+        let path = std::env::temp_dir().join(format!("runen-snapshot-{}.json", std::process::id()));
+
+        assert!(snapshot.save(&path).is_ok());
+        let loaded = NetworkSnapshot::load(&path).unwrap();
+        let _remove_result = std::fs::remove_file(&path);
+
+        assert_eq!(loaded, snapshot);
+        assert_eq!(loaded.get_neuron_state("M0Z0").unwrap().accumulator, -3);
+        assert!(loaded.get_neuron_state("M0Z1").is_none());
+    }
+
+    #[test]
+    fn delta_should_contain_only_changed_states() {
+        let state = |id: &str, accumulator| NeuronState {
+            id: id.to_string(),
+            accumulator,
+            ..NeuronState::default()
+        };
+        let base = NetworkSnapshot {
+            timestamp: Utc::now(),
+            config: NetworkCfg {
+                inputs: 1,
+                outputs: 0,
+                neurons: vec![],
+                links: vec![],
+                lineage: None,
+                composites: vec![],
+                labels: LabelMap::default(),
+                seed: None,
+            },
+            neurons: vec![state("M0Z0", 0), state("M0Z1", 0)],
+        };
+        let mut next = base.clone();
+        next.neurons[1].accumulator = 2;
+
+        assert!(base.delta(&base).is_empty());
+        let delta = next.delta(&base);
+        assert!(delta.config.is_none());
+        assert_eq!(delta.neurons, vec![state("M0Z1", 2)]);
+
+        next.config.inputs = 2;
+        let delta = next.delta(&base);
+        assert_eq!(delta.config, Some(next.config.clone()));
+        assert_eq!(delta.neurons.len(), 2);
+    }
+}
@@ -1,9 +1,13 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
+use super::network_cfg::NeuronKind;
 use super::signal::{Signal, Weight};
 
 /// Current neuron state.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NeuronInfo {
     /// The status of the neuron at a time.
     pub timestamp: DateTime<Utc>,
@@ -34,9 +38,18 @@ pub struct NeuronInfo {
 
     /// The sum of dendrites weight
     pub total_weight: Weight,
+
+    /// Signals lost by lagging synapses: synapse port -> number of signals.
+    /// Only synapses which lost signals since the connection are present.
+    pub dropped_signals: BTreeMap<usize, u64>,
+
+    /// The number of signals ignored during refractory periods.
+    pub refractory_count: u64,
+
+    pub kind: NeuronKind,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PortInfo {
     /// The port status at a time.
     pub timestamp: DateTime<Utc>,
@@ -51,9 +64,174 @@ pub struct PortInfo {
     pub recent_signal: Signal,
 }
 
-#[derive(Debug, Clone)]
+/// The neuron has been removed from the network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovalInfo {
+    pub timestamp: DateTime<Utc>,
+
+    /// The removed neuron id
+    pub id: String,
+
+    /// The number of links and ports disconnected from the neuron
+    pub disconnected_count: usize,
+}
+
+/// The synapse lagged behind the party's channel and lost signals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LagInfo {
+    pub timestamp: DateTime<Utc>,
+
+    /// The neuron id
+    pub id: String,
+
+    /// The lagging synapse port
+    pub port: usize,
+
+    /// The number of signals lost by this lag
+    pub dropped_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Status {
     Neuron(NeuronInfo),
 
     Port(PortInfo),
+
+    Removal(RemovalInfo),
+
+    PortLagged(LagInfo),
+}
+
+/// The set of status kinds (see MonitoringFilter of the network).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusMask(u8);
+
+impl StatusMask {
+    pub const NONE: StatusMask = StatusMask(0);
+    pub const NEURON: StatusMask = StatusMask(1);
+    pub const PORT: StatusMask = StatusMask(1 << 1);
+    pub const REMOVAL: StatusMask = StatusMask(1 << 2);
+    pub const PORT_LAGGED: StatusMask = StatusMask(1 << 3);
+    pub const ALL: StatusMask = StatusMask(0b1111);
+
+    pub fn of(status: &Status) -> StatusMask {
+        match status {
+            Status::Neuron(_) => StatusMask::NEURON,
+            Status::Port(_) => StatusMask::PORT,
+            Status::Removal(_) => StatusMask::REMOVAL,
+            Status::PortLagged(_) => StatusMask::PORT_LAGGED,
+        }
+    }
+
+    pub fn contains(&self, other: StatusMask) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl Default for StatusMask {
+    fn default() -> Self {
+        StatusMask::ALL
+    }
+}
+
+impl std::ops::BitOr for StatusMask {
+    type Output = StatusMask;
+
+    fn bitor(self, rhs: StatusMask) -> StatusMask {
+        StatusMask(self.0 | rhs.0)
+    }
+}
+
+/// The format of exported monitoring records (see Network::export_monitoring()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    /// One JSON serialized Status per line
+    JsonLines,
+
+    /// Flat rows with the STATUS_CSV_HEADER columns. Columns the status does not have are empty.
+    Csv,
+}
+
+pub const STATUS_CSV_HEADER: &str =
+    "status,timestamp,id,port,hit_count,reset_count,accumulator,total_weight,signal,count";
+
+impl Status {
+    pub fn get_timestamp(&self) -> DateTime<Utc> {
+        match self {
+            Status::Neuron(info) => info.timestamp,
+            Status::Port(info) => info.timestamp,
+            Status::Removal(info) => info.timestamp,
+            Status::PortLagged(info) => info.timestamp,
+        }
+    }
+
+    /// The row of STATUS_CSV_HEADER columns without line break.
+    pub fn to_csv_row(&self) -> String {
+        let timestamp = self.get_timestamp().to_rfc3339();
+        match self {
+            Status::Neuron(info) => format!(
+                "Neuron,{timestamp},{},,{},{},{},{},,",
+                info.id, info.hit_count, info.reset_count, info.accumulator, info.total_weight
+            ),
+            Status::Port(info) => format!(
+                "Port,{timestamp},{},,{},,,,{},",
+                info.id, info.hit_count, info.recent_signal
+            ),
+            Status::Removal(info) => format!(
+                "Removal,{timestamp},{},,,,,,,{}",
+                info.id, info.disconnected_count
+            ),
+            Status::PortLagged(info) => format!(
+                "PortLagged,{timestamp},{},{},,,,,,{}",
+                info.id, info.port, info.dropped_count
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_row_should_have_every_header_column() {
+        let columns = STATUS_CSV_HEADER.split(',').count();
+        let status = Status::PortLagged(LagInfo {
+            timestamp: DateTime::<Utc>::UNIX_EPOCH,
+            id: String::from("M0Z1"),
+            port: 2,
+            dropped_count: 3,
+        });
+
+        assert_eq!(
+            status.to_csv_row(),
+            "PortLagged,1970-01-01T00:00:00+00:00,M0Z1,2,,,,,,3"
+        );
+        assert_eq!(status.to_csv_row().split(',').count(), columns);
+    }
+
+    #[test]
+    fn status_mask_should_contain_combined_kinds() {
+        let mask = StatusMask::NEURON | StatusMask::PORT_LAGGED;
+
+        assert!(mask.contains(StatusMask::NEURON));
+        assert!(!mask.contains(StatusMask::PORT));
+        assert!(StatusMask::default().contains(mask));
+        assert!(!StatusMask::NONE.contains(StatusMask::REMOVAL));
+    }
+
+    #[test]
+    fn status_should_survive_json_round_trip() {
+        let status = Status::Port(PortInfo {
+            timestamp: DateTime::<Utc>::UNIX_EPOCH,
+            id: String::from("M0I0"),
+            hit_count: 4,
+            recent_signal: 7,
+        });
+
+        let status_json = serde_json::to_string(&status).unwrap();
+        let restored: Status = serde_json::from_str(&status_json).unwrap();
+
+        assert_eq!(restored.to_csv_row(), status.to_csv_row());
+    }
 }
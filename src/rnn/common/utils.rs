@@ -19,7 +19,7 @@ pub fn gen_id_by_spec_type(
         SpecificationType::Network => ('M', r"^$"),
     };
 
-    if !is_match_to_regexp(container_id, &regex_pattern) {
+    if !is_match_to_regexp(container_id, regex_pattern) {
         return Err(Box::new(RnnError::NotSupportedArgValue));
     }
     Ok(format!(
@@ -33,7 +33,7 @@ pub fn get_component_id_fraction(
     id: &str,
     spec_type: &SpecificationType,
 ) -> Result<usize, Box<dyn Error>> {
-    if id.len() == 0 {
+    if id.is_empty() {
         return Err(Box::new(RnnError::ExpectedDataNotPresent(String::from(
             "Empty string present",
         ))));
@@ -49,7 +49,7 @@ pub fn get_component_id_fraction(
         .captures(id)
         .ok_or_else(|| Box::new(RnnError::NotSupportedArgValue))?;
 
-    if &captures.len() < &2 {
+    if captures.len() < 2 {
         return Err(Box::new(RnnError::PatternNotFound));
     }
     let result = captures[1].parse::<usize>()?;
@@ -70,6 +70,14 @@ pub fn extract_neuron_id_from(id: &str) -> Option<String> {
         })
 }
 
+/// Extract network's id part from component id. E.g. from M3Z0C12 -> M3
+pub fn extract_network_id_from(id: &str) -> Option<String> {
+    Regex::new(r"^(M\d+)Z\d+.*$")
+        .unwrap()
+        .captures(id)
+        .map(|caps| caps[1].to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,227 @@
+//! Reproducible experiments: the manifest describes the dataset, the reservoir
+//! and trainer parameters, the runner executes it and writes the record of the run
+//! into its own directory: the manifest with resolved seeds (manifest.json),
+//! per-epoch errors (record.json) and the trained model (model.runen).
+//!
+//! ```ignore
+//! let manifest = Manifest::load(Path::new("experiments/xor.json"))?;
+//! let record = Runner::new(Path::new("results")).run(&manifest).await?;
+//! println!("{:?} -> {}", record.epoch_errors, record.run_dir.display());
+//! ```
+
+use std::error::Error;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::network_cfg::NetworkCfg;
+use crate::rnn::common::rng::RngProvider;
+use crate::rnn::common::signal::Weight;
+use crate::rnn::layouts::network::Network;
+use crate::rnn::layouts::reservoir::Reservoir;
+use crate::rnn::training::model::{Model, TrainingMetadata, MODEL_EXTENSION};
+use crate::rnn::training::reservoir_trainer::{ReservoirSample, ReservoirTrainer};
+
+/// Parameters of ReservoirTrainer and the number of epochs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrainerParams {
+    pub learning_rate: Weight,
+
+    /// The time of activity collection after the sample is sent
+    pub window: Duration,
+
+    pub epochs: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The name of the experiment, the prefix of its run directories
+    pub name: String,
+
+    /// The json file with training samples (array of ReservoirSample)
+    pub dataset: PathBuf,
+
+    /// The reservoir whose read-out layer is trained. Its topology is generated
+    /// from `params.seed`.
+    pub reservoir: Reservoir,
+
+    /// The initial config of the same reservoir layout (e.g. the previously trained
+    /// one) in json format. The config is generated from the reservoir when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<PathBuf>,
+
+    pub trainer: TrainerParams,
+
+    /// The root seed of the network (see Network::set_seed()).
+    /// It is drawn from entropy and written into the record when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+impl Manifest {
+    /// Read the manifest from json file.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_reader(File::open(path)?)?)
+    }
+
+    /// Write the manifest into file in json format.
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer_pretty(File::create(path)?, self)?;
+        Ok(())
+    }
+}
+
+/// The result of the run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentRecord {
+    /// The executed manifest with resolved seeds. Running it again reproduces the run.
+    pub manifest: Manifest,
+
+    pub started_at: DateTime<Utc>,
+
+    pub finished_at: DateTime<Utc>,
+
+    /// The sum of absolute output errors per epoch
+    pub epoch_errors: Vec<u64>,
+
+    /// The directory with files of the run
+    pub run_dir: PathBuf,
+}
+
+pub struct Runner {
+    results_dir: PathBuf,
+}
+
+impl Runner {
+    pub fn new(results_dir: &Path) -> Self {
+        Runner {
+            results_dir: results_dir.to_path_buf(),
+        }
+    }
+
+    /// Build the network, train it for the number of epochs and write the record
+    /// into the new directory `<results_dir>/<name>-<start time>`.
+    pub async fn run(&self, manifest: &Manifest) -> Result<ExperimentRecord, Box<dyn Error>> {
+        let started_at = Utc::now();
+        let mut manifest = manifest.clone();
+        let seed = *manifest
+            .seed
+            .get_or_insert_with(|| RngProvider::from_entropy().get_seed());
+
+        let samples: Vec<ReservoirSample> =
+            serde_json::from_reader(File::open(&manifest.dataset)?)?;
+        let config: NetworkCfg = match &manifest.config {
+            Some(path) => serde_json::from_reader(File::open(path)?)?,
+            None => manifest.reservoir.build()?,
+        };
+        let network = Arc::new(Network::new()?);
+        network.restore_config(network.clone(), &config).await?;
+        network.set_seed(Some(seed)).await;
+
+        let trainer = ReservoirTrainer::new(
+            manifest.reservoir.clone(),
+            manifest.trainer.learning_rate,
+            manifest.trainer.window,
+        );
+        let mut epoch_errors = vec![];
+        for _ in 0..manifest.trainer.epochs {
+            epoch_errors.push(trainer.train_epoch(&network, &samples).await?);
+        }
+
+        let run_dir = self.results_dir.join(format!(
+            "{}-{}",
+            manifest.name,
+            started_at.format("%Y%m%dT%H%M%S%.3f")
+        ));
+        fs::create_dir_all(&run_dir)?;
+
+        let dataset = manifest
+            .dataset
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().to_string());
+        let mut metadata = TrainingMetadata::new(&dataset, epoch_errors.len() as u64);
+        if let Some(error) = epoch_errors.last() {
+            metadata = metadata.with_metric("epoch_error", *error as f64);
+        }
+        Model::from_network(&network, metadata, false)
+            .await
+            .save(&run_dir.join(format!("model.{MODEL_EXTENSION}")))?;
+
+        manifest.save(&run_dir.join("manifest.json"))?;
+        let record = ExperimentRecord {
+            manifest,
+            started_at,
+            finished_at: Utc::now(),
+            epoch_errors,
+            run_dir,
+        };
+        serde_json::to_writer_pretty(File::create(record.run_dir.join("record.json"))?, &record)?;
+
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::layouts::topology::TopologyParams;
+
+    fn manifest_fixture(dir: &Path) -> Manifest {
+        let dataset = dir.join("samples.json");
+        let samples = vec![ReservoirSample {
+            inputs: vec![5],
+            targets: vec![200],
+        }];
+        serde_json::to_writer(File::create(&dataset).unwrap(), &samples).unwrap();
+
+        Manifest {
+            name: String::from("readout"),
+            dataset,
+            reservoir: Reservoir {
+                params: TopologyParams {
+                    neurons: 3,
+                    inputs: 1,
+                    outputs: 1,
+                    bias: 0,
+                    input_cfg: InputCfg::new(10, 10, 1).unwrap(),
+                    seed: 1,
+                },
+                connectivity: 0.0,
+                readout_cfg: InputCfg::new(10, 10, 0).unwrap(),
+            },
+            config: None,
+            trainer: TrainerParams {
+                learning_rate: 2,
+                window: Duration::from_millis(30),
+                epochs: 2,
+            },
+            seed: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn run_should_write_reproducible_record() {
+        let dir = std::env::temp_dir().join(format!("runen-experiment-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let manifest = manifest_fixture(&dir);
+
+        let record = Runner::new(&dir).run(&manifest).await.unwrap();
+
+        assert_eq!(record.epoch_errors.len(), 2);
+        assert!(record.manifest.seed.is_some());
+        assert_eq!(
+            Manifest::load(&record.run_dir.join("manifest.json")).unwrap(),
+            record.manifest
+        );
+        let model = Model::load(&record.run_dir.join("model.runen")).unwrap();
+        assert_eq!(model.metadata.dataset, "samples");
+        assert_eq!(model.config.seed, record.manifest.seed);
+        assert!(record.run_dir.join("record.json").exists());
+        let _remove_result = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,353 @@
+//! REST API over the registry of named networks, so networks can be operated
+//! from scripts and CI jobs written in other languages.
+//!
+//! | Method | Path                                   | Body          | Reply           |
+//! |--------|----------------------------------------|---------------|-----------------|
+//! | GET    | /capabilities                          |               | Capabilities    |
+//! | GET    | /networks                              |               | names           |
+//! | PUT    | /networks/:name                        | NetworkCfg    | NetworkInfo     |
+//! | GET    | /networks/:name                        |               | NetworkInfo     |
+//! | DELETE | /networks/:name                        |               |                 |
+//! | GET    | /networks/:name/config                 |               | NetworkCfg      |
+//! | POST   | /networks/:name/start                  |               | NetworkInfo     |
+//! | POST   | /networks/:name/stop                   |               | NetworkInfo     |
+//! | POST   | /networks/:name/inputs/:port           | InputRequest  | InputReply      |
+//! | GET    | /networks/:name/neurons/:id/status     |               | Status          |
+//!
+//! Errors are replied with the status code and the error text: 404 for unknown
+//! networks, neurons and ports, 409 for existing networks, 400 otherwise.
+//!
+//! ```ignore
+//! let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await?;
+//! librunen::rnn::http::serve(listener, Arc::new(NetworkRegistry::new())).await?;
+//! ```
+
+use std::error::Error;
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+
+use crate::rnn::common::capabilities::{capabilities, Capabilities};
+use crate::rnn::common::network_cfg::NetworkCfg;
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::signal::Signal;
+use crate::rnn::common::status::Status;
+use crate::rnn::layouts::network::Network;
+use crate::rnn::layouts::network_registry::{NetworkRegistry, NetworkState};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkInfo {
+    pub name: String,
+    pub id: String,
+    pub state: NetworkState,
+
+    /// The number of neurons
+    pub neurons: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputRequest {
+    pub signal: Signal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputReply {
+    /// The number of synapses which received the signal
+    pub receivers: usize,
+}
+
+/// The error replied with the status code and the error text.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub message: String,
+}
+
+impl From<Box<dyn Error>> for ApiError {
+    fn from(err: Box<dyn Error>) -> Self {
+        let status = match err.downcast_ref::<RnnError>() {
+            Some(
+                RnnError::NetworkNotFound(_)
+                | RnnError::NeuronNotFound(_)
+                | RnnError::PortNotFound(_),
+            ) => StatusCode::NOT_FOUND,
+            Some(RnnError::NetworkAlreadyExists(_)) => StatusCode::CONFLICT,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        ApiError {
+            status,
+            message: err.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, self.message).into_response()
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+/// Routes of the API over the registry.
+pub fn router(registry: Arc<NetworkRegistry>) -> Router {
+    Router::new()
+        .route("/capabilities", get(get_capabilities))
+        .route("/networks", get(get_networks))
+        .route(
+            "/networks/:name",
+            put(create_network).get(get_network).delete(remove_network),
+        )
+        .route("/networks/:name/config", get(get_config))
+        .route("/networks/:name/start", post(start_network))
+        .route("/networks/:name/stop", post(stop_network))
+        .route("/networks/:name/inputs/:port", post(input))
+        .route("/networks/:name/neurons/:id/status", get(get_neuron_status))
+        .with_state(registry)
+}
+
+/// Serve the API on the listener until the server fails.
+pub async fn serve(
+    listener: TcpListener,
+    registry: Arc<NetworkRegistry>,
+) -> Result<(), Box<dyn Error>> {
+    axum::serve(listener, router(registry)).await?;
+    Ok(())
+}
+
+async fn get_capabilities() -> Json<Capabilities> {
+    Json(capabilities())
+}
+
+async fn get_networks(State(registry): State<Arc<NetworkRegistry>>) -> Json<Vec<String>> {
+    Json(registry.get_names().await)
+}
+
+async fn create_network(
+    State(registry): State<Arc<NetworkRegistry>>,
+    Path(name): Path<String>,
+    Json(config): Json<NetworkCfg>,
+) -> Result<(StatusCode, Json<NetworkInfo>), ApiError> {
+    config.validate()?;
+    registry.create(&name, &config).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(network_info(&registry, &name).await?),
+    ))
+}
+
+async fn get_network(
+    State(registry): State<Arc<NetworkRegistry>>,
+    Path(name): Path<String>,
+) -> ApiResult<NetworkInfo> {
+    Ok(Json(network_info(&registry, &name).await?))
+}
+
+async fn remove_network(
+    State(registry): State<Arc<NetworkRegistry>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    registry.remove(&name).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_config(
+    State(registry): State<Arc<NetworkRegistry>>,
+    Path(name): Path<String>,
+) -> ApiResult<NetworkCfg> {
+    Ok(Json(find(&registry, &name).await?.get_config().await))
+}
+
+/// Restart the stopped network from its kept config and the archived one from its
+/// archive. Running network is not touched.
+async fn start_network(
+    State(registry): State<Arc<NetworkRegistry>>,
+    Path(name): Path<String>,
+) -> ApiResult<NetworkInfo> {
+    if matches!(
+        registry.get_state(&name).await,
+        Some(NetworkState::Stopped | NetworkState::Archived)
+    ) {
+        registry.restart(&name).await?;
+    }
+    Ok(Json(network_info(&registry, &name).await?))
+}
+
+async fn stop_network(
+    State(registry): State<Arc<NetworkRegistry>>,
+    Path(name): Path<String>,
+) -> ApiResult<NetworkInfo> {
+    registry.stop(&name).await?;
+    Ok(Json(network_info(&registry, &name).await?))
+}
+
+async fn input(
+    State(registry): State<Arc<NetworkRegistry>>,
+    Path((name, port)): Path<(String, usize)>,
+    Json(request): Json<InputRequest>,
+) -> ApiResult<InputReply> {
+    let receivers = find(&registry, &name)
+        .await?
+        .input(request.signal, port)
+        .await?;
+    Ok(Json(InputReply { receivers }))
+}
+
+async fn get_neuron_status(
+    State(registry): State<Arc<NetworkRegistry>>,
+    Path((name, id)): Path<(String, String)>,
+) -> ApiResult<Status> {
+    Ok(Json(
+        find(&registry, &name)
+            .await?
+            .get_current_neuron_status(&id)
+            .await?,
+    ))
+}
+
+async fn find(registry: &NetworkRegistry, name: &str) -> Result<Arc<Network>, ApiError> {
+    registry.get(name).await.ok_or_else(|| {
+        ApiError::from(Box::new(RnnError::NetworkNotFound(name.to_string())) as Box<dyn Error>)
+    })
+}
+
+async fn network_info(registry: &NetworkRegistry, name: &str) -> Result<NetworkInfo, ApiError> {
+    let network = find(registry, name).await?;
+    Ok(NetworkInfo {
+        name: name.to_string(),
+        id: network.get_id(),
+        state: registry
+            .get_state(name)
+            .await
+            .unwrap_or(NetworkState::Stopped),
+        neurons: network.len().await,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::{LabelMap, LinkCfg, NeuronCfg, NeuronKind};
+
+    /// I0 -> Z0 -> O0
+    fn config_fixture() -> NetworkCfg {
+        NetworkCfg {
+            inputs: 1,
+            outputs: 1,
+            neurons: vec![NeuronCfg {
+                id: String::from("M0Z0"),
+                bias: 1,
+                input_configs: vec![InputCfg::new(3, 3, 1).unwrap()],
+                lineage: None,
+                kind: NeuronKind::Excitatory,
+            }],
+            links: vec![
+                LinkCfg::Input {
+                    input_port: 0,
+                    dst_id: String::from("M0Z0"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Output {
+                    src_id: String::from("M0Z0"),
+                    output_port: 0,
+                },
+            ],
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn network_should_be_created_stimulated_and_stopped() {
+        let registry = Arc::new(NetworkRegistry::new());
+        let name = String::from("relay");
+
+        let (status, Json(info)) = create_network(
+            State(registry.clone()),
+            Path(name.clone()),
+            Json(config_fixture()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(info.neurons, 1);
+
+        let Json(reply) = input(
+            State(registry.clone()),
+            Path((name.clone(), 0)),
+            Json(InputRequest { signal: 2 }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(reply.receivers, 1);
+
+        let neuron_id = format!("{}Z0", info.id);
+        let neuron_status =
+            get_neuron_status(State(registry.clone()), Path((name.clone(), neuron_id))).await;
+        assert!(matches!(neuron_status, Ok(Json(Status::Neuron(_)))));
+
+        let Json(info) = stop_network(State(registry.clone()), Path(name.clone()))
+            .await
+            .unwrap();
+        assert_eq!(info.state, NetworkState::Stopped);
+        assert_eq!(info.neurons, 0);
+        let Json(info) = start_network(State(registry.clone()), Path(name.clone()))
+            .await
+            .unwrap();
+        assert_eq!(info.state, NetworkState::Running);
+        assert_eq!(info.neurons, 1);
+    }
+
+    #[tokio::test]
+    async fn archived_network_should_be_started_from_archive() {
+        let registry = Arc::new(NetworkRegistry::new());
+        let name = String::from("relay");
+        assert!(create_network(
+            State(registry.clone()),
+            Path(name.clone()),
+            Json(config_fixture())
+        )
+        .await
+        .is_ok());
+        let path = std::env::temp_dir().join(format!("runen-http-{}.json", std::process::id()));
+        assert!(registry.archive(&name, &path).await.is_ok());
+
+        let started = start_network(State(registry.clone()), Path(name.clone())).await;
+        let _remove_result = std::fs::remove_file(&path);
+
+        let Json(info) = started.unwrap();
+        assert_eq!(info.state, NetworkState::Running);
+        assert_eq!(info.neurons, 1);
+    }
+
+    #[tokio::test]
+    async fn errors_should_be_mapped_to_status_codes() {
+        let registry = Arc::new(NetworkRegistry::new());
+        let name = String::from("relay");
+
+        let missing = get_config(State(registry.clone()), Path(name.clone())).await;
+        assert_eq!(missing.unwrap_err().status, StatusCode::NOT_FOUND);
+
+        assert!(create_network(
+            State(registry.clone()),
+            Path(name.clone()),
+            Json(config_fixture())
+        )
+        .await
+        .is_ok());
+        let duplicate =
+            create_network(State(registry.clone()), Path(name), Json(config_fixture())).await;
+        assert_eq!(duplicate.unwrap_err().status, StatusCode::CONFLICT);
+
+        let _router = router(registry);
+    }
+}
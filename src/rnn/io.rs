@@ -0,0 +1,6 @@
+pub mod audio;
+pub mod decoders;
+pub mod dvs;
+pub mod encoders;
+pub mod generators;
+pub mod ingest;
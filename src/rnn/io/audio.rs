@@ -0,0 +1,254 @@
+//! Audio encoding: PCM samples are cut into frames, the magnitude of every band
+//! of the filter bank is measured per frame (Goertzel algorithm) and bands above
+//! the threshold spike into their input ports at the frame offset.
+//! WAV files are read with the wav feature.
+
+use std::error::Error;
+use std::f64::consts::PI;
+#[cfg(feature = "wav")]
+use std::path::Path;
+use std::time::Duration;
+
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::signal::Signal;
+
+use super::encoders::{Encoder, TimedSignal};
+
+/// Mono PCM samples in [-1, 1].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pcm {
+    pub sample_rate: u32,
+    pub samples: Vec<f64>,
+}
+
+impl Pcm {
+    /// Read the WAV file mixing its channels down to mono.
+    #[cfg(feature = "wav")]
+    pub fn read_wav(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut reader = hound::WavReader::open(path)?;
+        let spec = reader.spec();
+        let samples: Vec<f64> = match spec.sample_format {
+            hound::SampleFormat::Float => reader
+                .samples::<f32>()
+                .map(|sample| sample.map(f64::from))
+                .collect::<Result<_, _>>()?,
+            hound::SampleFormat::Int => {
+                let full_scale = (1_i64 << (spec.bits_per_sample - 1)) as f64;
+                reader
+                    .samples::<i32>()
+                    .map(|sample| sample.map(|sample| sample as f64 / full_scale))
+                    .collect::<Result<_, _>>()?
+            }
+        };
+        let samples = samples
+            .chunks(spec.channels.max(1) as usize)
+            .map(|frame| frame.iter().sum::<f64>() / frame.len() as f64)
+            .collect();
+        Ok(Pcm {
+            sample_rate: spec.sample_rate,
+            samples,
+        })
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.samples.len() as f64 / self.sample_rate.max(1) as f64)
+    }
+}
+
+/// The filter bank of bands with center frequencies going into their input ports.
+/// As the Encoder it takes PCM samples at sample_rate as values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterBankEncoder {
+    sample_rate: u32,
+    bands: Vec<(f64, usize)>,
+    frame: Duration,
+    threshold: f64,
+    signal: Signal,
+}
+
+impl FilterBankEncoder {
+    /// `bands` are pairs (center frequency in Hz, input port). The band spikes in
+    /// the frame when its magnitude (1 for the full scale sine) reaches the threshold.
+    pub fn new(
+        sample_rate: u32,
+        bands: Vec<(f64, usize)>,
+        frame: Duration,
+        threshold: f64,
+        signal: Signal,
+    ) -> Result<Self, Box<dyn Error>> {
+        let nyquist = sample_rate as f64 / 2.0;
+        let frame_samples = frame.as_secs_f64() * sample_rate as f64;
+        if frame_samples < 1.0
+            || bands
+                .iter()
+                .any(|(frequency, _)| !(*frequency > 0.0 && *frequency < nyquist))
+        {
+            return Err(Box::new(RnnError::NotSupportedArgValue));
+        }
+        Ok(FilterBankEncoder {
+            sample_rate,
+            bands,
+            frame,
+            threshold,
+            signal,
+        })
+    }
+
+    /// Bands with 1/3 octave spacing from `lowest` Hz into ports from 0 by frequency.
+    pub fn third_octaves(
+        sample_rate: u32,
+        lowest: f64,
+        count: usize,
+        frame: Duration,
+        threshold: f64,
+        signal: Signal,
+    ) -> Result<Self, Box<dyn Error>> {
+        let bands = (0..count)
+            .map(|band| (lowest * 2_f64.powf(band as f64 / 3.0), band))
+            .collect();
+        Self::new(sample_rate, bands, frame, threshold, signal)
+    }
+
+    pub fn get_bands(&self) -> &[(f64, usize)] {
+        &self.bands
+    }
+
+    fn frame_samples(&self) -> usize {
+        (self.frame.as_secs_f64() * self.sample_rate as f64).round() as usize
+    }
+
+    /// Magnitudes of bands in the frame of samples in order of bands.
+    pub fn magnitudes(&self, frame: &[f64]) -> Vec<f64> {
+        self.bands
+            .iter()
+            .map(|(frequency, _)| goertzel(frame, *frequency / self.sample_rate as f64))
+            .collect()
+    }
+
+    pub fn encode_pcm(&self, pcm: &Pcm) -> Result<Vec<TimedSignal>, Box<dyn Error>> {
+        if pcm.sample_rate != self.sample_rate {
+            return Err(Box::new(RnnError::NotSupportedArgValue));
+        }
+        Ok(self.encode(&pcm.samples))
+    }
+}
+
+impl Encoder for FilterBankEncoder {
+    /// Values are PCM samples, the incomplete last frame is dropped.
+    fn encode(&self, values: &[f64]) -> Vec<TimedSignal> {
+        let mut timed_signals = vec![];
+        for (idx, frame) in values.chunks_exact(self.frame_samples()).enumerate() {
+            for (magnitude, (_, port)) in self.magnitudes(frame).into_iter().zip(self.bands.iter())
+            {
+                if magnitude >= self.threshold {
+                    timed_signals.push(TimedSignal {
+                        offset: self.frame * idx as u32,
+                        port: *port,
+                        signal: self.signal,
+                    });
+                }
+            }
+        }
+        timed_signals.sort_by_key(|timed_signal| (timed_signal.offset, timed_signal.port));
+        timed_signals
+    }
+}
+
+/// The amplitude of the frequency (in cycles per sample) in the frame.
+fn goertzel(frame: &[f64], frequency: f64) -> f64 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    let coeff = 2.0 * (2.0 * PI * frequency).cos();
+    let (mut s1, mut s2) = (0.0, 0.0);
+    for sample in frame {
+        let s = sample + coeff * s1 - s2;
+        s2 = s1;
+        s1 = s;
+    }
+    let power = (s1 * s1 + s2 * s2 - coeff * s1 * s2).max(0.0);
+    2.0 * power.sqrt() / frame.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine_fixture(frequency: f64, sample_rate: u32, duration: Duration) -> Pcm {
+        let count = (duration.as_secs_f64() * sample_rate as f64) as usize;
+        Pcm {
+            sample_rate,
+            samples: (0..count)
+                .map(|idx| (2.0 * PI * frequency * idx as f64 / sample_rate as f64).sin())
+                .collect(),
+        }
+    }
+
+    fn encoder_fixture() -> FilterBankEncoder {
+        FilterBankEncoder::new(
+            8000,
+            vec![(220.0, 3), (440.0, 4), (880.0, 5)],
+            Duration::from_millis(20),
+            0.3,
+            1,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn tone_should_spike_its_band_every_frame() {
+        let encoder = encoder_fixture();
+        let pcm = sine_fixture(440.0, 8000, Duration::from_millis(100));
+
+        let timed_signals = encoder.encode_pcm(&pcm).unwrap();
+
+        assert_eq!(timed_signals.len(), 5);
+        assert!(timed_signals
+            .iter()
+            .all(|timed_signal| timed_signal.port == 4));
+        assert_eq!(timed_signals[4].offset, Duration::from_millis(80));
+        assert!(encoder.encode(&[0.0; 800]).is_empty());
+    }
+
+    #[test]
+    fn encoder_should_reject_unsupported_parameters() {
+        assert!(
+            FilterBankEncoder::new(8000, vec![(4000.0, 0)], Duration::from_millis(20), 0.3, 1)
+                .is_err()
+        );
+        assert!(FilterBankEncoder::new(8000, vec![(440.0, 0)], Duration::ZERO, 0.3, 1).is_err());
+        assert!(encoder_fixture()
+            .encode_pcm(&sine_fixture(440.0, 16000, Duration::from_millis(20)))
+            .is_err());
+
+        let encoder =
+            FilterBankEncoder::third_octaves(16000, 100.0, 4, Duration::from_millis(20), 0.3, 1)
+                .unwrap();
+        assert_eq!(encoder.get_bands().len(), 4);
+        assert!((encoder.get_bands()[3].0 - 200.0).abs() < 1e-9);
+    }
+
+    #[cfg(feature = "wav")]
+    #[test]
+    fn wav_should_be_read_into_mono_samples() {
+        let path = std::env::temp_dir().join(format!("runen-audio-{}.wav", std::process::id()));
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 8000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for (left, right) in [(16384, 0), (-16384, -16384)] {
+            writer.write_sample(left as i16).unwrap();
+            writer.write_sample(right as i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let pcm = Pcm::read_wav(&path).unwrap();
+
+        assert_eq!(pcm.sample_rate, 8000);
+        assert_eq!(pcm.samples, vec![0.25, -0.5]);
+        let _remove_result = std::fs::remove_file(&path);
+    }
+}
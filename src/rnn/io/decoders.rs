@@ -0,0 +1,139 @@
+//! Decoders of asynchronous spikes of output ports. Spikes are counted per port
+//! during the time window, then counts are turned into one signal per port and
+//! reconstructed into the value (see training::encoding::Reconstructible).
+
+use std::error::Error;
+use std::time::Duration;
+
+use tokio::sync::broadcast::error::TryRecvError;
+use tokio::sync::broadcast::Receiver;
+use tokio::time;
+
+use crate::rnn::common::signal::Signal;
+use crate::rnn::layouts::network::Network;
+use crate::rnn::training::encoding::Reconstructible;
+
+pub trait Decoder {
+    /// The time of spikes collection
+    fn window(&self) -> Duration;
+
+    /// Signals of output ports by numbers of their spikes during the window.
+    fn signals(&self, counts: &[u64]) -> Vec<Signal>;
+}
+
+/// The port spiking at least threshold times during the window gives the signal 1,
+/// otherwise 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateDecoder {
+    pub window: Duration,
+    pub threshold: u64,
+}
+
+impl Decoder for RateDecoder {
+    fn window(&self) -> Duration {
+        self.window
+    }
+
+    fn signals(&self, counts: &[u64]) -> Vec<Signal> {
+        counts
+            .iter()
+            .map(|count| (*count >= self.threshold) as Signal)
+            .collect()
+    }
+}
+
+/// Count spikes (positive signals) of every output port during the window.
+/// Counts are ordered by port index.
+pub async fn count_spikes(network: &Network, window: Duration) -> Result<Vec<u64>, Box<dyn Error>> {
+    let mut receivers = vec![];
+    for port in network.get_output_ports().await {
+        receivers.push(network.subscribe_output(port).await?);
+    }
+
+    time::sleep(window).await;
+
+    Ok(receivers.iter_mut().map(drain_spikes).collect())
+}
+
+/// Collect output spikes during the decoder's window and reconstruct the value.
+/// Returns Ok(None) when decoded signals do not describe any value of the type.
+pub async fn decode<O: Reconstructible>(
+    network: &Network,
+    decoder: &impl Decoder,
+) -> Result<Option<O>, Box<dyn Error>> {
+    let counts = count_spikes(network, decoder.window()).await?;
+    Ok(O::reconstruct(&decoder.signals(&counts)))
+}
+
+/// Lagged signals are counted as spikes, as the receiver is only read after the window.
+fn drain_spikes(receiver: &mut Receiver<Signal>) -> u64 {
+    let mut count = 0;
+    loop {
+        match receiver.try_recv() {
+            Ok(signal) => count += (signal > 0) as u64,
+            Err(TryRecvError::Lagged(lagged)) => count += lagged,
+            Err(_) => return count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+
+    /// Two independent relays: I0 -> Z0 -> O0 and I1 -> Z1 -> O1
+    async fn relays_network_fixture() -> Arc<Network> {
+        let net = Arc::new(new_network_fixture());
+        for port in 0..2 {
+            let id = net
+                .create_neuron(
+                    net.clone(),
+                    0,
+                    vec![InputCfg::new(3, 3, 1).unwrap()],
+                    NeuronKind::Excitatory,
+                )
+                .await
+                .unwrap()
+                .get_id();
+            assert!(net.setup_input(port, &id, 0).await.is_ok());
+            assert!(net.setup_output(port, &id).await.is_ok());
+        }
+        net
+    }
+
+    #[test]
+    fn rate_decoder_should_compare_counts_with_threshold() {
+        let decoder = RateDecoder {
+            window: Duration::from_millis(10),
+            threshold: 2,
+        };
+
+        assert_eq!(decoder.signals(&[0, 2, 5, 1]), vec![0, 1, 1, 0]);
+    }
+
+    #[tokio::test]
+    async fn should_decode_spikes_of_output_ports_during_window() {
+        let net = relays_network_fixture().await;
+        let decoder = RateDecoder {
+            window: Duration::from_millis(30),
+            threshold: 2,
+        };
+
+        let net_cloned = net.clone();
+        let stimulus = tokio::spawn(async move {
+            for signal in [[1, 1], [1, 0]] {
+                time::sleep(Duration::from_millis(5)).await;
+                net_cloned.input_vec(&signal).await.unwrap();
+            }
+        });
+        let decoded: Option<[bool; 2]> = decode(&net, &decoder).await.unwrap();
+        stimulus.await.unwrap();
+
+        assert_eq!(decoded, Some([true, false]));
+    }
+}
@@ -0,0 +1,286 @@
+//! Event camera (DVS) data: streams of (x, y, polarity, timestamp) events are
+//! loaded from AEDAT 2.0 files (DVS128 addressing) or text files and replayed
+//! into input ports keeping their relative timing. Every pixel gets its own
+//! input port, optionally one per polarity.
+
+use std::error::Error;
+use std::io::{BufRead, Read};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::signal::Signal;
+use crate::rnn::layouts::network::Network;
+
+use super::encoders::{send_timed, TimedSignal};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DvsEvent {
+    pub x: u16,
+    pub y: u16,
+
+    /// true for ON (brightness increase) events
+    pub polarity: bool,
+
+    /// Microseconds
+    pub timestamp: u64,
+}
+
+/// Read AEDAT 2.0 events: header lines starting with '#', then big-endian pairs
+/// of 32-bit address and timestamp. Addresses follow DVS128 layout, external
+/// events are skipped. Wrapped timestamps are unwrapped.
+pub fn read_aedat2(mut reader: impl Read) -> Result<Vec<DvsEvent>, Box<dyn Error>> {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+
+    let mut data = bytes.as_slice();
+    while data.first() == Some(&b'#') {
+        let end = data
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .ok_or_else(|| RnnError::MalformedRecord(String::from("unterminated header")))?;
+        data = &data[end + 1..];
+    }
+    if data.len() % 8 != 0 {
+        return Err(Box::new(RnnError::MalformedRecord(format!(
+            "{} trailing bytes",
+            data.len() % 8
+        ))));
+    }
+
+    let mut events = vec![];
+    let (mut wraps, mut previous) = (0_u64, 0_u32);
+    for chunk in data.chunks_exact(8) {
+        let address = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        let timestamp = u32::from_be_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]);
+        if timestamp < previous {
+            wraps += 1;
+        }
+        previous = timestamp;
+        if address & 0x8000 != 0 {
+            continue;
+        }
+        events.push(DvsEvent {
+            x: ((address >> 1) & 0x7f) as u16,
+            y: ((address >> 8) & 0x7f) as u16,
+            polarity: address & 1 == 1,
+            timestamp: (wraps << 32) + timestamp as u64,
+        });
+    }
+    Ok(events)
+}
+
+/// Read events from text lines `timestamp x y polarity` separated by spaces or commas,
+/// timestamps are in seconds, polarity is 1 or 0. Lines starting with '#' are skipped.
+pub fn read_text(reader: impl BufRead) -> Result<Vec<DvsEvent>, Box<dyn Error>> {
+    let mut events = vec![];
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let malformed = || RnnError::MalformedRecord(format!("line {}: {line}", idx + 1));
+        let cells: Vec<&str> = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|cell| !cell.is_empty())
+            .collect();
+        let [timestamp, x, y, polarity] = cells[..] else {
+            return Err(Box::new(malformed()));
+        };
+        let seconds: f64 = timestamp.parse().map_err(|_| malformed())?;
+        events.push(DvsEvent {
+            x: x.parse().map_err(|_| malformed())?,
+            y: y.parse().map_err(|_| malformed())?,
+            polarity: polarity.parse::<u8>().map_err(|_| malformed())? > 0,
+            timestamp: (seconds * 1e6).round() as u64,
+        });
+    }
+    Ok(events)
+}
+
+/// Input ports of the sensor: pixel ports are numbered in row-major order,
+/// with `split_polarity` the pixel has the OFF port followed by the ON port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DvsPorts {
+    pub width: u16,
+    pub height: u16,
+    pub split_polarity: bool,
+}
+
+impl DvsPorts {
+    /// The number of input ports.
+    pub fn len(&self) -> usize {
+        let pixels = self.width as usize * self.height as usize;
+        if self.split_polarity {
+            pixels * 2
+        } else {
+            pixels
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// None for events outside the sensor.
+    pub fn port(&self, event: &DvsEvent) -> Option<usize> {
+        if event.x >= self.width || event.y >= self.height {
+            return None;
+        }
+        let pixel = event.y as usize * self.width as usize + event.x as usize;
+        Some(if self.split_polarity {
+            pixel * 2 + event.polarity as usize
+        } else {
+            pixel
+        })
+    }
+
+    /// Timed signals of events with offsets from the earliest event.
+    /// Events outside the sensor are dropped.
+    pub fn timed_signals(&self, events: &[DvsEvent], signal: Signal) -> Vec<TimedSignal> {
+        let Some(start) = events.iter().map(|event| event.timestamp).min() else {
+            return vec![];
+        };
+        let mut timed_signals: Vec<TimedSignal> = events
+            .iter()
+            .filter_map(|event| {
+                self.port(event).map(|port| TimedSignal {
+                    offset: Duration::from_micros(event.timestamp - start),
+                    port,
+                    signal,
+                })
+            })
+            .collect();
+        timed_signals.sort_by_key(|timed_signal| timed_signal.offset);
+        timed_signals
+    }
+}
+
+/// Replay events into input ports of the network with their relative timing.
+pub async fn replay(
+    network: &Network,
+    events: &[DvsEvent],
+    ports: &DvsPorts,
+    signal: Signal,
+) -> Result<(), Box<dyn Error>> {
+    send_timed(network, &ports.timed_signals(events, signal)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::time::Instant;
+
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+
+    fn aedat_fixture(events: &[(u32, u32)]) -> Vec<u8> {
+        let mut bytes = b"#!AER-DAT2.0\r\n# This is a raw AE data file\r\n".to_vec();
+        for (address, timestamp) in events {
+            bytes.extend_from_slice(&address.to_be_bytes());
+            bytes.extend_from_slice(&timestamp.to_be_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn aedat2_events_should_be_decoded() {
+        // x = 3, y = 5, ON; external event; x = 0, y = 1, OFF after the wrap
+        let bytes = aedat_fixture(&[
+            ((5 << 8) | (3 << 1) | 1, u32::MAX - 10),
+            (0x8000, 5),
+            (1 << 8, 20),
+        ]);
+
+        let events = read_aedat2(bytes.as_slice()).unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                DvsEvent {
+                    x: 3,
+                    y: 5,
+                    polarity: true,
+                    timestamp: u32::MAX as u64 - 10,
+                },
+                DvsEvent {
+                    x: 0,
+                    y: 1,
+                    polarity: false,
+                    timestamp: (1 << 32) + 20,
+                },
+            ]
+        );
+        assert!(read_aedat2(&bytes[..bytes.len() - 3]).is_err());
+    }
+
+    #[test]
+    fn text_events_should_be_mapped_to_pixel_ports() {
+        let text = "# t x y p\n0.5 1 0 1\n0.25,2,1,0\n\n";
+        let events = read_text(text.as_bytes()).unwrap();
+        let ports = DvsPorts {
+            width: 3,
+            height: 2,
+            split_polarity: true,
+        };
+
+        assert_eq!(ports.len(), 12);
+        assert_eq!(
+            ports.timed_signals(&events, 1),
+            vec![
+                TimedSignal {
+                    offset: Duration::ZERO,
+                    port: 10,
+                    signal: 1,
+                },
+                TimedSignal {
+                    offset: Duration::from_millis(250),
+                    port: 3,
+                    signal: 1,
+                },
+            ]
+        );
+        assert!(read_text("0.5 1 0".as_bytes()).is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn replay_should_keep_relative_timing() {
+        let net = Arc::new(new_network_fixture());
+        let id = net
+            .create_neuron(
+                net.clone(),
+                0,
+                vec![InputCfg::new(3, 3, 1).unwrap()],
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap()
+            .get_id();
+        assert!(net.setup_input(1, &id, 0).await.is_ok());
+        let ports = DvsPorts {
+            width: 2,
+            height: 1,
+            split_polarity: false,
+        };
+        let events: Vec<DvsEvent> = [1_000_000, 1_040_000]
+            .into_iter()
+            .map(|timestamp| DvsEvent {
+                x: 1,
+                y: 0,
+                polarity: true,
+                timestamp,
+            })
+            .collect();
+
+        let start = Instant::now();
+        assert!(replay(&net, &events, &ports, 1).await.is_ok());
+
+        assert!(start.elapsed() >= Duration::from_millis(40));
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+}
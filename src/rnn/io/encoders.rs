@@ -0,0 +1,247 @@
+//! Encoders of external data into timed signals for input ports. Values are
+//! expected to be normalized into [0, 1] (others are clamped) and the value
+//! with index i goes into the input port i.
+
+use std::error::Error;
+use std::time::Duration;
+
+use tokio::time::{self, Instant};
+
+use crate::rnn::common::rng::SeededRng;
+use crate::rnn::common::signal::Signal;
+use crate::rnn::layouts::network::Network;
+
+/// The signal going into the input port at the offset from the stimulus start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedSignal {
+    pub offset: Duration,
+    pub port: usize,
+    pub signal: Signal,
+}
+
+pub trait Encoder {
+    /// Timed signals of the values ordered by offset.
+    fn encode(&self, values: &[f64]) -> Vec<TimedSignal>;
+
+    /// Pixels of the image row (or the whole flattened image), 255 is the brightest.
+    fn encode_pixels(&self, pixels: &[u8]) -> Vec<TimedSignal> {
+        let values: Vec<f64> = pixels.iter().map(|pixel| *pixel as f64 / 255.0).collect();
+        self.encode(&values)
+    }
+}
+
+/// Rate coding: the value defines the number of spikes evenly spread over the window,
+/// the value 1 gives max_rate spikes per second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateEncoder {
+    pub window: Duration,
+    pub max_rate: f64,
+    pub signal: Signal,
+}
+
+impl Encoder for RateEncoder {
+    fn encode(&self, values: &[f64]) -> Vec<TimedSignal> {
+        let mut timed_signals = vec![];
+        for (port, value) in values.iter().enumerate() {
+            let count =
+                (value.clamp(0.0, 1.0) * self.max_rate * self.window.as_secs_f64()).round() as u32;
+            for spike in 0..count {
+                timed_signals.push(TimedSignal {
+                    offset: self.window * spike / count,
+                    port,
+                    signal: self.signal,
+                });
+            }
+        }
+        timed_signals.sort_by_key(|timed_signal| (timed_signal.offset, timed_signal.port));
+        timed_signals
+    }
+}
+
+/// Poisson rate coding: spikes of the value come with exponentially distributed
+/// intervals during the window, the value 1 gives max_rate spikes per second on average.
+/// The same seed produces the same spikes for the same values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoissonEncoder {
+    pub window: Duration,
+    pub max_rate: f64,
+    pub signal: Signal,
+    pub seed: u64,
+}
+
+impl Encoder for PoissonEncoder {
+    fn encode(&self, values: &[f64]) -> Vec<TimedSignal> {
+        let mut rng = SeededRng::new(self.seed);
+        let window = self.window.as_secs_f64();
+        let mut timed_signals = vec![];
+        for (port, value) in values.iter().enumerate() {
+            let rate = value.clamp(0.0, 1.0) * self.max_rate;
+            if rate.is_nan() || rate <= 0.0 {
+                continue;
+            }
+            let mut offset = -(1.0 - rng.next_f64()).ln() / rate;
+            while offset < window {
+                timed_signals.push(TimedSignal {
+                    offset: Duration::from_secs_f64(offset),
+                    port,
+                    signal: self.signal,
+                });
+                offset += -(1.0 - rng.next_f64()).ln() / rate;
+            }
+        }
+        timed_signals.sort_by_key(|timed_signal| (timed_signal.offset, timed_signal.port));
+        timed_signals
+    }
+}
+
+/// Latency coding: the single spike per value, the greater value spikes earlier.
+/// Zero values do not spike at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyEncoder {
+    pub window: Duration,
+    pub signal: Signal,
+}
+
+impl Encoder for LatencyEncoder {
+    fn encode(&self, values: &[f64]) -> Vec<TimedSignal> {
+        let mut timed_signals: Vec<TimedSignal> = values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value > 0.0)
+            .map(|(port, value)| TimedSignal {
+                offset: self.window.mul_f64(1.0 - value.min(1.0)),
+                port,
+                signal: self.signal,
+            })
+            .collect();
+        timed_signals.sort_by_key(|timed_signal| (timed_signal.offset, timed_signal.port));
+        timed_signals
+    }
+}
+
+/// Send timed signals into input ports of the network keeping their offsets.
+pub async fn send_timed(
+    network: &Network,
+    timed_signals: &[TimedSignal],
+) -> Result<(), Box<dyn Error>> {
+    let start = Instant::now();
+    for timed_signal in timed_signals {
+        time::sleep_until(start + timed_signal.offset).await;
+        network
+            .input(timed_signal.signal, timed_signal.port)
+            .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+
+    #[test]
+    fn rate_encoder_should_spread_spikes_over_window() {
+        let encoder = RateEncoder {
+            window: Duration::from_millis(100),
+            max_rate: 40.0,
+            signal: 1,
+        };
+
+        let timed_signals = encoder.encode(&[1.0, 0.5, 0.0, 7.0]);
+
+        let count = |port| {
+            timed_signals
+                .iter()
+                .filter(|timed_signal| timed_signal.port == port)
+                .count()
+        };
+        assert_eq!((count(0), count(1), count(2), count(3)), (4, 2, 0, 4));
+        assert!(timed_signals
+            .windows(2)
+            .all(|pair| pair[0].offset <= pair[1].offset));
+        assert!(timed_signals
+            .iter()
+            .all(|timed_signal| timed_signal.offset < encoder.window));
+    }
+
+    #[test]
+    fn poisson_encoder_should_follow_pixel_intensity() {
+        let encoder = PoissonEncoder {
+            window: Duration::from_secs(1),
+            max_rate: 1000.0,
+            signal: 1,
+            seed: 7,
+        };
+
+        let timed_signals = encoder.encode_pixels(&[255, 0, 128]);
+
+        let count = |port| {
+            timed_signals
+                .iter()
+                .filter(|timed_signal| timed_signal.port == port)
+                .count() as f64
+        };
+        assert!((count(0) - 1000.0).abs() < 100.0);
+        assert_eq!(count(1), 0.0);
+        assert!((count(2) - 500.0).abs() < 75.0);
+        assert!(timed_signals
+            .iter()
+            .all(|timed_signal| timed_signal.offset < encoder.window));
+        assert_eq!(encoder.encode_pixels(&[255, 0, 128]), timed_signals);
+    }
+
+    #[test]
+    fn latency_encoder_should_spike_greater_values_earlier() {
+        let encoder = LatencyEncoder {
+            window: Duration::from_millis(100),
+            signal: 1,
+        };
+
+        let timed_signals = encoder.encode_pixels(&[51, 255, 0]);
+
+        assert_eq!(timed_signals.len(), 2);
+        assert_eq!(timed_signals[0].port, 1);
+        assert_eq!(timed_signals[0].offset, Duration::ZERO);
+        assert_eq!(timed_signals[1].port, 0);
+        assert_eq!(timed_signals[1].offset, Duration::from_millis(80));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn timed_signals_should_be_sent_at_offsets() {
+        let net = Arc::new(new_network_fixture());
+        let id = net
+            .create_neuron(
+                net.clone(),
+                0,
+                vec![InputCfg::new(3, 3, 1).unwrap()],
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap()
+            .get_id();
+        assert!(net.setup_input(0, &id, 0).await.is_ok());
+        let timed_signals = vec![TimedSignal {
+            offset: Duration::from_millis(30),
+            port: 0,
+            signal: 1,
+        }];
+
+        let start = Instant::now();
+        assert!(send_timed(&net, &timed_signals).await.is_ok());
+
+        assert!(start.elapsed() >= Duration::from_millis(30));
+        assert!(send_timed(
+            &net,
+            &[TimedSignal {
+                port: 3,
+                ..timed_signals[0]
+            }]
+        )
+        .await
+        .is_err());
+    }
+}
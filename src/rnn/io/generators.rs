@@ -0,0 +1,196 @@
+//! Spike train generators driving input ports of the network. Every generator
+//! runs as the tokio task sending the signal into the port at intervals of
+//! the train until it is cancelled, the input fails or the network is dropped.
+
+use std::error::Error;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time;
+use tokio_util::sync::CancellationToken;
+
+use crate::rnn::common::rng::SeededRng;
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::signal::Signal;
+use crate::rnn::layouts::network::Network;
+
+/// The source of intervals between spikes.
+pub trait SpikeTrain: Send + 'static {
+    fn next_interval(&mut self) -> Duration;
+}
+
+/// Spikes with exponentially distributed intervals: `rate` spikes per second on average.
+#[derive(Debug, Clone)]
+pub struct PoissonSpikeTrain {
+    rate: f64,
+    rng: SeededRng,
+}
+
+impl PoissonSpikeTrain {
+    /// The same seed produces the same sequence of intervals.
+    pub fn new(rate: f64, seed: u64) -> Result<Self, Box<dyn Error>> {
+        if !rate.is_finite() || rate <= 0.0 {
+            return Err(Box::new(RnnError::NotSupportedArgValue));
+        }
+        Ok(PoissonSpikeTrain {
+            rate,
+            rng: SeededRng::new(seed),
+        })
+    }
+}
+
+impl SpikeTrain for PoissonSpikeTrain {
+    fn next_interval(&mut self) -> Duration {
+        Duration::from_secs_f64(-(1.0 - self.rng.next_f64()).ln() / self.rate)
+    }
+}
+
+/// Spikes at the constant interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegularSpikeTrain {
+    interval: Duration,
+}
+
+impl RegularSpikeTrain {
+    pub fn new(interval: Duration) -> Result<Self, Box<dyn Error>> {
+        if interval.is_zero() {
+            return Err(Box::new(RnnError::NotSupportedArgValue));
+        }
+        Ok(RegularSpikeTrain { interval })
+    }
+}
+
+impl SpikeTrain for RegularSpikeTrain {
+    fn next_interval(&mut self) -> Duration {
+        self.interval
+    }
+}
+
+/// The handle of the running generator.
+#[derive(Debug)]
+pub struct GeneratorHandle {
+    sent: Arc<AtomicU64>,
+    cancel_token: CancellationToken,
+    task_handler: JoinHandle<Result<(), String>>,
+}
+
+impl GeneratorHandle {
+    /// The number of signals sent so far.
+    pub fn get_sent(&self) -> u64 {
+        self.sent.load(Ordering::Relaxed)
+    }
+
+    pub fn cancel(&self) {
+        self.cancel_token.cancel();
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.task_handler.is_finished()
+    }
+
+    /// Cancel the generator and wait for its end. Returns the number of sent
+    /// signals or the error which stopped the generator before.
+    pub async fn stop(self) -> Result<u64, Box<dyn Error>> {
+        self.cancel_token.cancel();
+        self.task_handler.await??;
+        Ok(self.sent.load(Ordering::Relaxed))
+    }
+}
+
+/// Spawn the generator sending the signal into the input port of the network
+/// after every interval of the train.
+pub fn spawn_generator(
+    mut train: impl SpikeTrain,
+    network: &Arc<Network>,
+    port: usize,
+    signal: Signal,
+) -> GeneratorHandle {
+    let sent = Arc::new(AtomicU64::new(0));
+    let cancel_token = CancellationToken::new();
+    let network: Weak<Network> = Arc::downgrade(network);
+
+    let sent_cloned = sent.clone();
+    let token_cloned = cancel_token.clone();
+    let task_handler = tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = token_cloned.cancelled() => return Ok(()),
+                _ = time::sleep(train.next_interval()) => {}
+            }
+            let Some(network) = network.upgrade() else {
+                return Ok(());
+            };
+            network
+                .input(signal, port)
+                .await
+                .map_err(|err| err.to_string())?;
+            sent_cloned.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+
+    GeneratorHandle {
+        sent,
+        cancel_token,
+        task_handler,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+
+    async fn relay_network_fixture() -> Arc<Network> {
+        let net = Arc::new(new_network_fixture());
+        let id = net
+            .create_neuron(
+                net.clone(),
+                0,
+                vec![InputCfg::new(3, 3, 1).unwrap()],
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap()
+            .get_id();
+        assert!(net.setup_input(0, &id, 0).await.is_ok());
+        net
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn regular_train_should_send_signal_every_interval() {
+        let net = relay_network_fixture().await;
+        let train = RegularSpikeTrain::new(Duration::from_millis(10)).unwrap();
+
+        let handle = spawn_generator(train, &net, 0, 1);
+        time::sleep(Duration::from_millis(55)).await;
+
+        assert_eq!(handle.stop().await.unwrap(), 5);
+    }
+
+    #[test]
+    fn poisson_train_should_follow_rate_on_average() {
+        let mut train = PoissonSpikeTrain::new(100.0, 7).unwrap();
+
+        let total: Duration = (0..1000).map(|_| train.next_interval()).sum();
+
+        assert!((total.as_secs_f64() - 10.0).abs() < 1.0);
+        assert!(PoissonSpikeTrain::new(0.0, 7).is_err());
+        assert!(RegularSpikeTrain::new(Duration::ZERO).is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn generator_should_stop_on_input_error() {
+        let net = relay_network_fixture().await;
+        let train = RegularSpikeTrain::new(Duration::from_millis(10)).unwrap();
+
+        let handle = spawn_generator(train, &net, 5, 1);
+        time::sleep(Duration::from_millis(15)).await;
+
+        assert!(handle.is_finished());
+        assert!(handle.stop().await.is_err());
+    }
+}
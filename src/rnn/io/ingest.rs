@@ -0,0 +1,321 @@
+//! Ingestion of datasets into input ports: every record of the CSV or JSONL source
+//! is mapped to values of input ports by the spec, encoded and sent into the network.
+//! Records are paced by their timestamps, or by the fixed interval without them.
+//!
+//! CSV sources start with the header line of field names; quoted cells are not supported.
+//! JSONL sources have the json object per line. Non-numeric values are ignored.
+//!
+//! ```ignore
+//! let spec = IngestSpec::load(Path::new("sensors.json"))?;
+//! let records = ingest(&network, source(None).await?, &spec, &encoder).await?;
+//! ```
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+use tokio::time::{self, Instant};
+
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::layouts::network::Network;
+
+use super::encoders::{send_timed, Encoder};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Format {
+    Csv,
+    Jsonl,
+}
+
+/// The field of records going into the input port. Values are normalized
+/// from [min, max] into [0, 1] before encoding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldMapping {
+    pub field: String,
+    pub port: usize,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl FieldMapping {
+    fn normalize(&self, value: f64) -> f64 {
+        if self.max > self.min {
+            (value - self.min) / (self.max - self.min)
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IngestSpec {
+    pub format: Format,
+
+    pub mappings: Vec<FieldMapping>,
+
+    /// The field with timestamps of records in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+
+    /// The interval between records without the timestamp field
+    pub interval: Duration,
+}
+
+/// The record mapped to values of input ports: the value with index i goes into the port i.
+/// Ports without mappings get 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub timestamp: Option<f64>,
+    pub values: Vec<f64>,
+}
+
+impl IngestSpec {
+    /// Read the spec from json file.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_reader(File::open(path)?)?)
+    }
+
+    pub fn map_record(&self, fields: &BTreeMap<String, f64>) -> Result<Record, Box<dyn Error>> {
+        let field = |name: &String| {
+            fields
+                .get(name)
+                .copied()
+                .ok_or_else(|| RnnError::MalformedRecord(format!("missing field {name}")))
+        };
+
+        let ports = self
+            .mappings
+            .iter()
+            .map(|mapping| mapping.port + 1)
+            .max()
+            .unwrap_or(0);
+        let mut values = vec![0.0; ports];
+        for mapping in self.mappings.iter() {
+            values[mapping.port] = mapping.normalize(field(&mapping.field)?);
+        }
+        let timestamp = match &self.timestamp {
+            Some(name) => Some(field(name)?),
+            None => None,
+        };
+        Ok(Record { timestamp, values })
+    }
+}
+
+/// Numeric fields of records line by line.
+#[derive(Debug, Clone)]
+pub struct RecordParser {
+    format: Format,
+    header: Option<Vec<String>>,
+    line: usize,
+}
+
+impl RecordParser {
+    pub fn new(format: Format) -> Self {
+        RecordParser {
+            format,
+            header: None,
+            line: 0,
+        }
+    }
+
+    /// None for empty lines and the CSV header.
+    pub fn parse(&mut self, line: &str) -> Result<Option<BTreeMap<String, f64>>, Box<dyn Error>> {
+        self.line += 1;
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(None);
+        }
+
+        match self.format {
+            Format::Jsonl => {
+                let object: serde_json::Map<String, serde_json::Value> =
+                    serde_json::from_str(line).map_err(|err| self.malformed(&err.to_string()))?;
+                Ok(Some(
+                    object
+                        .into_iter()
+                        .filter_map(|(name, value)| value.as_f64().map(|value| (name, value)))
+                        .collect(),
+                ))
+            }
+            Format::Csv => {
+                let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+                let Some(header) = &self.header else {
+                    self.header = Some(cells.into_iter().map(String::from).collect());
+                    return Ok(None);
+                };
+                if cells.len() != header.len() {
+                    return Err(self.malformed(&format!(
+                        "{} cells instead of {}",
+                        cells.len(),
+                        header.len()
+                    )));
+                }
+                Ok(Some(
+                    header
+                        .iter()
+                        .zip(cells)
+                        .filter_map(|(name, cell)| {
+                            cell.parse().ok().map(|value| (name.clone(), value))
+                        })
+                        .collect(),
+                ))
+            }
+        }
+    }
+
+    fn malformed(&self, detail: &str) -> Box<dyn Error> {
+        Box::new(RnnError::MalformedRecord(format!(
+            "line {}: {detail}",
+            self.line
+        )))
+    }
+}
+
+/// The file at the path or stdin without the path. Not available on wasm32.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn source(
+    path: Option<&Path>,
+) -> Result<Box<dyn AsyncBufRead + Unpin + Send>, Box<dyn Error>> {
+    Ok(match path {
+        Some(path) => Box::new(tokio::io::BufReader::new(
+            tokio::fs::File::open(path).await?,
+        )),
+        None => Box::new(tokio::io::BufReader::new(tokio::io::stdin())),
+    })
+}
+
+/// Send records of the source into the network until the source ends. Every record
+/// is sent at its timestamp relative to the first one (or after the interval) and
+/// its signals keep offsets given by the encoder. Returns the number of sent records.
+pub async fn ingest<R>(
+    network: &Network,
+    source: R,
+    spec: &IngestSpec,
+    encoder: &impl Encoder,
+) -> Result<u64, Box<dyn Error>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut lines = source.lines();
+    let mut parser = RecordParser::new(spec.format);
+    let start = Instant::now();
+    let mut first_timestamp = None;
+    let mut sent: u64 = 0;
+
+    while let Some(line) = lines.next_line().await? {
+        let Some(fields) = parser.parse(&line)? else {
+            continue;
+        };
+        let record = spec.map_record(&fields)?;
+        let offset = match record.timestamp {
+            Some(timestamp) => {
+                let first = *first_timestamp.get_or_insert(timestamp);
+                Duration::try_from_secs_f64(timestamp - first).unwrap_or_default()
+            }
+            None => spec.interval * sent as u32,
+        };
+        time::sleep_until(start + offset).await;
+        send_timed(network, &encoder.encode(&record.values)).await?;
+        sent += 1;
+    }
+
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::io::encoders::RateEncoder;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+
+    fn spec_fixture(format: Format, timestamp: Option<&str>) -> IngestSpec {
+        IngestSpec {
+            format,
+            mappings: vec![FieldMapping {
+                field: String::from("temp"),
+                port: 1,
+                min: 10.0,
+                max: 30.0,
+            }],
+            timestamp: timestamp.map(String::from),
+            interval: Duration::from_millis(10),
+        }
+    }
+
+    #[test]
+    fn csv_records_should_be_mapped_to_ports() {
+        let spec = spec_fixture(Format::Csv, Some("t"));
+        let mut parser = RecordParser::new(Format::Csv);
+
+        assert_eq!(parser.parse("t, temp, label").unwrap(), None);
+        let fields = parser.parse("0.5, 25, warm").unwrap().unwrap();
+
+        assert_eq!(
+            spec.map_record(&fields).unwrap(),
+            Record {
+                timestamp: Some(0.5),
+                values: vec![0.0, 0.75],
+            }
+        );
+        assert!(parser.parse("0.6, 25").is_err());
+        let fields = parser.parse("0.7, , cold").unwrap().unwrap();
+        assert!(spec.map_record(&fields).is_err());
+    }
+
+    #[test]
+    fn jsonl_records_should_take_numeric_fields() {
+        let mut parser = RecordParser::new(Format::Jsonl);
+
+        assert_eq!(parser.parse("").unwrap(), None);
+        let fields = parser
+            .parse(r#"{"temp": 10, "label": "cold"}"#)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(fields, BTreeMap::from([(String::from("temp"), 10.0)]));
+        assert!(parser.parse("[1, 2]").is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn records_should_be_paced_by_timestamps() {
+        let net = Arc::new(new_network_fixture());
+        let id = net
+            .create_neuron(
+                net.clone(),
+                0,
+                vec![InputCfg::new(3, 3, 1).unwrap()],
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap()
+            .get_id();
+        assert!(net.setup_input(1, &id, 0).await.is_ok());
+        let encoder = RateEncoder {
+            window: Duration::from_millis(10),
+            max_rate: 100.0,
+            signal: 1,
+        };
+        let data = "{\"t\": 1.0, \"temp\": 30}\n{\"t\": 1.2, \"temp\": 20}\n";
+
+        let start = Instant::now();
+        let sent = ingest(
+            &net,
+            data.as_bytes(),
+            &spec_fixture(Format::Jsonl, Some("t")),
+            &encoder,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(sent, 2);
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
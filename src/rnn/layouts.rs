@@ -1,2 +1,15 @@
+pub mod co_simulation;
+pub mod construction_log;
+pub mod ensemble;
+pub mod event_queue;
+pub mod layered_builder;
+pub mod monitoring_store;
 pub mod network;
+pub mod network_registry;
+pub mod neuron_group;
+pub mod pynn;
+pub mod reservoir;
+pub mod routing_table;
+pub mod sharded_executor;
 pub mod signal_handler;
+pub mod topology;
@@ -0,0 +1,141 @@
+//! Co-simulation of the network with an external model (physics simulation,
+//! plant model) in lockstep with SimulationMode::Stepped: on every tick the model
+//! takes signals of output ports emitted during the previous step and returns
+//! signals of input ports for the next one, so closed control loops have
+//! deterministic timing.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::time::Duration;
+
+use tokio::time;
+
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::runtime_cfg::SimulationMode;
+use crate::rnn::common::signal::Signal;
+
+use super::network::Network;
+
+/// The model advanced once per network step.
+pub trait ExternalModel {
+    /// Advance the model to the tick. `outputs` are signals emitted into output
+    /// ports by port index during the previous step, empty on the first tick.
+    /// Returns (input port, signal) pairs delivered by the step of this tick.
+    fn advance(
+        &mut self,
+        tick: u64,
+        outputs: &BTreeMap<usize, Vec<Signal>>,
+    ) -> Vec<(usize, Signal)>;
+}
+
+impl<F> ExternalModel for F
+where
+    F: FnMut(u64, &BTreeMap<usize, Vec<Signal>>) -> Vec<(usize, Signal)>,
+{
+    fn advance(
+        &mut self,
+        tick: u64,
+        outputs: &BTreeMap<usize, Vec<Signal>>,
+    ) -> Vec<(usize, Signal)> {
+        self(tick, outputs)
+    }
+}
+
+/// Signals exchanged on the tick.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tick {
+    pub tick: u64,
+
+    /// (input port, signal) pairs returned by the model
+    pub inputs: Vec<(usize, Signal)>,
+
+    /// Signals of output ports emitted during the step by port index
+    pub outputs: BTreeMap<usize, Vec<Signal>>,
+}
+
+/// Run the network and the model in lockstep for the number of ticks and return
+/// the history of exchanged signals. Tokio time advances by `tick_duration` after
+/// every step (zero keeps it), so with paused time the loop timing is deterministic.
+pub async fn co_simulate(
+    network: &Network,
+    model: &mut impl ExternalModel,
+    ticks: u64,
+    tick_duration: Duration,
+) -> Result<Vec<Tick>, Box<dyn Error>> {
+    if network.get_runtime_cfg().simulation != SimulationMode::Stepped {
+        return Err(Box::new(RnnError::NotSupportedArgValue));
+    }
+
+    let mut history: Vec<Tick> = vec![];
+    let mut outputs = BTreeMap::new();
+    for tick in 0..ticks {
+        let inputs = model.advance(tick, &outputs);
+        for (port, signal) in inputs.iter() {
+            network.input(*signal, *port).await?;
+        }
+        outputs = network.step().await?;
+        if !tick_duration.is_zero() {
+            time::sleep(tick_duration).await;
+        }
+        history.push(Tick {
+            tick,
+            inputs,
+            outputs: outputs.clone(),
+        });
+    }
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::tests::fixtures::{new_network_fixture, new_stepped_network_fixture};
+
+    /// I0 -> Z0 -> O0
+    async fn relay_network_fixture(net: Arc<Network>) -> Arc<Network> {
+        let n = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+        assert!(net.setup_output(0, &n.get_id()).await.is_ok());
+        net
+    }
+
+    #[tokio::test]
+    async fn model_should_close_loop_with_network() {
+        let net = relay_network_fixture(Arc::new(new_stepped_network_fixture())).await;
+
+        // the plant drives the relay while it is silent
+        let mut plant =
+            |_tick: u64, outputs: &BTreeMap<usize, Vec<Signal>>| -> Vec<(usize, Signal)> {
+                if outputs.get(&0).is_some_and(|signals| !signals.is_empty()) {
+                    vec![]
+                } else {
+                    vec![(0, 2)]
+                }
+            };
+        let history = co_simulate(&net, &mut plant, 4, Duration::ZERO)
+            .await
+            .unwrap();
+
+        let responses: Vec<usize> = history.iter().map(|tick| tick.outputs[&0].len()).collect();
+        assert_eq!(responses, vec![1, 0, 1, 0]);
+        assert_eq!(history[2].inputs, vec![(0, 2)]);
+    }
+
+    #[tokio::test]
+    async fn co_simulation_should_require_stepped_network() {
+        let net = relay_network_fixture(Arc::new(new_network_fixture())).await;
+        fn silent(_tick: u64, _outputs: &BTreeMap<usize, Vec<Signal>>) -> Vec<(usize, Signal)> {
+            vec![]
+        }
+
+        assert!(co_simulate(&net, &mut silent, 1, Duration::ZERO)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::{
+    input_cfg::InputCfg,
+    network_cfg::{LabelMap, NetworkCfg, NetworkLineage, NeuronKind},
+    signal::{Signal, Weight},
+};
+
+/// The mutation of the network's topology or parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum NetworkEvent {
+    NeuronCreated {
+        id: String,
+        bias: Weight,
+        input_configs: Vec<InputCfg>,
+
+        /// Logs recorded before neuron kinds have the default one
+        #[serde(default)]
+        kind: NeuronKind,
+    },
+    NeuronRemoved {
+        id: String,
+    },
+    NeuronConfigured {
+        id: String,
+        input_configs: Vec<InputCfg>,
+    },
+    NeuronsConnected {
+        src_id: String,
+        dst_id: String,
+        dst_synapse_idx: usize,
+    },
+    NeuronsDisconnected {
+        src_id: String,
+        dst_id: String,
+        dst_synapse_idx: usize,
+    },
+    InputSetup {
+        input_port: usize,
+        dst_id: String,
+        dst_synapse_idx: usize,
+    },
+    InputFreed {
+        input_port: usize,
+    },
+    OutputSetup {
+        src_id: String,
+        output_port: usize,
+    },
+    OutputFreed {
+        output_port: usize,
+    },
+    SynapseUpdated {
+        id: String,
+        port: usize,
+        weight: Option<Weight>,
+        capacity: Option<Signal>,
+    },
+    BiasUpdated {
+        id: String,
+        bias: Weight,
+    },
+    LabelsSet {
+        labels: LabelMap,
+    },
+    SeedSet {
+        seed: Option<u64>,
+    },
+    LineageSet {
+        lineage: Option<NetworkLineage>,
+    },
+
+    /// The whole topology has been replaced with configuration (e.g. rollback to checkpoint).
+    ConfigRestored {
+        config: NetworkCfg,
+    },
+}
+
+/// The log entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventRecord {
+    /// Sequence number of the event in the log.
+    pub seq: u64,
+
+    /// The moment when mutation happened.
+    pub timestamp: DateTime<Utc>,
+
+    pub event: NetworkEvent,
+}
+
+/// Append-only log of the network's structural mutations.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ConstructionLog {
+    records: Vec<EventRecord>,
+}
+
+impl ConstructionLog {
+    pub fn append(&mut self, event: NetworkEvent) {
+        self.append_at(event, Utc::now());
+    }
+
+    /// Append the event happened at specified moment.
+    pub fn append_at(&mut self, event: NetworkEvent, timestamp: DateTime<Utc>) {
+        let seq = self.records.len() as u64;
+        self.records.push(EventRecord {
+            seq,
+            timestamp,
+            event,
+        });
+    }
+
+    pub fn records(&self) -> &[EventRecord] {
+        &self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_append_records_with_sequence_numbers() {
+        let mut log = ConstructionLog::default();
+        assert!(log.is_empty());
+
+        log.append(NetworkEvent::NeuronRemoved {
+            id: String::from("M0Z0"),
+        });
+        log.append(NetworkEvent::OutputFreed { output_port: 1 });
+
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.records()[0].seq, 0);
+        assert_eq!(log.records()[1].seq, 1);
+        assert_eq!(
+            log.records()[1].event,
+            NetworkEvent::OutputFreed { output_port: 1 }
+        );
+    }
+
+    #[test]
+    fn log_should_survive_json_round_trip() {
+        let mut log = ConstructionLog::default();
+        log.append(NetworkEvent::NeuronCreated {
+            id: String::from("M0Z0"),
+            bias: 1,
+            input_configs: vec![InputCfg::new(2, 1, -1).unwrap()],
+            kind: NeuronKind::Inhibitory,
+        });
+
+        let log_json = serde_json::to_string(&log).unwrap();
+        let restored: ConstructionLog = serde_json::from_str(&log_json).unwrap();
+
+        assert_eq!(restored.records(), log.records());
+    }
+}
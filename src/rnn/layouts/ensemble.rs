@@ -0,0 +1,234 @@
+//! Ensemble of networks: the same sample is fed into every member network and
+//! their outputs are aggregated into the single prediction, which is decoded
+//! by the Reconstructible implementation of the output type.
+
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::signal::Signal;
+use crate::rnn::training::encoding::Reconstructible;
+use crate::rnn::training::inference::infer_batch;
+
+use super::network::Network;
+
+/// How outputs of members are combined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Voting {
+    /// Outputs of every member are decoded and the value with the largest sum
+    /// of member weights wins. Ties go to the value of the earlier member.
+    #[default]
+    Majority,
+
+    /// Signals of every output port are averaged with member weights
+    /// and the averaged signals are decoded.
+    WeightedAverage,
+}
+
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub network: Arc<Network>,
+
+    /// The member's vote
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Ensemble {
+    members: Vec<Member>,
+    voting: Voting,
+}
+
+impl Ensemble {
+    pub fn new(voting: Voting) -> Self {
+        Ensemble {
+            members: vec![],
+            voting,
+        }
+    }
+
+    /// Members should have the same input and output ports.
+    pub fn add_member(&mut self, network: Arc<Network>, weight: f64) {
+        self.members.push(Member { network, weight });
+    }
+
+    pub fn get_members(&self) -> &[Member] {
+        &self.members
+    }
+
+    pub fn get_voting(&self) -> Voting {
+        self.voting
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Feed the encoded sample into members one by one (see training::inference::infer_batch())
+    /// and combine their responses. None when the combined output is not decodable.
+    pub async fn infer<I, O>(
+        &self,
+        sample: &I,
+        encoder: impl Fn(&I) -> Vec<Signal>,
+        per_member_budget: Duration,
+    ) -> Result<Option<O>, Box<dyn Error>>
+    where
+        O: Reconstructible + PartialEq,
+    {
+        let signals = encoder(sample);
+        let mut outputs = vec![];
+        for member in self.members.iter() {
+            let inference = infer_batch(
+                &member.network,
+                &[()],
+                |_| signals.clone(),
+                |_| Some(()),
+                per_member_budget,
+            )
+            .await?
+            .pop()
+            .ok_or_else(|| Box::new(RnnError::ExpectedDataNotPresent(member.network.get_id())))?;
+            outputs.push(inference.signals);
+        }
+        Ok(self.vote(&outputs))
+    }
+
+    /// Combine signals of output ports of members (given in order of members).
+    pub fn vote<O>(&self, outputs: &[Vec<Signal>]) -> Option<O>
+    where
+        O: Reconstructible + PartialEq,
+    {
+        let weighted = self
+            .members
+            .iter()
+            .map(|member| member.weight)
+            .zip(outputs.iter());
+
+        match self.voting {
+            Voting::Majority => {
+                let mut tally: Vec<(O, f64)> = vec![];
+                for (weight, signals) in weighted {
+                    let Some(value) = O::reconstruct(signals) else {
+                        continue;
+                    };
+                    match tally.iter_mut().find(|(candidate, _)| *candidate == value) {
+                        Some((_, votes)) => *votes += weight,
+                        None => tally.push((value, weight)),
+                    }
+                }
+                let mut winner: Option<(O, f64)> = None;
+                for (value, votes) in tally {
+                    let leads = match &winner {
+                        Some((_, best)) => votes > *best,
+                        None => true,
+                    };
+                    if leads {
+                        winner = Some((value, votes));
+                    }
+                }
+                winner.map(|(value, _)| value)
+            }
+            Voting::WeightedAverage => {
+                let total: f64 = weighted.clone().map(|(weight, _)| weight).sum();
+                if total <= 0.0 {
+                    return None;
+                }
+                let ports = outputs.iter().map(Vec::len).max().unwrap_or(0);
+                let averaged: Vec<Signal> = (0..ports)
+                    .map(|port| {
+                        let sum: f64 = weighted
+                            .clone()
+                            .map(|(weight, signals)| {
+                                weight * signals.get(port).copied().unwrap_or(0) as f64
+                            })
+                            .sum();
+                        (sum / total).round() as Signal
+                    })
+                    .collect();
+                O::reconstruct(&averaged)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+    use crate::rnn::training::encoding::Splittable;
+
+    fn ensemble_fixture(voting: Voting, weights: &[f64]) -> Ensemble {
+        let mut ensemble = Ensemble::new(voting);
+        for weight in weights {
+            ensemble.add_member(Arc::new(new_network_fixture()), *weight);
+        }
+        ensemble
+    }
+
+    /// I0 -> Z0 -> O0
+    async fn relay_fixture() -> Arc<Network> {
+        let net = Arc::new(new_network_fixture());
+        let id = net
+            .create_neuron(
+                net.clone(),
+                0,
+                vec![InputCfg::new(3, 3, 1).unwrap()],
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap()
+            .get_id();
+        assert!(net.setup_input(0, &id, 0).await.is_ok());
+        assert!(net.setup_output(0, &id).await.is_ok());
+        net
+    }
+
+    #[test]
+    fn majority_should_sum_weights_of_decoded_values() {
+        let ensemble = ensemble_fixture(Voting::Majority, &[1.0, 1.0, 2.5]);
+        let outputs = vec![vec![1], vec![1], vec![0]];
+
+        assert_eq!(ensemble.vote::<bool>(&outputs), Some(false));
+
+        let ensemble = ensemble_fixture(Voting::Majority, &[1.0, 1.0, 1.0]);
+        assert_eq!(ensemble.vote::<bool>(&outputs), Some(true));
+        assert_eq!(ensemble.vote::<bool>(&[vec![0, 1], vec![1, 1]]), None);
+    }
+
+    #[test]
+    fn weighted_average_should_decode_averaged_signals() {
+        let ensemble = ensemble_fixture(Voting::WeightedAverage, &[3.0, 1.0]);
+
+        assert_eq!(ensemble.vote::<bool>(&[vec![0], vec![4]]), Some(true));
+        assert_eq!(ensemble.vote::<bool>(&[vec![0], vec![1]]), Some(false));
+        assert_eq!(
+            ensemble_fixture(Voting::WeightedAverage, &[]).vote::<bool>(&[]),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn infer_should_combine_responses_of_members() {
+        let mut ensemble = Ensemble::new(Voting::Majority);
+        for _ in 0..3 {
+            ensemble.add_member(relay_fixture().await, 1.0);
+        }
+
+        let prediction: Option<bool> = ensemble
+            .infer(&true, bool::split, Duration::from_millis(100))
+            .await
+            .unwrap();
+
+        assert_eq!(ensemble.len(), 3);
+        assert_eq!(prediction, Some(true));
+    }
+}
@@ -0,0 +1,210 @@
+//! The network-wide queue of signal events used with RoutingBackend::EventDriven.
+//! Events are ordered by logical time (the number of hops from the input)
+//! and then by arrival, and are processed by a small pool of worker tasks
+//! instead of a task per neuron or per synapse. The queue is split into
+//! partitions by the hash of destination neuron id and every partition has
+//! its own worker, so events of one neuron are processed one by one in order.
+
+use std::cmp::{Ordering as CmpOrdering, Reverse};
+use std::collections::BinaryHeap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use tokio::sync::Notify;
+
+use crate::rnn::common::signal::Signal;
+
+/// The signal going into the synapse of destination neuron.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignalEvent {
+    /// The logical time: the event caused by the event at time t happens at t + 1
+    pub time: u64,
+
+    /// Arrival order of events with the same time
+    seq: u64,
+    pub dst_id: String,
+    pub dst_synapse_idx: usize,
+    pub signal: Signal,
+}
+
+impl Ord for SignalEvent {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        (self.time, self.seq).cmp(&(other.time, other.seq))
+    }
+}
+
+impl PartialOrd for SignalEvent {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Partition {
+    heap: Mutex<BinaryHeap<Reverse<SignalEvent>>>,
+    notify: Notify,
+}
+
+#[derive(Debug)]
+pub struct EventQueue {
+    partitions: Vec<Partition>,
+
+    /// The maximal number of events in every partition
+    capacity: usize,
+    seq: AtomicU64,
+
+    /// The time of the latest popped event
+    now: AtomicU64,
+}
+
+impl EventQueue {
+    /// Creates the queue of `partitions` partitions with the capacity each.
+    pub fn new(partitions: usize, capacity: usize) -> Self {
+        EventQueue {
+            partitions: (0..partitions).map(|_| Partition::default()).collect(),
+            capacity,
+            seq: AtomicU64::new(0),
+            now: AtomicU64::new(0),
+        }
+    }
+
+    pub fn partitions(&self) -> usize {
+        self.partitions.len()
+    }
+
+    /// The partition index of the neuron. Events of the same neuron always go
+    /// to the same partition.
+    pub fn partition_of(&self, dst_id: &str) -> usize {
+        let hash = dst_id.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        (hash % self.partitions.len() as u64) as usize
+    }
+
+    /// Returns the event back when the partition of destination neuron is full.
+    pub fn push(
+        &self,
+        time: u64,
+        dst_id: &str,
+        dst_synapse_idx: usize,
+        signal: Signal,
+    ) -> Result<(), SignalEvent> {
+        let event = SignalEvent {
+            time,
+            seq: self.seq.fetch_add(1, Ordering::Relaxed),
+            dst_id: dst_id.to_string(),
+            dst_synapse_idx,
+            signal,
+        };
+        let partition = &self.partitions[self.partition_of(dst_id)];
+        {
+            let mut heap = partition.heap.lock().unwrap();
+            if heap.len() >= self.capacity {
+                return Err(event);
+            }
+            heap.push(Reverse(event));
+        }
+        partition.notify.notify_one();
+        Ok(())
+    }
+
+    /// The earliest event of the partition, if any.
+    pub fn pop(&self, partition: usize) -> Option<SignalEvent> {
+        let event = self.partitions[partition]
+            .heap
+            .lock()
+            .unwrap()
+            .pop()
+            .map(|Reverse(event)| event);
+        if let Some(event) = event.as_ref() {
+            self.now.fetch_max(event.time, Ordering::Relaxed);
+        }
+        event
+    }
+
+    /// The time of the latest processed event. Signals from input ports start at this time.
+    pub fn now(&self) -> u64 {
+        self.now.load(Ordering::Relaxed)
+    }
+
+    /// Wait for the earliest event of the partition.
+    pub async fn next(&self, partition: usize) -> SignalEvent {
+        loop {
+            if let Some(event) = self.pop(partition) {
+                return event;
+            }
+            self.partitions[partition].notify.notified().await;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.partitions
+            .iter()
+            .map(|partition| partition.heap.lock().unwrap().len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn events_should_be_ordered_by_time_then_by_arrival() {
+        let queue = EventQueue::new(1, 8);
+        assert!(queue.push(2, "M0Z0", 0, 1).is_ok());
+        assert!(queue.push(1, "M0Z1", 0, 2).is_ok());
+        assert!(queue.push(1, "M0Z2", 0, 3).is_ok());
+
+        let order: Vec<Signal> = std::iter::from_fn(|| queue.pop(0))
+            .map(|event| event.signal)
+            .collect();
+        assert_eq!(order, vec![2, 3, 1]);
+        assert!(queue.is_empty());
+        assert_eq!(queue.now(), 2);
+    }
+
+    #[test]
+    fn events_of_neuron_should_go_into_its_partition() {
+        let queue = EventQueue::new(3, 8);
+        let partition = queue.partition_of("M0Z1");
+        assert!(queue.push(1, "M0Z1", 0, 5).is_ok());
+        assert!(queue.push(0, "M0Z1", 1, 7).is_ok());
+
+        assert_eq!(queue.partitions(), 3);
+        for other in (0..3).filter(|other| *other != partition) {
+            assert!(queue.pop(other).is_none());
+        }
+        assert_eq!(queue.pop(partition).unwrap().signal, 7);
+        assert_eq!(queue.pop(partition).unwrap().signal, 5);
+    }
+
+    #[test]
+    fn full_partition_should_reject_events() {
+        let queue = EventQueue::new(1, 1);
+
+        assert!(queue.push(0, "M0Z0", 0, 1).is_ok());
+        assert_eq!(queue.push(0, "M0Z1", 0, 2).unwrap_err().signal, 2);
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn next_should_wait_for_pushed_event() {
+        let queue = Arc::new(EventQueue::new(1, 8));
+        let queue_cloned = queue.clone();
+        let waiter = tokio::spawn(async move { queue_cloned.next(0).await });
+
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(queue.push(0, "M0Z0", 1, 5).is_ok());
+
+        let event = waiter.await.unwrap();
+        assert_eq!((event.dst_synapse_idx, event.signal), (1, 5));
+    }
+}
@@ -0,0 +1,348 @@
+//! Feed-forward topology helper: the network is described as a sequence of layers
+//! (populations of neurons with the same parameters) and connections between
+//! consecutive layers. Input ports form the layer before the first one and every
+//! neuron of the last layer gets its own output port.
+
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::input_cfg::InputCfg;
+use crate::rnn::common::network_cfg::{LabelMap, LinkCfg, NetworkCfg, NeuronCfg, NeuronKind};
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::signal::Weight;
+
+/// The population of neurons with the same parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Layer {
+    /// The number of neurons
+    pub width: usize,
+
+    pub bias: Weight,
+
+    /// The config of every dendrite of layer's neurons
+    pub input_cfg: InputCfg,
+}
+
+/// The way neurons of the layer are connected to the previous layer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConnectionStrategy {
+    /// Every neuron receives signals from all neurons of the previous layer.
+    Dense,
+
+    /// Every neuron receives signals from `fan_in` neurons of the previous layer
+    /// taken in round-robin order starting from the neuron with the same index.
+    Sparse { fan_in: usize },
+
+    /// The previous layer is the grid of `columns` neurons per row (e.g. pixels of
+    /// the image in row-major order). Every neuron receives signals from the square
+    /// `kernel` x `kernel` field, fields are placed with `stride` and numbered
+    /// in row-major order. Neurons after the last field start with the first one
+    /// again, so the layer may hold several maps of fields.
+    ReceptiveField {
+        columns: usize,
+        kernel: usize,
+        stride: usize,
+    },
+}
+
+impl ConnectionStrategy {
+    /// Indexes of previous layer's neurons connected to the neuron with index `idx`.
+    fn sources(&self, idx: usize, prev_width: usize) -> Vec<usize> {
+        match self {
+            ConnectionStrategy::Dense => (0..prev_width).collect(),
+            ConnectionStrategy::Sparse { fan_in } => (0..*fan_in.min(&prev_width))
+                .map(|shift| (idx + shift) % prev_width)
+                .collect(),
+            ConnectionStrategy::ReceptiveField {
+                columns,
+                kernel,
+                stride,
+            } => {
+                let Some((field_columns, field_rows)) = self.field_grid(prev_width) else {
+                    return vec![];
+                };
+                let (columns, kernel, stride) = (*columns, *kernel, *stride);
+                let field = idx % (field_columns * field_rows);
+                let (top, left) = (
+                    field / field_columns * stride,
+                    field % field_columns * stride,
+                );
+                (0..kernel)
+                    .flat_map(|dy| (0..kernel).map(move |dx| (top + dy) * columns + left + dx))
+                    .collect()
+            }
+        }
+    }
+
+    /// Columns and rows of receptive fields over the previous layer of `prev_width`
+    /// neurons. None for other strategies and grids which don't fit the kernel.
+    pub fn field_grid(&self, prev_width: usize) -> Option<(usize, usize)> {
+        let ConnectionStrategy::ReceptiveField {
+            columns,
+            kernel,
+            stride,
+        } = *self
+        else {
+            return None;
+        };
+        if columns == 0 || kernel == 0 || stride == 0 || prev_width % columns != 0 {
+            return None;
+        }
+        let rows = prev_width / columns;
+        if kernel > columns || kernel > rows {
+            return None;
+        }
+        Some((
+            (columns - kernel) / stride + 1,
+            (rows - kernel) / stride + 1,
+        ))
+    }
+}
+
+/// Generates NetworkCfg of feed-forward network layer by layer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayeredNetworkBuilder {
+    inputs: usize,
+    layers: Vec<(Layer, ConnectionStrategy)>,
+}
+
+impl LayeredNetworkBuilder {
+    /// Starts description of the network with the number of input ports.
+    pub fn new(inputs: usize) -> Self {
+        LayeredNetworkBuilder {
+            inputs,
+            layers: vec![],
+        }
+    }
+
+    /// Append the layer connected to the previous one (or input ports) by strategy.
+    pub fn layer(mut self, layer: Layer, connection: ConnectionStrategy) -> Self {
+        self.layers.push((layer, connection));
+        self
+    }
+
+    /// Generate config. Neuron ids are numbered layer by layer.
+    pub fn build(&self) -> Result<NetworkCfg, Box<dyn Error>> {
+        if self.inputs == 0
+            || self.layers.is_empty()
+            || self.layers.iter().any(|(layer, _)| layer.width == 0)
+            || self.layers.iter().any(|(_, connection)| {
+                matches!(connection, ConnectionStrategy::Sparse { fan_in: 0 })
+            })
+        {
+            return Err(Box::new(RnnError::NotSupportedArgValue));
+        }
+
+        let mut neurons = vec![];
+        let mut links = vec![];
+        let mut prev_ids: Vec<String> = vec![];
+        for (layer, connection) in self.layers.iter() {
+            let prev_width = if prev_ids.is_empty() {
+                self.inputs
+            } else {
+                prev_ids.len()
+            };
+            if matches!(connection, ConnectionStrategy::ReceptiveField { .. })
+                && connection.field_grid(prev_width).is_none()
+            {
+                return Err(Box::new(RnnError::NotSupportedArgValue));
+            }
+
+            let mut ids = vec![];
+            for idx in 0..layer.width {
+                let id = format!("M0Z{}", neurons.len());
+                let sources = connection.sources(idx, prev_width);
+                for (dst_synapse_idx, src) in sources.iter().enumerate() {
+                    links.push(match prev_ids.get(*src) {
+                        Some(src_id) => LinkCfg::Inner {
+                            src_id: src_id.clone(),
+                            dst_id: id.clone(),
+                            dst_synapse_idx,
+                        },
+                        None => LinkCfg::Input {
+                            input_port: *src,
+                            dst_id: id.clone(),
+                            dst_synapse_idx,
+                        },
+                    });
+                }
+                neurons.push(NeuronCfg {
+                    id: id.clone(),
+                    bias: layer.bias,
+                    input_configs: vec![layer.input_cfg.clone(); sources.len()],
+                    lineage: None,
+                    kind: NeuronKind::Excitatory,
+                });
+                ids.push(id);
+            }
+            prev_ids = ids;
+        }
+
+        for (output_port, src_id) in prev_ids.iter().enumerate() {
+            links.push(LinkCfg::Output {
+                src_id: src_id.clone(),
+                output_port,
+            });
+        }
+
+        let config = NetworkCfg {
+            inputs: self.inputs,
+            outputs: prev_ids.len(),
+            neurons,
+            links,
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::time;
+
+    use super::*;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+
+    fn layer_fixture(width: usize) -> Layer {
+        Layer {
+            width,
+            bias: 0,
+            input_cfg: InputCfg::new(3, 3, 1).unwrap(),
+        }
+    }
+
+    #[test]
+    fn should_build_dense_layers() {
+        let config = LayeredNetworkBuilder::new(2)
+            .layer(layer_fixture(3), ConnectionStrategy::Dense)
+            .layer(layer_fixture(2), ConnectionStrategy::Dense)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.inputs, 2);
+        assert_eq!(config.outputs, 2);
+        assert_eq!(config.neurons.len(), 5);
+        assert_eq!(config.neurons[0].input_configs.len(), 2);
+        assert_eq!(config.neurons[4].input_configs.len(), 3);
+        // 2 * 3 input links, 3 * 2 inner links and 2 output links
+        assert_eq!(config.links.len(), 14);
+        assert_eq!(
+            config.links[6],
+            LinkCfg::Inner {
+                src_id: String::from("M0Z0"),
+                dst_id: String::from("M0Z3"),
+                dst_synapse_idx: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn should_build_sparse_layers_in_round_robin_order() {
+        let config = LayeredNetworkBuilder::new(3)
+            .layer(layer_fixture(3), ConnectionStrategy::Sparse { fan_in: 2 })
+            .build()
+            .unwrap();
+
+        assert_eq!(config.neurons[2].input_configs.len(), 2);
+        assert_eq!(
+            config.links[4..6],
+            [
+                LinkCfg::Input {
+                    input_port: 2,
+                    dst_id: String::from("M0Z2"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Input {
+                    input_port: 0,
+                    dst_id: String::from("M0Z2"),
+                    dst_synapse_idx: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_not_build_empty_layers() {
+        assert!(LayeredNetworkBuilder::new(2).build().is_err());
+        assert!(LayeredNetworkBuilder::new(2)
+            .layer(layer_fixture(0), ConnectionStrategy::Dense)
+            .build()
+            .is_err());
+        assert!(LayeredNetworkBuilder::new(2)
+            .layer(layer_fixture(1), ConnectionStrategy::Sparse { fan_in: 0 })
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn should_build_receptive_fields_over_image() {
+        let field = ConnectionStrategy::ReceptiveField {
+            columns: 4,
+            kernel: 2,
+            stride: 2,
+        };
+        let config = LayeredNetworkBuilder::new(16)
+            .layer(layer_fixture(8), field.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(field.field_grid(16), Some((2, 2)));
+        assert_eq!(config.neurons[3].input_configs.len(), 4);
+        let sources = |dst: &str| {
+            config
+                .links
+                .iter()
+                .filter_map(|link| match link {
+                    LinkCfg::Input {
+                        input_port, dst_id, ..
+                    } if dst_id == dst => Some(*input_port),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sources("M0Z3"), vec![10, 11, 14, 15]);
+        // the second map starts from the first field
+        assert_eq!(sources("M0Z4"), vec![0, 1, 4, 5]);
+
+        assert!(LayeredNetworkBuilder::new(16)
+            .layer(
+                layer_fixture(4),
+                ConnectionStrategy::ReceptiveField {
+                    columns: 3,
+                    kernel: 2,
+                    stride: 1,
+                }
+            )
+            .build()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn built_config_should_be_installed_into_network() {
+        let config = LayeredNetworkBuilder::new(1)
+            .layer(layer_fixture(1), ConnectionStrategy::Dense)
+            .layer(layer_fixture(1), ConnectionStrategy::Dense)
+            .build()
+            .unwrap();
+        let net = Arc::new(new_network_fixture());
+
+        assert!(net.restore_config(net.clone(), &config).await.is_ok());
+        assert_eq!(net.len().await, 2);
+
+        let mut receiver = net.subscribe_output(0).await.unwrap();
+        assert!(net.input(2, 0).await.is_ok());
+        let signal = time::timeout(Duration::from_millis(100), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(signal > 0);
+    }
+}
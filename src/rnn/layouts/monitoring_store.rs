@@ -0,0 +1,122 @@
+//! The network's store of monitoring statuses. Every status is kept in the bounded
+//! (or unbounded) in-memory queue drained by Network::pop_monitoring_store() and
+//! sent into the broadcast channel read by Network::monitoring_stream() subscribers.
+
+use std::collections::VecDeque;
+
+use tokio::sync::{broadcast, RwLock};
+
+use crate::rnn::common::runtime_cfg::{MonitoringStoreCfg, StoreRetention};
+use crate::rnn::common::status::Status;
+
+#[derive(Debug)]
+pub struct MonitoringStore {
+    cfg: MonitoringStoreCfg,
+    statuses: RwLock<VecDeque<Status>>,
+    stream_sender: broadcast::Sender<Status>,
+}
+
+impl MonitoringStore {
+    pub fn new(cfg: MonitoringStoreCfg) -> Self {
+        let (stream_sender, _stream_receiver) = broadcast::channel(cfg.stream_capacity);
+        MonitoringStore {
+            cfg,
+            statuses: RwLock::new(VecDeque::new()),
+            stream_sender,
+        }
+    }
+
+    /// Keep the status according to the retention policy and send it to subscribers.
+    pub async fn push(&self, status: Status) {
+        let _no_subscribers = self.stream_sender.send(status.clone());
+
+        let mut w_statuses = self.statuses.write().await;
+        match self.cfg.capacity {
+            Some(capacity) if w_statuses.len() >= capacity => match self.cfg.retention {
+                StoreRetention::KeepLatest if capacity > 0 => {
+                    w_statuses.pop_front();
+                    w_statuses.push_back(status);
+                }
+                _ => {}
+            },
+            _ => w_statuses.push_back(status),
+        }
+    }
+
+    /// Take all kept statuses in order of arrival.
+    pub async fn pop_all(&self) -> Vec<Status> {
+        self.statuses.write().await.drain(..).collect()
+    }
+
+    pub async fn clear(&self) {
+        self.statuses.write().await.clear();
+    }
+
+    /// Receiver of statuses pushed after subscription.
+    pub fn subscribe(&self) -> broadcast::Receiver<Status> {
+        self.stream_sender.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::rnn::common::status::PortInfo;
+
+    fn status_fixture(hit_count: u64) -> Status {
+        Status::Port(PortInfo {
+            timestamp: Utc::now(),
+            id: String::from("M0I0"),
+            hit_count,
+            recent_signal: 1,
+        })
+    }
+
+    async fn hit_counts(store: &MonitoringStore) -> Vec<u64> {
+        store
+            .pop_all()
+            .await
+            .iter()
+            .map(|status| match status {
+                Status::Port(info) => info.hit_count,
+                _ => panic!("unexpected status"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn bounded_store_should_follow_retention_policy() {
+        for (retention, expected) in [
+            (StoreRetention::KeepLatest, vec![2, 3]),
+            (StoreRetention::KeepEarliest, vec![0, 1]),
+        ] {
+            let store = MonitoringStore::new(MonitoringStoreCfg {
+                capacity: Some(2),
+                retention,
+                ..MonitoringStoreCfg::default()
+            });
+            for hit_count in 0..4 {
+                store.push(status_fixture(hit_count)).await;
+            }
+
+            assert_eq!(hit_counts(&store).await, expected);
+            assert!(store.pop_all().await.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn subscribers_should_receive_statuses_not_kept_in_store() {
+        let store = MonitoringStore::new(MonitoringStoreCfg {
+            capacity: Some(0),
+            ..MonitoringStoreCfg::default()
+        });
+        let mut receiver = store.subscribe();
+
+        store.push(status_fixture(7)).await;
+
+        assert!(matches!(receiver.recv().await, Ok(Status::Port(info)) if info.hit_count == 7));
+        assert!(store.pop_all().await.is_empty());
+    }
+}
@@ -1,34 +1,60 @@
 use core::fmt;
 use std::collections::btree_map::Entry;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::error::Error;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{self as std_sync, Arc};
 use std::time::Duration;
 
-use chrono::Utc;
-use regex::Regex;
+use chrono::{DateTime, Utc};
 use tokio::sync::broadcast::{self, Receiver};
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{mpsc, Notify, RwLock};
+use tokio::task::JoinHandle;
 use tokio::time;
-use tokio_util::sync::CancellationToken;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt, StreamMap};
+use tokio_util::sync::{CancellationToken, DropGuard};
 use tokio_util::task::TaskTracker;
+use tracing::{debug, info, warn};
 
+use crate::rnn::analysis::doctor::{self, DoctorReport, Finding, Severity};
+use crate::rnn::analysis::probe::ProbeSample;
+use crate::rnn::analysis::structure::{self, StructureReport};
+use crate::rnn::common::clock::{Clock, SystemClock};
 use crate::rnn::common::command::NeuronCommand;
 use crate::rnn::common::input_cfg::InputCfg;
-use crate::rnn::common::network_cfg::NeuronCfg;
+use crate::rnn::common::network_cfg::{
+    LabelMap, LinkCfg, NetworkCfg, NetworkLineage, NeuronCfg, NeuronKind,
+};
+use crate::rnn::common::rng::RngProvider;
 use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::runtime_cfg::{
+    BackpressurePolicy, RoutingBackend, RuntimeCfg, SimulationMode,
+};
 use crate::rnn::common::signal::{Signal, Weight};
+use crate::rnn::common::snapshot::{NetworkSnapshot, SnapshotDelta};
 use crate::rnn::common::spec_type::SpecificationType;
-use crate::rnn::common::status::{PortInfo, Status};
-use crate::rnn::common::utils::gen_id_by_spec_type;
+use crate::rnn::common::status::{
+    ExportFormat, PortInfo, RemovalInfo, Status, StatusMask, STATUS_CSV_HEADER,
+};
+use crate::rnn::common::utils::{gen_id_by_spec_type, get_component_id_fraction};
 use crate::rnn::neural::neuron::Neuron;
 
+use super::construction_log::{ConstructionLog, EventRecord, NetworkEvent};
+use super::event_queue::EventQueue;
+use super::monitoring_store::MonitoringStore;
+use super::neuron_group::{GroupConnection, NeuronGroup};
+use super::routing_table::{Route, RoutingTable};
+use super::sharded_executor::{ShardItem, ShardedExecutor};
 use super::signal_handler::SignalHandler;
 
 static ID_COUNTER: AtomicUsize = AtomicUsize::new(0_usize);
-static CHANNEL_CAPACITY: usize = 5;
 static GRACEFUL_SHUTDOWN_PERIOD: u64 = 20;
+static COMMAND_REPLY_TIMEOUT: u64 = 100;
+static STEP_SETTLE_TIMEOUT: u64 = 1000;
 
 /// The network tracing mode
 #[derive(Debug, Clone, PartialEq)]
@@ -37,14 +63,89 @@ pub enum MonitoringMode {
     None,
 
     /// Enable monitoring mode and store monitoring data from neurons
-    /// into self.monitoring_ch.store vector
+    /// into self.monitoring_ch.store (see Network::pop_monitoring_store()
+    /// and Network::monitoring_stream())
     Monitoring,
 }
 
+/// Which statuses are collected in MonitoringMode::Monitoring.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitoringFilter {
+    /// Only neurons with these ids send statuses. None lets all of them.
+    /// Port statuses are filtered by the mask only.
+    pub ids: Option<BTreeSet<String>>,
+
+    /// Every neuron sends only every n-th of its statuses. Zero is the same as one.
+    pub sample_every: u64,
+
+    /// Kinds of collected statuses
+    pub mask: StatusMask,
+}
+
+impl MonitoringFilter {
+    /// The status of the kind sent by the component passes the ids and the mask.
+    pub fn accepts(&self, id: &str, kind: StatusMask) -> bool {
+        self.mask.contains(kind)
+            && (kind == StatusMask::PORT || self.ids.as_ref().is_none_or(|ids| ids.contains(id)))
+    }
+}
+
+impl Default for MonitoringFilter {
+    fn default() -> Self {
+        MonitoringFilter {
+            ids: None,
+            sample_every: 1,
+            mask: StatusMask::ALL,
+        }
+    }
+}
+
+/// The number of live tasks grouped by their role.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskReport {
+    /// Neurons' loops which handle network commands
+    pub command_loops: usize,
+
+    /// Neurons' mailbox readers (RoutingBackend::Mailbox)
+    pub mailbox_readers: usize,
+
+    /// Network's workers of the event queue (RoutingBackend::EventDriven)
+    pub event_workers: usize,
+
+    /// Network's workers of shards (RoutingBackend::Sharded)
+    pub shard_workers: usize,
+
+    /// Tasks which listen neurons' synapses (RoutingBackend::Broadcast and input ports)
+    pub synapse_listeners: usize,
+
+    /// Tasks which pump signals into network's output ports
+    pub output_port_pumps: usize,
+
+    /// Tasks which save monitoring data
+    pub monitoring: usize,
+}
+
+impl std::ops::Add for TaskReport {
+    type Output = TaskReport;
+
+    fn add(self, other: TaskReport) -> TaskReport {
+        TaskReport {
+            command_loops: self.command_loops + other.command_loops,
+            mailbox_readers: self.mailbox_readers + other.mailbox_readers,
+            event_workers: self.event_workers + other.event_workers,
+            shard_workers: self.shard_workers + other.shard_workers,
+            synapse_listeners: self.synapse_listeners + other.synapse_listeners,
+            output_port_pumps: self.output_port_pumps + other.output_port_pumps,
+            monitoring: self.monitoring + other.monitoring,
+        }
+    }
+}
+
 /// Networks mode set like as monitoring mode.
 #[derive(Debug)]
 struct Modes {
     monitoring_mode: MonitoringMode,
+    monitoring_filter: MonitoringFilter,
 }
 
 /// Network spreads command via command channel to all neurons.
@@ -53,11 +154,93 @@ struct CommandsCh {
     sender: Arc<broadcast::Sender<NeuronCommand>>,
 }
 
+/// Statuses lost because the monitoring channel was full (see MonitoringOverflow).
+#[derive(Debug, Default)]
+pub struct MonitoringCounters {
+    dropped: AtomicU64,
+    coalesced: AtomicU64,
+}
+
+impl MonitoringCounters {
+    pub fn count_dropped(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn count_coalesced(&self) {
+        self.coalesced.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn get_coalesced(&self) -> u64 {
+        self.coalesced.load(Ordering::Relaxed)
+    }
+}
+
+/// Signals delivered to neurons but not processed yet (SimulationMode::Stepped).
+/// Network::step() waits until the counter settles to zero.
+#[derive(Debug, Default)]
+pub struct InFlight {
+    count: AtomicUsize,
+    settled: Notify,
+}
+
+impl InFlight {
+    pub fn add(&self, count: usize) {
+        self.count.fetch_add(count, Ordering::AcqRel);
+    }
+
+    /// Waiters of settled() are woken when the counter drops to zero.
+    pub fn settle(&self, count: usize) {
+        if count > 0 && self.count.fetch_sub(count, Ordering::AcqRel) == count {
+            self.settled.notify_waiters();
+        }
+    }
+
+    pub fn get(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// Wait until every delivered signal is processed.
+    pub async fn settled(&self) {
+        loop {
+            let notified = self.settled.notified();
+            tokio::pin!(notified);
+            // register before the check, so the notification can't be missed
+            notified.as_mut().enable();
+            if self.get() == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Signals of the routing table lost because the mailbox of destination neuron,
+/// its shard queue or event queue partition was full (see BackpressurePolicy).
+#[derive(Debug, Default)]
+pub struct RoutingCounters {
+    dropped: AtomicU64,
+}
+
+impl RoutingCounters {
+    pub fn count_dropped(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
 /// Neurons and ports are sending status information to Network when MonitoringMode is enabled
 #[derive(Debug)]
 struct MonitoringCh {
     sender: Arc<mpsc::Sender<Status>>,
-    store: Arc<RwLock<Vec<Status>>>,
+    store: Arc<MonitoringStore>,
+    counters: Arc<MonitoringCounters>,
 }
 
 /// The port proprties
@@ -66,11 +249,32 @@ struct PortCore {
     /// Port id
     id: String,
 
+    /// Ids of neurons connected to the port. The input port may feed synapses
+    /// of several neurons, the output port is driven by the single one.
+    parties: Vec<String>,
+
     /// Signal hits counter
     signal_hits: u64,
 
     /// Sender or receiver handler dependent from port kind (Input or Output)
     signal_handler: SignalHandler,
+
+    /// The task which pumps signals of the output port into monitoring
+    pump: Option<JoinHandle<()>>,
+}
+
+impl PortCore {
+    /// Stop the task pumping signals of the output port, if any.
+    fn stop_pump(&mut self) {
+        if let Some(pump) = self.pump.take() {
+            pump.abort();
+        }
+    }
+
+    /// The id of neuron driving the output port.
+    fn get_driver(&self) -> String {
+        self.parties.first().cloned().unwrap_or_default()
+    }
 }
 
 /// Network is a high level container to other containers (neurons)
@@ -79,8 +283,11 @@ pub struct Network {
     /// The network id
     id: String,
 
+    /// The network runtime configuration
+    runtime_cfg: RuntimeCfg,
+
     /// Inner neurons
-    neurons: RwLock<BTreeMap<String, Arc<Neuron>>>,
+    neurons: Arc<RwLock<BTreeMap<String, Arc<Neuron>>>>,
 
     /// Network's modes set
     modes: Arc<RwLock<Modes>>,
@@ -91,6 +298,14 @@ pub struct Network {
     /// Network's output ports
     output_interface: Arc<RwLock<BTreeMap<usize, Arc<RwLock<PortCore>>>>>,
 
+    /// Routes between neurons' axons and mailboxes. Used by RoutingBackend::Mailbox.
+    /// It is read by neurons on every fire while their core is locked,
+    /// so it is protected by the std lock which never held across await points.
+    routing_table: Arc<std_sync::RwLock<RoutingTable>>,
+
+    /// Signals dropped by routes of the routing table.
+    routing_counters: Arc<RoutingCounters>,
+
     /// The command channel stuff. Network sends command to all inner neurons
     /// through broadcast channel.
     commands_ch: CommandsCh,
@@ -99,41 +314,185 @@ pub struct Network {
     /// the network when enabled monitoring mode
     monitoring_ch: MonitoringCh,
 
+    /// Append-only log of structural mutations (create/remove/connect/configure).
+    construction_log: RwLock<ConstructionLog>,
+
+    /// Named snapshots of the network's topology and parameters.
+    checkpoints: RwLock<BTreeMap<String, NetworkCfg>>,
+
+    /// The origin of the network's config. It is passed through get_config().
+    lineage: RwLock<Option<NetworkLineage>>,
+
+    /// Assignment of dataset labels to output ports. It is passed through get_config().
+    labels: RwLock<LabelMap>,
+
+    /// Neuron ids by group names.
+    groups: RwLock<BTreeMap<String, Vec<String>>>,
+
     /// all receivers are handling incoming signals and messages in separated tasks.
     /// These tasks are tracked by receivers_tracker.
     receivers_tracker: TaskTracker,
+
+    /// Tasks which pump signals from neurons' axons into output ports.
+    output_ports_tracker: TaskTracker,
+
+    /// Signals delivered to neurons but not processed yet (SimulationMode::Stepped).
+    in_flight: Arc<InFlight>,
+
+    /// Input signals (port, signal) waiting for the next step (SimulationMode::Stepped).
+    queued_inputs: RwLock<Vec<(usize, Signal)>>,
+
+    /// Notified by synapses after they take a signal, input ports wait on it
+    /// for free space (BackpressurePolicy::Block).
+    consumed: Arc<Notify>,
+
+    /// The source of timestamps shared with neurons.
+    clock: Arc<dyn Clock>,
+
+    /// The root seed of random generators. It is passed through get_config().
+    seed: RwLock<Option<u64>>,
+
+    /// Used when the seed is not set.
+    entropy_rng: RngProvider,
+
+    /// Signals waiting for delivery (RoutingBackend::EventDriven).
+    event_queue: Option<Arc<EventQueue>>,
+
+    /// Workers which deliver signals of the event queue.
+    event_workers_tracker: TaskTracker,
+
+    /// Shards of neurons (RoutingBackend::Sharded).
+    sharded_executor: Option<Arc<ShardedExecutor>>,
+
+    /// Workers which deliver signals of shards.
+    shard_workers_tracker: TaskTracker,
+
+    /// Stops event and shard workers when the network is dropped.
+    _workers_guard: DropGuard,
     cancel_token: CancellationToken,
 }
 
 impl Network {
     pub fn new() -> Result<Network, Box<dyn Error>> {
-        let (monitoring_sender, monitoring_receiver) = mpsc::channel(CHANNEL_CAPACITY);
-        let (commands_sender, _commands_receiver) = broadcast::channel(CHANNEL_CAPACITY);
+        Self::with_runtime_cfg(RuntimeCfg::default())
+    }
+
+    /// Creates network with specified runtime configuration.
+    pub fn with_runtime_cfg(runtime_cfg: RuntimeCfg) -> Result<Network, Box<dyn Error>> {
+        Self::with_clock(runtime_cfg, Arc::new(SystemClock))
+    }
+
+    /// Creates network which takes timestamps of statuses and log records from the clock.
+    pub fn with_clock(
+        runtime_cfg: RuntimeCfg,
+        clock: Arc<dyn Clock>,
+    ) -> Result<Network, Box<dyn Error>> {
+        if runtime_cfg.mailbox_capacity == 0
+            || !runtime_cfg.channels.is_valid()
+            || runtime_cfg.monitoring_store.stream_capacity == 0
+            || (runtime_cfg.routing == RoutingBackend::EventDriven
+                && runtime_cfg.event_workers == 0)
+            || (runtime_cfg.routing == RoutingBackend::Sharded && runtime_cfg.shards == 0)
+            || (runtime_cfg.simulation == SimulationMode::Stepped
+                && runtime_cfg.routing == RoutingBackend::Broadcast)
+        {
+            return Err(Box::new(RnnError::NotSupportedArgValue));
+        }
+
+        let (monitoring_sender, monitoring_receiver) =
+            mpsc::channel(runtime_cfg.channels.monitoring);
+        let (commands_sender, _commands_receiver) =
+            broadcast::channel(runtime_cfg.channels.commands);
+        let monitoring_store = Arc::new(MonitoringStore::new(runtime_cfg.monitoring_store.clone()));
+        let workers_token = CancellationToken::new();
+        let event_queue = (runtime_cfg.routing == RoutingBackend::EventDriven).then(|| {
+            Arc::new(EventQueue::new(
+                runtime_cfg.event_workers,
+                runtime_cfg.mailbox_capacity,
+            ))
+        });
+        let (sharded_executor, shard_receivers) = if runtime_cfg.routing == RoutingBackend::Sharded
+        {
+            let (executor, receivers) =
+                ShardedExecutor::new(runtime_cfg.shards, runtime_cfg.mailbox_capacity);
+            (Some(Arc::new(executor)), receivers)
+        } else {
+            (None, vec![])
+        };
 
         let net = gen_id_by_spec_type(
             "",
-            unsafe { ID_COUNTER.fetch_add(1, Ordering::Relaxed) },
+            ID_COUNTER.fetch_add(1, Ordering::Relaxed),
             &SpecificationType::Network,
         )
         .map(|id| Network {
             id,
-            neurons: RwLock::new(BTreeMap::new()),
+            runtime_cfg,
+            neurons: Arc::new(RwLock::new(BTreeMap::new())),
             modes: Arc::new(RwLock::new(Modes {
                 monitoring_mode: MonitoringMode::None,
+                monitoring_filter: MonitoringFilter::default(),
             })),
             input_interface: Arc::new(RwLock::new(BTreeMap::new())),
             output_interface: Arc::new(RwLock::new(BTreeMap::new())),
+            routing_table: Arc::new(std_sync::RwLock::new(RoutingTable::default())),
+            routing_counters: Arc::new(RoutingCounters::default()),
             commands_ch: CommandsCh {
                 sender: Arc::new(commands_sender),
             },
             monitoring_ch: MonitoringCh {
                 sender: Arc::new(monitoring_sender),
-                store: Arc::new(RwLock::new(vec![])),
+                store: monitoring_store,
+                counters: Arc::new(MonitoringCounters::default()),
             },
+            construction_log: RwLock::new(ConstructionLog::default()),
+            checkpoints: RwLock::new(BTreeMap::new()),
+            lineage: RwLock::new(None),
+            labels: RwLock::new(LabelMap::default()),
+            groups: RwLock::new(BTreeMap::new()),
             receivers_tracker: TaskTracker::new(),
+            output_ports_tracker: TaskTracker::new(),
+            in_flight: Arc::new(InFlight::default()),
+            queued_inputs: RwLock::new(vec![]),
+            consumed: Arc::new(Notify::new()),
+            clock,
+            seed: RwLock::new(None),
+            entropy_rng: RngProvider::from_entropy(),
+            event_queue,
+            event_workers_tracker: TaskTracker::new(),
+            sharded_executor,
+            shard_workers_tracker: TaskTracker::new(),
+            _workers_guard: workers_token.clone().drop_guard(),
             cancel_token: CancellationToken::new(),
         })?;
 
+        if let Some(event_queue) = net.event_queue.as_ref() {
+            for partition in 0..event_queue.partitions() {
+                let event_queue = event_queue.clone();
+                let neurons = Arc::downgrade(&net.neurons);
+                let in_flight = net.get_in_flight();
+                let token = workers_token.clone();
+                net.event_workers_tracker.spawn(async move {
+                    tokio::select! {
+                        () = Self::event_worker(event_queue, partition, neurons, in_flight) => {}
+                        () = token.cancelled() => {}
+                    }
+                });
+            }
+        }
+
+        for shard_receiver in shard_receivers {
+            let neurons = Arc::downgrade(&net.neurons);
+            let in_flight = net.get_in_flight();
+            let token = workers_token.clone();
+            net.shard_workers_tracker.spawn(async move {
+                tokio::select! {
+                    () = Self::shard_worker(shard_receiver, neurons, in_flight) => {}
+                    () = token.cancelled() => {}
+                }
+            });
+        }
+
         let monitoring_store_cloned = net.monitoring_ch.store.clone();
         let cancel_token_cloned = net.cancel_token.clone();
 
@@ -141,9 +500,9 @@ impl Network {
             tokio::select! {
                 () = Self::monitoring_save_task(monitoring_store_cloned, monitoring_receiver) => {}
                 () = cancel_token_cloned.cancelled() => {
-                    println!("Waiting to shutdown....");
+                    info!("waiting to shutdown");
                     time::sleep(Duration::from_millis(GRACEFUL_SHUTDOWN_PERIOD)).await;
-                    println!("Cleanup complete.");
+                    info!("cleanup complete");
                 }
             }
         });
@@ -152,12 +511,11 @@ impl Network {
     }
 
     async fn monitoring_save_task(
-        monitoring_store: Arc<RwLock<Vec<Status>>>,
+        monitoring_store: Arc<MonitoringStore>,
         mut monitoring_receiver: mpsc::Receiver<Status>,
     ) {
         while let Some(neuron_state) = monitoring_receiver.recv().await {
-            let mut w_monitoring_store = monitoring_store.write().await;
-            w_monitoring_store.push(neuron_state); // TODO review possibility write into stream
+            monitoring_store.push(neuron_state).await;
         }
     }
 
@@ -170,13 +528,259 @@ impl Network {
             .send(NeuronCommand::SwitchMonitoringMode(mode));
     }
 
+    /// Clear runtime state of all neurons (see Neuron::reset()) keeping topology
+    /// and parameters, and clear the monitoring store. Neurons handle the command
+    /// asynchronously.
+    pub async fn reset(&self) {
+        let _send_command_result = self.commands_ch.sender.send(NeuronCommand::Reset);
+        self.monitoring_ch.store.clear().await;
+    }
+
+    /// Pause processing of signals by all neurons (see Neuron::pause()).
+    pub fn pause(&self) {
+        let _send_command_result = self.commands_ch.sender.send(NeuronCommand::Pause);
+    }
+
+    pub fn resume(&self) {
+        let _send_command_result = self.commands_ch.sender.send(NeuronCommand::Resume);
+    }
+
+    /// Pause processing of signals by the single neuron.
+    pub fn pause_neuron(&self, id: &str) {
+        self.command_neuron(id, NeuronCommand::Pause);
+    }
+
+    pub fn resume_neuron(&self, id: &str) {
+        self.command_neuron(id, NeuronCommand::Resume);
+    }
+
+    /// Send the command to the single neuron without disturbing the rest.
+    pub fn command_neuron(&self, id: &str, command: NeuronCommand) {
+        let _send_command_result = self
+            .commands_ch
+            .sender
+            .send(NeuronCommand::For(id.to_string(), Box::new(command)));
+    }
+
+    /// Change weight and/or capacity of the neuron's dendrite on the running network.
+    pub async fn update_synapse(
+        &self,
+        id: &str,
+        port: usize,
+        weight: Option<Weight>,
+        capacity: Option<Signal>,
+    ) {
+        self.command_neuron(
+            id,
+            NeuronCommand::UpdateSynapse {
+                port,
+                weight,
+                capacity,
+            },
+        );
+        self.log_event(NetworkEvent::SynapseUpdated {
+            id: id.to_string(),
+            port,
+            weight,
+            capacity,
+        })
+        .await;
+    }
+
+    pub async fn update_bias(&self, id: &str, bias: Weight) {
+        self.command_neuron(id, NeuronCommand::UpdateBias(bias));
+        self.log_event(NetworkEvent::BiasUpdated {
+            id: id.to_string(),
+            bias,
+        })
+        .await;
+    }
+
+    /// Ask the live neuron for its config.
+    pub async fn query_config(&self, id: &str) -> Result<NeuronCfg, Box<dyn Error>> {
+        let (reply, mut replies) = mpsc::channel(1);
+        self.command_neuron(id, NeuronCommand::GetConfig { reply });
+
+        time::timeout(Duration::from_millis(COMMAND_REPLY_TIMEOUT), replies.recv())
+            .await
+            .ok()
+            .flatten()
+            .ok_or_else(|| {
+                Box::new(RnnError::ExpectedDataNotPresent(id.to_string())) as Box<dyn Error>
+            })
+    }
+
+    /// Ask the live neuron for its current sample (see analysis::probe::Probe).
+    pub async fn query_sample(&self, id: &str) -> Result<ProbeSample, Box<dyn Error>> {
+        let (reply, mut replies) = mpsc::channel(1);
+        self.command_neuron(id, NeuronCommand::GetSample { reply });
+
+        time::timeout(Duration::from_millis(COMMAND_REPLY_TIMEOUT), replies.recv())
+            .await
+            .ok()
+            .flatten()
+            .ok_or_else(|| {
+                Box::new(RnnError::ExpectedDataNotPresent(id.to_string())) as Box<dyn Error>
+            })
+    }
+
+    /// Send the command and wait until neurons confirm it was applied.
+    /// Returns ids of confirmed neurons. Addressed command (NeuronCommand::For)
+    /// is confirmed by the single neuron, others by every neuron of the network.
+    pub async fn send_confirmed(
+        &self,
+        command: NeuronCommand,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let expected = match command {
+            NeuronCommand::For(..) => 1,
+            _ => self.len().await,
+        };
+        let (reply, mut replies) = mpsc::channel(expected.max(1));
+        let _send_command_result = self.commands_ch.sender.send(NeuronCommand::Confirmed {
+            command: Box::new(command),
+            reply,
+        });
+
+        let deadline = time::Instant::now() + Duration::from_millis(COMMAND_REPLY_TIMEOUT);
+        let mut confirmed = vec![];
+        while confirmed.len() < expected {
+            match time::timeout_at(deadline, replies.recv()).await {
+                Ok(Some(id)) => confirmed.push(id),
+                Ok(None) | Err(_) => break,
+            }
+        }
+        if confirmed.len() < expected {
+            return Err(Box::new(RnnError::ExpectedDataNotPresent(format!(
+                "{} of {expected} confirmations",
+                confirmed.len()
+            ))));
+        }
+        Ok(confirmed)
+    }
+
     pub async fn get_monitoring_mode(&self) -> MonitoringMode {
         self.modes.read().await.monitoring_mode.clone()
     }
 
+    /// Set the filter of statuses for all neurons including ones created later.
+    pub async fn set_monitoring_filter(&self, filter: MonitoringFilter) {
+        let mut w_state = self.modes.write().await;
+        w_state.monitoring_filter = filter.clone();
+        let _send_command_result = self
+            .commands_ch
+            .sender
+            .send(NeuronCommand::SetMonitoringFilter(filter));
+    }
+
+    pub async fn get_monitoring_filter(&self) -> MonitoringFilter {
+        self.modes.read().await.monitoring_filter.clone()
+    }
+
+    /// Collect statuses of the listed neurons only keeping other filter settings.
+    pub async fn monitor_only(&self, ids: &[&str]) {
+        let filter = MonitoringFilter {
+            ids: Some(ids.iter().map(|id| id.to_string()).collect()),
+            ..self.get_monitoring_filter().await
+        };
+        self.set_monitoring_filter(filter).await;
+    }
+
+    /// The network's own status passes the monitoring mode and filter.
+    async fn is_monitored(&self, id: &str, kind: StatusMask) -> bool {
+        Self::accepts_status(&self.modes, id, kind).await
+    }
+
+    async fn accepts_status(modes: &RwLock<Modes>, id: &str, kind: StatusMask) -> bool {
+        let r_state = modes.read().await;
+        r_state.monitoring_mode == MonitoringMode::Monitoring
+            && r_state.monitoring_filter.accepts(id, kind)
+    }
+
+    pub fn get_runtime_cfg(&self) -> RuntimeCfg {
+        self.runtime_cfg.clone()
+    }
+
+    /// The queue of signals between neurons. Present in RoutingBackend::EventDriven only.
+    pub fn get_event_queue(&self) -> Option<Arc<EventQueue>> {
+        self.event_queue.clone()
+    }
+
+    /// Deliver events of the queue's partition into synapses of neurons one by one
+    /// until the network is dropped.
+    async fn event_worker(
+        event_queue: Arc<EventQueue>,
+        partition: usize,
+        neurons: std_sync::Weak<RwLock<BTreeMap<String, Arc<Neuron>>>>,
+        in_flight: Option<Arc<InFlight>>,
+    ) {
+        loop {
+            let event = event_queue.next(partition).await;
+            let Some(neurons) = neurons.upgrade() else {
+                break;
+            };
+            let neuron = neurons.read().await.get(&event.dst_id).cloned();
+            drop(neurons);
+            if let Some(neuron) = neuron {
+                let _write_me_into_log = neuron.receive_event(&event).await;
+            }
+            if let Some(in_flight) = in_flight.as_ref() {
+                in_flight.settle(1);
+            }
+        }
+    }
+
+    /// RoutingBackend::Sharded only.
+    pub fn get_sharded_executor(&self) -> Option<Arc<ShardedExecutor>> {
+        self.sharded_executor.clone()
+    }
+
+    /// Deliver signals of the shard into synapses of its neurons one by one.
+    async fn shard_worker(
+        mut shard_receiver: mpsc::Receiver<ShardItem>,
+        neurons: std_sync::Weak<RwLock<BTreeMap<String, Arc<Neuron>>>>,
+        in_flight: Option<Arc<InFlight>>,
+    ) {
+        while let Some((dst_id, dst_synapse_idx, signal)) = shard_receiver.recv().await {
+            let Some(neurons) = neurons.upgrade() else {
+                break;
+            };
+            let neuron = neurons.read().await.get(&dst_id).cloned();
+            drop(neurons);
+            if let Some(neuron) = neuron {
+                let _write_me_into_log = neuron.receive_signal(signal, dst_synapse_idx).await;
+            }
+            if let Some(in_flight) = in_flight.as_ref() {
+                in_flight.settle(1);
+            }
+        }
+    }
+
+    /// SimulationMode::Stepped only.
+    pub fn get_in_flight(&self) -> Option<Arc<InFlight>> {
+        (self.runtime_cfg.simulation == SimulationMode::Stepped).then(|| self.in_flight.clone())
+    }
+
+    /// The notification of taken signals. Present with BackpressurePolicy::Block only.
+    pub fn get_consumed(&self) -> Option<Arc<Notify>> {
+        (self.runtime_cfg.backpressure == BackpressurePolicy::Block).then(|| self.consumed.clone())
+    }
+
+    pub fn get_clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+
+    pub fn get_routing_table(&self) -> Arc<std_sync::RwLock<RoutingTable>> {
+        self.routing_table.clone()
+    }
+
+    /// Counters of signals dropped by routes due to full mailboxes or queues.
+    pub fn get_routing_counters(&self) -> Arc<RoutingCounters> {
+        self.routing_counters.clone()
+    }
+
     pub async fn get_neuron(&self, id: &str) -> Option<Arc<Neuron>> {
         let r_neurons = self.neurons.read().await;
-        r_neurons.get(id).map(|neuron| Arc::clone(neuron))
+        r_neurons.get(id).map(Arc::clone)
     }
 
     pub fn get_commands_receiver(&self) -> broadcast::Receiver<NeuronCommand> {
@@ -187,14 +791,18 @@ impl Network {
         self.monitoring_ch.sender.downgrade()
     }
 
+    /// Counters of statuses dropped or coalesced due to the full monitoring channel.
+    pub fn get_monitoring_counters(&self) -> Arc<MonitoringCounters> {
+        self.monitoring_ch.counters.clone()
+    }
+
     pub async fn create_neuron(
         &self,
         network: Arc<Network>,
         bias: Weight,
         input_configs: Vec<InputCfg>,
+        kind: NeuronKind,
     ) -> Result<Arc<Neuron>, Box<dyn std::error::Error>> {
-        use std::collections::btree_map::Entry;
-
         let prefix = 'Z';
         let new_id = format!(
             "{}{prefix}{}",
@@ -214,42 +822,124 @@ impl Network {
         let neuron_config = NeuronCfg {
             id: new_id.clone(),
             bias,
-            input_configs,
+            input_configs: input_configs.clone(),
+            lineage: None,
+            kind,
         };
+        let neuron = self.insert_neuron(network, neuron_config).await?;
+        self.log_event(NetworkEvent::NeuronCreated {
+            id: new_id,
+            bias,
+            input_configs,
+            kind,
+        })
+        .await;
+        Ok(neuron)
+    }
+
+    async fn insert_neuron(
+        &self,
+        network: Arc<Network>,
+        neuron_config: NeuronCfg,
+    ) -> Result<Arc<Neuron>, Box<dyn Error>> {
+        use std::collections::btree_map::Entry;
+
         let mut w_neurons = self.neurons.write().await;
-        match w_neurons.entry(new_id.clone()) {
+        match w_neurons.entry(neuron_config.id.clone()) {
             Entry::Vacant(entry) => Ok(Arc::clone(
                 entry.insert(Neuron::build(Arc::clone(&network), neuron_config).await),
             )),
-            Entry::Occupied(_) => Err(Box::new(RnnError::NeuronAlreadyExists(new_id))),
+            Entry::Occupied(entry) => {
+                Err(Box::new(RnnError::NeuronAlreadyExists(entry.key().clone())))
+            }
         }
     }
 
+    /// Reconfigure dendrites of the neuron.
+    pub async fn configure_neuron(
+        &self,
+        id: &str,
+        input_configs: Vec<InputCfg>,
+    ) -> Result<(), Box<dyn Error>> {
+        let neuron = self
+            .get_neuron(id)
+            .await
+            .ok_or_else(|| Box::new(RnnError::NeuronNotFound(id.to_string())))?;
+        neuron.config(input_configs.clone()).await;
+        self.log_event(NetworkEvent::NeuronConfigured {
+            id: id.to_string(),
+            input_configs,
+        })
+        .await;
+        Ok(())
+    }
+
     pub async fn get_available_neuron_id(&self) -> usize {
         let r_neurons = self.neurons.read().await;
-        r_neurons.keys().last().map_or(0, |id| {
-            if id.is_empty() {
-                return 0;
-            }
+        // Keys are sorted as strings ("M0Z10" < "M0Z9"), so the last key
+        // is not always the highest id and all keys have to be checked.
+        r_neurons
+            .keys()
+            .filter_map(|id| get_component_id_fraction(id, &SpecificationType::Neuron).ok())
+            .max()
+            .map_or(0, |id_num| id_num + 1)
+    }
+
+    /// Remove the neuron with all its links: synapses connected to its axon are freed,
+    /// routes and network ports bound to the neuron are removed and its tasks are stopped.
+    /// The teardown is best-effort: failed disconnections are logged and the removal goes on.
+    pub async fn remove_neuron(&self, id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let neuron = self
+            .neurons
+            .write()
+            .await
+            .remove(id)
+            .ok_or_else(|| Box::new(RnnError::NeuronNotFound(id.to_string())))?;
 
-            let r_pattern = r"^M\d+Z(\d+)$";
+        // inbound links including input ports
+        let mut disconnected_count = neuron.get_connections().await.len();
 
-            let rex = Regex::new(&r_pattern).unwrap();
-            let captures = rex.captures(id).unwrap();
-            if &captures.len() < &2 {
-                return 0;
+        // outbound links
+        let parties: Vec<Arc<Neuron>> = self.neurons.read().await.values().cloned().collect();
+        for party in parties {
+            for (port, party_id) in party.get_connections().await {
+                if party_id == id {
+                    match party.disconnect(port).await {
+                        Ok(_) => disconnected_count += 1,
+                        Err(error) => warn!(
+                            id,
+                            party = %party.get_id(),
+                            port,
+                            %error,
+                            "synapse is not freed"
+                        ),
+                    }
+                }
             }
-            let id_num = captures[1].parse::<usize>().unwrap();
-            id_num + 1
-        })
-    }
+        }
+        self.routing_table.write().unwrap().remove_neuron(id);
 
-    pub async fn remove_neuron(&self, id: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let mut w_neurons = self.neurons.write().await;
-        match w_neurons.remove(id) {
-            Some(_) => Ok(()),
-            None => Err(Box::new(RnnError::NeuronNotFound(id.to_string()))),
+        Self::remove_bound_ports(&self.input_interface, id).await;
+        disconnected_count += Self::remove_bound_ports(&self.output_interface, id).await;
+
+        neuron.shutdown().await;
+        for ids in self.groups.write().await.values_mut() {
+            ids.retain(|group_id| group_id != id);
+        }
+        self.log_event(NetworkEvent::NeuronRemoved { id: id.to_string() })
+            .await;
+        debug!(id, disconnected_count, "neuron removed");
+        if self.is_monitored(id, StatusMask::REMOVAL).await {
+            self.monitoring_ch
+                .store
+                .push(Status::Removal(RemovalInfo {
+                    timestamp: self.clock.now(),
+                    id: id.to_string(),
+                    disconnected_count,
+                }))
+                .await;
         }
+        Ok(())
     }
 
     pub async fn has_neuron(&self, id: &str) -> bool {
@@ -261,6 +951,22 @@ impl Network {
         src_id: &str,
         dst_id: &str,
         dst_port: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        self.link_neurons(src_id, dst_id, dst_port).await?;
+        self.log_event(NetworkEvent::NeuronsConnected {
+            src_id: src_id.to_string(),
+            dst_id: dst_id.to_string(),
+            dst_synapse_idx: dst_port,
+        })
+        .await;
+        Ok(())
+    }
+
+    async fn link_neurons(
+        &self,
+        src_id: &str,
+        dst_id: &str,
+        dst_port: usize,
     ) -> Result<(), Box<dyn Error>> {
         let src_neuron = self.get_neuron(src_id).await;
         if src_neuron.is_none() {
@@ -274,82 +980,551 @@ impl Network {
 
         let src_neuron = src_neuron.unwrap();
         let dst_neuron = dst_neuron.unwrap();
-        src_neuron.link_to(dst_neuron, dst_port).await
-    }
-
-    pub async fn len(&self) -> usize {
-        self.neurons.read().await.len()
-    }
-
-    /// Send signal to port connected to synapse
-    pub async fn input(&self, signal: Signal, port: usize) -> Result<usize, Box<dyn Error>> {
-        if let Some(port_core) = self.input_interface.write().await.get_mut(&port) {
-            let mut w_port_core = port_core.write().await;
-            w_port_core.signal_hits += 1;
-            if let SignalHandler::Input(synapse) = &w_port_core.signal_handler {
-                let w_synapse = synapse.read().await;
-                let result = w_synapse
-                    .send(signal)
-                    .map_err(|error| Box::new(error) as Box<dyn Error>);
+        match self.runtime_cfg.routing {
+            RoutingBackend::Broadcast => src_neuron.link_to(dst_neuron.clone(), dst_port).await?,
+            RoutingBackend::Mailbox => {
+                src_neuron.check_closed_loop(&dst_neuron.get_id()).await?;
+                let mailbox = dst_neuron.get_mailbox().ok_or_else(|| {
+                    Box::new(RnnError::ExpectedDataNotPresent(dst_id.to_string()))
+                })?;
+                dst_neuron.attach(src_id, dst_port).await?;
+                self.routing_table.write().unwrap().add_route(
+                    src_id,
+                    Route {
+                        dst_id: dst_id.to_string(),
+                        dst_synapse_idx: dst_port,
+                        mailbox: Some(mailbox),
+                    },
+                );
+            }
+            RoutingBackend::EventDriven | RoutingBackend::Sharded => {
+                src_neuron.check_closed_loop(&dst_neuron.get_id()).await?;
+                dst_neuron.attach(src_id, dst_port).await?;
+                self.routing_table.write().unwrap().add_route(
+                    src_id,
+                    Route {
+                        dst_id: dst_id.to_string(),
+                        dst_synapse_idx: dst_port,
+                        mailbox: None,
+                    },
+                );
+            }
+        }
 
-                if result.is_ok() && self.get_monitoring_mode().await == MonitoringMode::Monitoring
+        // Dale's law: all outbound connections of the inhibitory neuron are negative
+        if src_neuron.get_kind() == NeuronKind::Inhibitory {
+            let weight = dst_neuron
+                .get_config()
+                .await
+                .input_configs
+                .get(dst_port)
+                .map(|input_cfg| input_cfg.weight)
+                .unwrap_or_default();
+            if weight > 0 {
+                if let Err(error) = dst_neuron
+                    .update_synapse(dst_port, Some(-weight), None)
+                    .await
                 {
-                    Self::send_port_status(
-                        self.monitoring_ch.store.clone(),
-                        w_port_core.id.as_str(),
-                        w_port_core.signal_hits,
-                        signal,
-                    )
-                    .await;
+                    // the inhibitory neuron must not keep the positive link
+                    if let Err(disconnect_error) = dst_neuron.disconnect(dst_port).await {
+                        warn!(src_id, dst_id, dst_port, %disconnect_error, "link is not rolled back");
+                    }
+                    self.routing_table
+                        .write()
+                        .unwrap()
+                        .remove_route(src_id, dst_id, dst_port);
+                    return Err(error);
                 }
-
-                result
-            } else {
-                Err(Box::new(RnnError::IncorrectPortType))
             }
-        } else {
-            Err(Box::new(RnnError::PortNotFound(port)))
         }
+        Ok(())
     }
 
-    /// Configuring the input interface of the network in such a way that an unambiguous
-    /// mapping is established between input ports and neuron synapses.
-    pub async fn setup_input(
+    /// Remove the link between the source neuron's axon and the destination's synapse.
+    pub async fn disconnect_neurons(
         &self,
-        network_port: usize,
-        neuron_id: &str,
-        neuron_port: usize,
-    ) -> Result<(), Box<(dyn Error)>> {
-        use std::collections::btree_map::Entry;
-
-        if let Some(neuron) = self.get_neuron(neuron_id).await {
-            let (tx, rx) = broadcast::channel(CHANNEL_CAPACITY);
-            let src_id = format!("{}I{}", self.get_id(), network_port);
-            neuron
-                .connect(&src_id, neuron_port, Arc::new(RwLock::new(rx)))
-                .await?;
-            let mut w_input_interface = self.input_interface.write().await;
-            match w_input_interface.entry(network_port) {
-                Entry::Vacant(entry) => {
-                    entry.insert(Arc::new(RwLock::new(PortCore {
-                        id: src_id.clone(),
+        src_id: &str,
+        dst_id: &str,
+        dst_port: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        let dst_neuron = self
+            .get_neuron(dst_id)
+            .await
+            .ok_or_else(|| Box::new(RnnError::NeuronNotFound(dst_id.to_string())))?;
+        match dst_neuron.get_connections().await.get(&dst_port) {
+            Some(party_id) if party_id == src_id => {}
+            Some(_) => {
+                return Err(Box::new(RnnError::NotConnected(format!(
+                    "{src_id} -> {dst_id}:{dst_port}"
+                ))))
+            }
+            None => return Err(Box::new(RnnError::PortAlreadyFree)),
+        }
+
+        dst_neuron.disconnect(dst_port).await?;
+        self.routing_table
+            .write()
+            .unwrap()
+            .remove_route(src_id, dst_id, dst_port);
+        self.log_event(NetworkEvent::NeuronsDisconnected {
+            src_id: src_id.to_string(),
+            dst_id: dst_id.to_string(),
+            dst_synapse_idx: dst_port,
+        })
+        .await;
+        Ok(())
+    }
+
+    /// Create the named group of neurons with the same parameters.
+    pub async fn create_group(
+        &self,
+        network: Arc<Network>,
+        name: &str,
+        size: usize,
+        bias: Weight,
+        input_configs: Vec<InputCfg>,
+    ) -> Result<NeuronGroup, Box<dyn Error>> {
+        if self.groups.read().await.contains_key(name) {
+            return Err(Box::new(RnnError::GroupAlreadyExists(name.to_string())));
+        }
+
+        let mut ids = vec![];
+        for _ in 0..size {
+            let neuron = self
+                .create_neuron(
+                    network.clone(),
+                    bias,
+                    input_configs.clone(),
+                    NeuronKind::Excitatory,
+                )
+                .await?;
+            ids.push(neuron.get_id());
+        }
+        self.groups
+            .write()
+            .await
+            .insert(name.to_string(), ids.clone());
+
+        Ok(NeuronGroup {
+            name: name.to_string(),
+            ids,
+        })
+    }
+
+    pub async fn get_group(&self, name: &str) -> Option<NeuronGroup> {
+        self.groups.read().await.get(name).map(|ids| NeuronGroup {
+            name: name.to_string(),
+            ids: ids.clone(),
+        })
+    }
+
+    pub async fn get_group_names(&self) -> Vec<String> {
+        self.groups.read().await.keys().cloned().collect()
+    }
+
+    async fn get_group_ids(&self, name: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        self.get_group(name)
+            .await
+            .map(|group| group.ids)
+            .ok_or_else(|| Box::new(RnnError::GroupNotFound(name.to_string())) as Box<dyn Error>)
+    }
+
+    /// Connect neurons of the source group to free dendrites of the destination group's neurons.
+    /// Returns the number of created links.
+    pub async fn connect_groups(
+        &self,
+        src_name: &str,
+        dst_name: &str,
+        connection: &GroupConnection,
+    ) -> Result<usize, Box<dyn Error>> {
+        let src_ids = self.get_group_ids(src_name).await?;
+        let dst_ids = self.get_group_ids(dst_name).await?;
+
+        let mut free_dendrites: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (dst, dst_id) in dst_ids.iter().enumerate() {
+            if let Some(neuron) = self.get_neuron(dst_id).await {
+                let connected = neuron.get_connections().await;
+                let dendrites_count = neuron.get_config().await.input_configs.len();
+                free_dendrites.insert(
+                    dst,
+                    (0..dendrites_count)
+                        .rev()
+                        .filter(|idx| !connected.contains_key(idx))
+                        .collect(),
+                );
+            }
+        }
+
+        let mut count = 0;
+        for (src, dst) in connection.pairs(src_ids.len(), dst_ids.len()) {
+            if src_ids[src] == dst_ids[dst] {
+                continue;
+            }
+            if let Some(dst_port) = free_dendrites.get_mut(&dst).and_then(|free| free.pop()) {
+                self.connect_neurons(&src_ids[src], &dst_ids[dst], dst_port)
+                    .await?;
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Reconfigure dendrites of all group's neurons.
+    pub async fn configure_group(
+        &self,
+        name: &str,
+        input_configs: Vec<InputCfg>,
+    ) -> Result<(), Box<dyn Error>> {
+        for id in self.get_group_ids(name).await? {
+            self.configure_neuron(&id, input_configs.clone()).await?;
+        }
+        Ok(())
+    }
+
+    /// Switch monitoring mode of group's neurons only.
+    pub async fn set_group_monitoring_mode(
+        &self,
+        name: &str,
+        mode: MonitoringMode,
+    ) -> Result<(), Box<dyn Error>> {
+        for id in self.get_group_ids(name).await? {
+            if let Some(neuron) = self.get_neuron(&id).await {
+                neuron.switch_monitoring_mode(mode.clone()).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the group with all its neurons.
+    pub async fn remove_group(&self, name: &str) -> Result<(), Box<dyn Error>> {
+        for id in self.get_group_ids(name).await? {
+            self.remove_neuron(&id).await?;
+        }
+        self.groups.write().await.remove(name);
+        Ok(())
+    }
+
+    /// Live tasks of the network and all its neurons. Pumps of output ports stop
+    /// when the port is freed or its neuron is removed.
+    pub async fn tasks(&self) -> TaskReport {
+        let neurons: Vec<Arc<Neuron>> = self.neurons.read().await.values().cloned().collect();
+        let mut report = TaskReport {
+            event_workers: self.event_workers_tracker.len(),
+            shard_workers: self.shard_workers_tracker.len(),
+            output_port_pumps: self.output_ports_tracker.len(),
+            monitoring: self.receivers_tracker.len(),
+            ..TaskReport::default()
+        };
+        for neuron in neurons {
+            report = report + neuron.tasks().await;
+        }
+        report
+    }
+
+    pub async fn len(&self) -> usize {
+        self.neurons.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.neurons.read().await.is_empty()
+    }
+
+    /// Send signal to port connected to synapse. In SimulationMode::Stepped
+    /// the signal is queued until the next step() and zero is returned.
+    pub async fn input(&self, signal: Signal, port: usize) -> Result<usize, Box<dyn Error>> {
+        if self.runtime_cfg.simulation == SimulationMode::Stepped {
+            if !self.input_interface.read().await.contains_key(&port) {
+                return Err(Box::new(RnnError::PortNotFound(port)));
+            }
+            self.queued_inputs.write().await.push((port, signal));
+            return Ok(0);
+        }
+        self.deliver_input(signal, port).await
+    }
+
+    async fn deliver_input(&self, signal: Signal, port: usize) -> Result<usize, Box<dyn Error>> {
+        let port_core = self
+            .input_interface
+            .read()
+            .await
+            .get(&port)
+            .cloned()
+            .ok_or_else(|| Box::new(RnnError::PortNotFound(port)))?;
+        // Guards of the port are released before waiting for free space,
+        // so a full port does not block other ports and config readers.
+        let (port_id, signal_hits, synapse) = {
+            let mut w_port_core = port_core.write().await;
+            w_port_core.signal_hits += 1;
+            match &w_port_core.signal_handler {
+                SignalHandler::Input(synapse) => (
+                    w_port_core.id.clone(),
+                    w_port_core.signal_hits,
+                    synapse.clone(),
+                ),
+                SignalHandler::Output(_) => return Err(Box::new(RnnError::IncorrectPortType)),
+            }
+        };
+
+        let capacity = self.runtime_cfg.channels.input_port;
+        match self.runtime_cfg.backpressure {
+            BackpressurePolicy::DropOldest => {}
+            BackpressurePolicy::Block => loop {
+                // Register for the notification before the check, so a signal
+                // taken in between is not missed.
+                let consumed = self.consumed.notified();
+                tokio::pin!(consumed);
+                consumed.as_mut().enable();
+                {
+                    let r_synapse = synapse.read().await;
+                    if r_synapse.len() < capacity || r_synapse.receiver_count() == 0 {
+                        break;
+                    }
+                }
+                consumed.await;
+            },
+            BackpressurePolicy::Error => {
+                if synapse.read().await.len() >= capacity {
+                    return Err(Box::new(RnnError::ChannelFull(port_id)));
+                }
+            }
+        }
+
+        let result = synapse.read().await.send(signal);
+
+        if result.is_ok() && self.is_monitored(&port_id, StatusMask::PORT).await {
+            Self::send_port_status(
+                self.monitoring_ch.store.clone(),
+                self.clock.now(),
+                port_id.as_str(),
+                signal_hits,
+                signal,
+            )
+            .await;
+        }
+
+        result.map_err(|error| Box::new(error) as Box<dyn Error>)
+    }
+
+    /// Deliver queued input signals and wait until every neuron settles
+    /// (SimulationMode::Stepped only). Returns signals emitted into output ports
+    /// during the step by port index.
+    pub async fn step(&self) -> Result<BTreeMap<usize, Vec<Signal>>, Box<dyn Error>> {
+        if self.runtime_cfg.simulation != SimulationMode::Stepped {
+            return Err(Box::new(RnnError::NotSupportedArgValue));
+        }
+
+        let mut receivers = vec![];
+        for port in self.get_output_ports().await {
+            receivers.push((port, self.subscribe_output(port).await?));
+        }
+
+        let queued_inputs: Vec<(usize, Signal)> =
+            self.queued_inputs.write().await.drain(..).collect();
+        for (port, signal) in queued_inputs {
+            // every synapse fed by the port settles its copy of the signal
+            let fan_out = self.get_input_fan_out(port).await;
+            self.in_flight.add(fan_out);
+            if let Err(error) = self.deliver_input(signal, port).await {
+                self.in_flight.settle(fan_out);
+                return Err(error);
+            }
+        }
+
+        time::timeout(
+            Duration::from_millis(STEP_SETTLE_TIMEOUT),
+            self.in_flight.settled(),
+        )
+        .await
+        .map_err(|_| {
+            Box::new(RnnError::ExpectedDataNotPresent(String::from(
+                "settled network",
+            )))
+        })?;
+
+        let mut outputs = BTreeMap::new();
+        for (port, mut receiver) in receivers {
+            let mut signals = vec![];
+            loop {
+                match receiver.try_recv() {
+                    Ok(signal) => signals.push(signal),
+                    Err(broadcast::error::TryRecvError::Lagged(lost_count)) => {
+                        warn!(port, lost_count, "output signals of the step are lost");
+                    }
+                    Err(_) => break,
+                }
+            }
+            outputs.insert(port, signals);
+        }
+        Ok(outputs)
+    }
+
+    /// Send signals into consecutive input ports: signal with index i goes into port i.
+    pub async fn input_vec(&self, signals: &[Signal]) -> Result<(), Box<dyn Error>> {
+        for (port, signal) in signals.iter().enumerate() {
+            self.input(*signal, port).await?;
+        }
+        Ok(())
+    }
+
+    /// Feed the sequence of stimuli (see input_vec()) one by one with the interval
+    /// between them. The returned handle completes when the sequence is over
+    /// or the first stimulus can not be sent.
+    pub fn input_pattern(
+        &self,
+        network: Arc<Network>,
+        pattern: Vec<Vec<Signal>>,
+        interval: Duration,
+    ) -> JoinHandle<Result<(), RnnError>> {
+        tokio::spawn(async move {
+            for (idx, signals) in pattern.iter().enumerate() {
+                if idx > 0 {
+                    time::sleep(interval).await;
+                }
+                network.input_vec(signals).await.map_err(|error| {
+                    match error.downcast::<RnnError>() {
+                        Ok(error) => *error,
+                        Err(_) => RnnError::SignalSendError,
+                    }
+                })?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Configuring the input interface of the network in such a way that an unambiguous
+    /// mapping is established between input ports and neuron synapses.
+    pub async fn setup_input(
+        &self,
+        network_port: usize,
+        neuron_id: &str,
+        neuron_port: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        self.bind_input(network_port, neuron_id, neuron_port)
+            .await?;
+        self.log_event(NetworkEvent::InputSetup {
+            input_port: network_port,
+            dst_id: neuron_id.to_string(),
+            dst_synapse_idx: neuron_port,
+        })
+        .await;
+        Ok(())
+    }
+
+    /// Connect the input port to the neuron's synapse. The bound port fans out:
+    /// every connected synapse gets its own receiver of the port's channel.
+    async fn bind_input(
+        &self,
+        network_port: usize,
+        neuron_id: &str,
+        neuron_port: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        let neuron = self
+            .get_neuron(neuron_id)
+            .await
+            .ok_or_else(|| Box::new(RnnError::NeuronNotFound(neuron_id.to_string())))?;
+        let src_id = format!("{}I{}", self.get_id(), network_port);
+        let mut w_input_interface = self.input_interface.write().await;
+        match w_input_interface.get(&network_port) {
+            Some(port_core) => {
+                let mut w_port_core = port_core.write().await;
+                let SignalHandler::Input(synapse) = &w_port_core.signal_handler else {
+                    return Err(Box::new(RnnError::IncorrectPortType));
+                };
+                let rx = synapse.read().await.subscribe();
+                neuron
+                    .connect(&src_id, neuron_port, Arc::new(RwLock::new(rx)))
+                    .await?;
+                w_port_core.parties.push(neuron_id.to_string());
+            }
+            None => {
+                let (tx, rx) = broadcast::channel(self.runtime_cfg.channels.input_port);
+                neuron
+                    .connect(&src_id, neuron_port, Arc::new(RwLock::new(rx)))
+                    .await?;
+                w_input_interface.insert(
+                    network_port,
+                    Arc::new(RwLock::new(PortCore {
+                        id: src_id,
+                        parties: vec![neuron_id.to_string()],
                         signal_hits: 0,
                         signal_handler: SignalHandler::Input(Arc::new(RwLock::new(tx))),
-                    })));
-                    Ok(())
+                        pump: None,
+                    })),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// The number of synapses fed by the input port.
+    async fn get_input_fan_out(&self, port: usize) -> usize {
+        match self.input_interface.read().await.get(&port) {
+            Some(port_core) => port_core.read().await.parties.len(),
+            None => 0,
+        }
+    }
+
+    /// Disconnect the input port from synapses of neurons and remove the port.
+    pub async fn free_input(&self, network_port: usize) -> Result<(), Box<dyn Error>> {
+        let port_core = self
+            .input_interface
+            .write()
+            .await
+            .remove(&network_port)
+            .ok_or_else(|| Box::new(RnnError::PortAlreadyFree))?;
+        let r_port_core = port_core.read().await;
+        for party_id in r_port_core.parties.iter() {
+            let Some(neuron) = self.get_neuron(party_id).await else {
+                continue;
+            };
+            for (port, src_id) in neuron.get_connections().await {
+                if src_id == r_port_core.id {
+                    neuron.disconnect(port).await?;
                 }
-                Entry::Occupied(_) => Err(Box::new(RnnError::PortBusy(src_id))),
             }
-        } else {
-            Err(Box::new(RnnError::NeuronNotFound(neuron_id.to_string())) as Box<dyn Error>)
         }
+        self.log_event(NetworkEvent::InputFreed {
+            input_port: network_port,
+        })
+        .await;
+        Ok(())
     }
 
     pub async fn pop_monitoring_store(&self) -> Vec<Status> {
-        let mut w_monitoring_store = self.monitoring_ch.store.write().await;
-        let snapshot = w_monitoring_store.clone();
-        w_monitoring_store.clear();
-        snapshot
+        self.monitoring_ch.store.pop_all().await
+    }
+
+    /// Pop the monitoring store into the writer. Returns the number of written records.
+    pub async fn export_monitoring(
+        &self,
+        writer: &mut impl Write,
+        format: ExportFormat,
+    ) -> Result<usize, Box<dyn Error>> {
+        let statuses = self.pop_monitoring_store().await;
+        match format {
+            ExportFormat::JsonLines => {
+                for status in statuses.iter() {
+                    serde_json::to_writer(&mut *writer, status)?;
+                    writeln!(writer)?;
+                }
+            }
+            ExportFormat::Csv => {
+                writeln!(writer, "{STATUS_CSV_HEADER}")?;
+                for status in statuses.iter() {
+                    writeln!(writer, "{}", status.to_csv_row())?;
+                }
+            }
+        }
+        writer.flush()?;
+        Ok(statuses.len())
+    }
+
+    /// Statuses collected in MonitoringMode::Monitoring after subscription.
+    /// Every stream gets all statuses regardless of RuntimeCfg::monitoring_store capacity,
+    /// statuses lost because the stream lagged behind are skipped.
+    pub fn monitoring_stream(&self) -> impl Stream<Item = Status> + Unpin {
+        BroadcastStream::new(self.monitoring_ch.store.subscribe()).filter_map(|status| status.ok())
     }
 
     pub async fn get_current_neuron_status(
@@ -367,7 +1542,11 @@ impl Network {
         let mut w_output_interface = self.output_interface.write().await;
         match w_output_interface.entry(network_port) {
             Entry::Occupied(entity) => {
-                entity.remove();
+                entity.remove().write().await.stop_pump();
+                self.log_event(NetworkEvent::OutputFreed {
+                    output_port: network_port,
+                })
+                .await;
                 Ok(())
             }
             Entry::Vacant(_) => Err(Box::new(RnnError::PortAlreadyFree)),
@@ -378,30 +1557,54 @@ impl Network {
         &self,
         network_port: usize,
         neuron_id: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        self.bind_output(network_port, neuron_id).await?;
+        self.log_event(NetworkEvent::OutputSetup {
+            src_id: neuron_id.to_string(),
+            output_port: network_port,
+        })
+        .await;
+        Ok(())
+    }
+
+    async fn bind_output(
+        &self,
+        network_port: usize,
+        neuron_id: &str,
     ) -> Result<(), Box<dyn Error>> {
         let port_id = format!("{}O{}", self.get_id(), network_port);
         let mut w_output_interface = self.output_interface.write().await;
         if let Some(neuron) = self.get_neuron(neuron_id).await {
             // connect axon to output port if port is free else return error
-            match w_output_interface.entry(network_port.clone()) {
+            match w_output_interface.entry(network_port) {
                 Entry::Occupied(_) => Err(Box::new(RnnError::PortBusy(port_id.clone()))),
                 Entry::Vacant(entry) => {
                     let receiver = neuron.provide_output().await;
                     let port_core = Arc::new(RwLock::new(PortCore {
                         id: port_id.clone(),
+                        parties: vec![neuron_id.to_string()],
                         signal_hits: 0,
                         signal_handler: SignalHandler::Output(receiver.clone()),
+                        pump: None,
                     }));
                     entry.insert(port_core.clone());
+                    let pumped_port_core = port_core.clone();
+                    debug!(port_id = %port_id, neuron_id, "output port bound");
                     let monitoring_store_cloned = self.monitoring_ch.store.clone();
+                    let modes = self.modes.clone();
+                    let clock = self.clock.clone();
 
-                    self.receivers_tracker.spawn(async move {
+                    let pump = self.output_ports_tracker.spawn(async move {
                         while let Ok(signal) = receiver.write().await.recv().await {
                             let port_core_cloned = port_core.clone();
                             let mut w_port_core = port_core_cloned.write().await;
                             w_port_core.signal_hits += 1;
+                            if !Self::accepts_status(&modes, &port_id, StatusMask::PORT).await {
+                                continue;
+                            }
                             Self::send_port_status(
                                 monitoring_store_cloned.clone(),
+                                clock.now(),
                                 &port_id,
                                 w_port_core.signal_hits,
                                 signal,
@@ -409,6 +1612,7 @@ impl Network {
                             .await;
                         }
                     });
+                    pumped_port_core.write().await.pump = Some(pump);
 
                     Ok(())
                 }
@@ -431,94 +1635,783 @@ impl Network {
         }
     }
 
-    pub fn get_id(&self) -> String {
-        self.id.clone()
+    /// Subscribe to signals of the output port. Unlike get_output_receiver() the receiver
+    /// is not shared, so it gets every signal sent after subscription.
+    pub async fn subscribe_output(&self, port: usize) -> Result<Receiver<Signal>, Box<dyn Error>> {
+        let party_id = match self.output_interface.read().await.get(&port) {
+            Some(port_core) => port_core.read().await.get_driver(),
+            None => return Err(Box::new(RnnError::PortNotFound(port))),
+        };
+        let neuron = self
+            .get_neuron(&party_id)
+            .await
+            .ok_or_else(|| Box::new(RnnError::NeuronNotFound(party_id)))?;
+        Ok(neuron.subscribe().await)
     }
 
-    async fn send_port_status(
-        monitoring_store: Arc<RwLock<Vec<Status>>>,
-        port_id: &str,
-        signal_hits: u64,
-        recent_signal: Signal,
-    ) {
-        let mut w_monitoring_store = monitoring_store.write().await;
-        let timestamp = Utc::now();
-        w_monitoring_store.push(Status::Port(PortInfo {
-            timestamp,
-            id: port_id.to_string(),
-            hit_count: signal_hits,
-            recent_signal,
-        }));
+    /// Signals of the output port as the wakeup-driven stream (see subscribe_output()).
+    /// Signals lost because the stream lagged behind are skipped.
+    pub async fn output_port_stream(
+        &self,
+        port: usize,
+    ) -> Result<impl Stream<Item = Signal> + Unpin, Box<dyn Error>> {
+        let receiver = self.subscribe_output(port).await?;
+        Ok(BroadcastStream::new(receiver).filter_map(|signal| signal.ok()))
     }
-}
 
-impl fmt::Display for Network {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "The Network {} ", self.id)
+    /// Signals of all connected output ports merged into the single stream
+    /// of (port, signal). Ports connected later are not included.
+    pub async fn output_stream(
+        &self,
+    ) -> Result<impl Stream<Item = (usize, Signal)> + Unpin, Box<dyn Error>> {
+        let mut streams = StreamMap::new();
+        for port in self.get_output_ports().await {
+            streams.insert(port, self.output_port_stream(port).await?);
+        }
+        Ok(streams)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::rnn::tests::fixtures::{gen_neuron_input_config_fixture, new_network_fixture};
+    /// Get indexes of connected output ports.
+    pub async fn get_output_ports(&self) -> Vec<usize> {
+        self.output_interface.read().await.keys().copied().collect()
+    }
 
-    use super::*;
+    pub fn get_id(&self) -> String {
+        self.id.clone()
+    }
 
-    #[tokio::test]
-    async fn should_create_two_unique_networks() {
-        let n1 = Network::new().unwrap();
-        let n2 = Network::new().unwrap();
+    async fn log_event(&self, event: NetworkEvent) {
+        self.construction_log
+            .write()
+            .await
+            .append_at(event, self.clock.now());
+    }
 
-        assert_ne!(n1.id, n2.id);
+    /// Get records of structural mutations in order they happened.
+    pub async fn get_construction_log(&self) -> Vec<EventRecord> {
+        self.construction_log.read().await.records().to_vec()
     }
 
-    #[tokio::test]
-    async fn should_create_two_neurons_in_same_network() {
-        let net_orig = Arc::new(Network::new().unwrap());
+    /// Apply recorded structural mutations to this network to reconstruct the topology.
+    /// The recorded neuron ids belong to the origin network, therefore they are mapped
+    /// to ids of created neurons. Returns this mapping (recorded id -> new id).
+    pub async fn replay_construction_log(
+        &self,
+        network: Arc<Network>,
+        records: &[EventRecord],
+    ) -> Result<BTreeMap<String, String>, Box<dyn Error>> {
+        let mut ids: BTreeMap<String, String> = BTreeMap::new();
+        let map_id = |ids: &BTreeMap<String, String>, id: &str| {
+            ids.get(id)
+                .cloned()
+                .ok_or_else(|| Box::new(RnnError::NeuronNotFound(id.to_string())))
+        };
 
-        for _ in 0..=1 {
-            let net = net_orig.clone();
-            assert!(net.create_neuron(net.clone(), 1, vec![]).await.is_ok());
+        for record in records {
+            match &record.event {
+                NetworkEvent::NeuronCreated {
+                    id,
+                    bias,
+                    input_configs,
+                    kind,
+                } => {
+                    let neuron = self
+                        .create_neuron(network.clone(), *bias, input_configs.clone(), *kind)
+                        .await?;
+                    ids.insert(id.clone(), neuron.get_id());
+                }
+                NetworkEvent::NeuronRemoved { id } => {
+                    self.remove_neuron(&map_id(&ids, id)?).await?;
+                    ids.remove(id);
+                }
+                NetworkEvent::NeuronConfigured { id, input_configs } => {
+                    self.configure_neuron(&map_id(&ids, id)?, input_configs.clone())
+                        .await?;
+                }
+                NetworkEvent::NeuronsConnected {
+                    src_id,
+                    dst_id,
+                    dst_synapse_idx,
+                } => {
+                    self.connect_neurons(
+                        &map_id(&ids, src_id)?,
+                        &map_id(&ids, dst_id)?,
+                        *dst_synapse_idx,
+                    )
+                    .await?;
+                }
+                NetworkEvent::NeuronsDisconnected {
+                    src_id,
+                    dst_id,
+                    dst_synapse_idx,
+                } => {
+                    self.disconnect_neurons(
+                        &map_id(&ids, src_id)?,
+                        &map_id(&ids, dst_id)?,
+                        *dst_synapse_idx,
+                    )
+                    .await?;
+                }
+                NetworkEvent::InputSetup {
+                    input_port,
+                    dst_id,
+                    dst_synapse_idx,
+                } => {
+                    self.setup_input(*input_port, &map_id(&ids, dst_id)?, *dst_synapse_idx)
+                        .await?;
+                }
+                NetworkEvent::OutputSetup {
+                    src_id,
+                    output_port,
+                } => {
+                    self.setup_output(*output_port, &map_id(&ids, src_id)?)
+                        .await?;
+                }
+                NetworkEvent::InputFreed { input_port } => {
+                    self.free_input(*input_port).await?;
+                }
+                NetworkEvent::OutputFreed { output_port } => {
+                    self.free_output(*output_port).await?;
+                }
+                NetworkEvent::SynapseUpdated {
+                    id,
+                    port,
+                    weight,
+                    capacity,
+                } => {
+                    self.update_synapse(&map_id(&ids, id)?, *port, *weight, *capacity)
+                        .await;
+                }
+                NetworkEvent::BiasUpdated { id, bias } => {
+                    self.update_bias(&map_id(&ids, id)?, *bias).await;
+                }
+                NetworkEvent::LabelsSet { labels } => {
+                    self.set_labels(labels.clone()).await?;
+                }
+                NetworkEvent::SeedSet { seed } => {
+                    self.set_seed(*seed).await;
+                }
+                NetworkEvent::LineageSet { lineage } => {
+                    self.set_lineage(lineage.clone()).await;
+                }
+                NetworkEvent::ConfigRestored { config } => {
+                    ids = self.restore_config(network.clone(), config).await?;
+                }
+            }
         }
 
-        let net = net_orig.clone();
-        assert_eq!(net.len().await, 2);
+        Ok(ids)
     }
 
-    #[tokio::test]
-    async fn network_can_get_neuron_after_create() {
-        let net = Arc::new(Network::new().unwrap());
-
-        let neuron_rc = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
-        let neuron_id = neuron_rc.get_id();
-
-        assert_eq!(net.len().await, 1);
-        assert!(
-            net.get_neuron(neuron_id.as_str()).await.is_some(),
-            "Neuron not found"
-        );
-        assert!(
-            net.get_neuron("missed").await.is_none(),
-            "Should be nothing"
-        );
+    /// Structural problems of the current topology: cycles, neurons unreachable
+    /// from input ports and dead-end axons (see analysis::structure).
+    pub async fn analyze(&self) -> Result<StructureReport, Box<dyn Error>> {
+        structure::analyze(&self.get_config().await)
     }
 
-    #[tokio::test]
-    async fn network_can_remove_neuron_after_create() {
-        let net = Arc::new(Network::new().unwrap());
+    /// Lint the live network: config validation and structural analysis
+    /// (see analysis::doctor::lint()) plus runtime checks of input ports without
+    /// subscribed synapses and statuses lost by the saturated monitoring channel.
+    pub async fn doctor(&self) -> DoctorReport {
+        let id = self.get_id();
+        let mut findings = doctor::lint(&self.get_config().await, &id);
 
-        let neuron_rc = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
-        assert_eq!(net.len().await, 1);
+        for (port, port_core) in self.input_interface.read().await.iter() {
+            let r_port_core = port_core.read().await;
+            if let SignalHandler::Input(sender) = &r_port_core.signal_handler {
+                if sender.read().await.receiver_count() == 0 {
+                    findings.push(Finding::new(
+                        Severity::Error,
+                        &r_port_core.id,
+                        &format!("input port {port} has no subscribed synapses"),
+                    ));
+                }
+            }
+        }
 
-        assert!(net.remove_neuron(&neuron_rc.get_id()).await.is_ok());
-        assert_eq!(net.len().await, 0);
+        let counters = &self.monitoring_ch.counters;
+        if counters.get_dropped() > 0 {
+            findings.push(Finding::new(
+                Severity::Warning,
+                &id,
+                &format!(
+                    "{} statuses dropped by the full monitoring channel",
+                    counters.get_dropped()
+                ),
+            ));
+        }
+        if counters.get_coalesced() > 0 {
+            findings.push(Finding::new(
+                Severity::Info,
+                &id,
+                &format!(
+                    "{} statuses coalesced by the full monitoring channel",
+                    counters.get_coalesced()
+                ),
+            ));
+        }
+        if self.monitoring_ch.sender.capacity() == 0 {
+            findings.push(Finding::new(
+                Severity::Warning,
+                &id,
+                "monitoring channel is full",
+            ));
+        }
+
+        DoctorReport::new(findings)
     }
 
-    #[tokio::test]
-    async fn network_should_return_error_if_remove_by_incorrect_id() {
-        let net = Arc::new(Network::new().unwrap());
+    /// GraphViz DOT graph of the current topology (see NetworkCfg::to_dot()).
+    pub async fn to_dot(&self) -> String {
+        self.get_config().await.to_dot()
+    }
 
-        let neuron = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+    /// Get current topology and parameters of the network. Composites are flattened
+    /// when the config is installed, so the config has none and their inner neurons
+    /// are plain ones with lineage markers prefixed by composite's id.
+    pub async fn get_config(&self) -> NetworkCfg {
+        let neurons: Vec<Arc<Neuron>> = self.neurons.read().await.values().cloned().collect();
+        let input_ports: BTreeMap<String, usize> = {
+            let r_input_interface = self.input_interface.read().await;
+            let mut input_ports = BTreeMap::new();
+            for (port, port_core) in r_input_interface.iter() {
+                input_ports.insert(port_core.read().await.id.clone(), *port);
+            }
+            input_ports
+        };
+
+        let mut neuron_cfgs = vec![];
+        let mut input_links = vec![];
+        let mut inner_links = vec![];
+        for neuron in neurons.iter() {
+            neuron_cfgs.push(neuron.get_config().await);
+            for (dst_synapse_idx, party_id) in neuron.get_connections().await {
+                if let Some(input_port) = input_ports.get(&party_id) {
+                    input_links.push(LinkCfg::Input {
+                        input_port: *input_port,
+                        dst_id: neuron.get_id(),
+                        dst_synapse_idx,
+                    });
+                } else {
+                    inner_links.push(LinkCfg::Inner {
+                        src_id: party_id,
+                        dst_id: neuron.get_id(),
+                        dst_synapse_idx,
+                    });
+                }
+            }
+        }
+        input_links.sort_by_key(|link| match link {
+            LinkCfg::Input { input_port, .. } => *input_port,
+            _ => 0,
+        });
+
+        let mut output_links = vec![];
+        let mut outputs = 0;
+        for (output_port, port_core) in self.output_interface.read().await.iter() {
+            output_links.push(LinkCfg::Output {
+                src_id: port_core.read().await.get_driver(),
+                output_port: *output_port,
+            });
+            outputs = output_port + 1;
+        }
+
+        // ports may be bound sparsely, so counts cover the highest bound port
+        NetworkCfg {
+            inputs: input_ports.values().max().map_or(0, |port| port + 1),
+            outputs,
+            neurons: neuron_cfgs,
+            links: input_links
+                .into_iter()
+                .chain(inner_links)
+                .chain(output_links)
+                .collect(),
+            lineage: self.get_lineage().await,
+            composites: vec![],
+            labels: self.get_labels().await,
+            seed: *self.seed.read().await,
+        }
+    }
+
+    /// Get the origin of the network's config.
+    pub async fn get_lineage(&self) -> Option<NetworkLineage> {
+        self.lineage.read().await.clone()
+    }
+
+    /// Set the origin of the network's config, e.g. after the trainer has produced it.
+    pub async fn set_lineage(&self, lineage: Option<NetworkLineage>) {
+        *self.lineage.write().await = lineage.clone();
+        self.log_event(NetworkEvent::LineageSet { lineage }).await;
+    }
+
+    /// The provider of random generators seeded by the network's seed
+    /// or by entropy when the seed is not set.
+    pub async fn get_rng_provider(&self) -> RngProvider {
+        self.seed
+            .read()
+            .await
+            .map_or(self.entropy_rng, RngProvider::new)
+    }
+
+    /// Set the root seed of random generators to make the network reproducible.
+    pub async fn set_seed(&self, seed: Option<u64>) {
+        *self.seed.write().await = seed;
+        self.log_event(NetworkEvent::SeedSet { seed }).await;
+    }
+
+    /// Get the assignment of dataset labels to output ports.
+    pub async fn get_labels(&self) -> LabelMap {
+        self.labels.read().await.clone()
+    }
+
+    /// Set the assignment of labels, e.g. after binding the training data.
+    pub async fn set_labels(&self, labels: LabelMap) -> Result<(), Box<dyn Error>> {
+        labels.validate(self.get_output_ports().await.len())?;
+        *self.labels.write().await = labels.clone();
+        self.log_event(NetworkEvent::LabelsSet { labels }).await;
+        Ok(())
+    }
+
+    /// Stop and remove all neurons, network's ports, groups and routes.
+    pub(crate) async fn clear(&self) {
+        let neurons: Vec<Arc<Neuron>> = {
+            let mut w_neurons = self.neurons.write().await;
+            let neurons = w_neurons.values().cloned().collect();
+            w_neurons.clear();
+            neurons
+        };
+        for neuron in neurons {
+            neuron.shutdown().await;
+        }
+        self.input_interface.write().await.clear();
+        let output_ports: Vec<Arc<RwLock<PortCore>>> = {
+            let mut w_output_interface = self.output_interface.write().await;
+            let output_ports = w_output_interface.values().cloned().collect();
+            w_output_interface.clear();
+            output_ports
+        };
+        for port_core in output_ports {
+            port_core.write().await.stop_pump();
+        }
+        self.groups.write().await.clear();
+        *self.routing_table.write().unwrap() = RoutingTable::default();
+    }
+
+    /// Flatten and validate the config and check that its neuron ids map to unique
+    /// ids of the network. Returns the flattened config.
+    fn check_installable(config: &NetworkCfg) -> Result<NetworkCfg, Box<dyn Error>> {
+        let flat = config.flatten()?;
+        flat.validate()?;
+        let mut id_nums = BTreeSet::new();
+        for neuron_cfg in flat.neurons.iter() {
+            let id_num = get_component_id_fraction(&neuron_cfg.id, &SpecificationType::Neuron)?;
+            if !id_nums.insert(id_num) {
+                return Err(Box::new(RnnError::NeuronAlreadyExists(
+                    neuron_cfg.id.clone(),
+                )));
+            }
+        }
+        Ok(flat)
+    }
+
+    /// Create neurons, links and ports described by the flattened configuration
+    /// (see check_installable()).
+    /// Neuron ids keep their numeric part but get the prefix of this network.
+    /// Neurons which change their id keep the configured one as lineage marker.
+    /// Returns mapping of configured neuron ids to ids of created neurons.
+    async fn install_config(
+        &self,
+        network: Arc<Network>,
+        config: &NetworkCfg,
+    ) -> Result<BTreeMap<String, String>, Box<dyn Error>> {
+        let mut ids: BTreeMap<String, String> = BTreeMap::new();
+        for neuron_cfg in config.neurons.iter() {
+            let id_num = get_component_id_fraction(&neuron_cfg.id, &SpecificationType::Neuron)?;
+            let id = gen_id_by_spec_type(&self.id, id_num, &SpecificationType::Neuron)?;
+            self.insert_neuron(
+                network.clone(),
+                NeuronCfg {
+                    id: id.clone(),
+                    lineage: Some(neuron_cfg.lineage_marker().to_string())
+                        .filter(|marker| *marker != id),
+                    ..neuron_cfg.clone()
+                },
+            )
+            .await?;
+            ids.insert(neuron_cfg.id.clone(), id);
+        }
+
+        let map_id = |id: &str| {
+            ids.get(id)
+                .cloned()
+                .ok_or_else(|| Box::new(RnnError::NeuronNotFound(id.to_string())))
+        };
+        for link in config.links.iter() {
+            match link {
+                LinkCfg::Input {
+                    input_port,
+                    dst_id,
+                    dst_synapse_idx,
+                } => {
+                    self.bind_input(*input_port, &map_id(dst_id)?, *dst_synapse_idx)
+                        .await?
+                }
+                LinkCfg::Inner {
+                    src_id,
+                    dst_id,
+                    dst_synapse_idx,
+                } => {
+                    self.link_neurons(&map_id(src_id)?, &map_id(dst_id)?, *dst_synapse_idx)
+                        .await?
+                }
+                LinkCfg::Output {
+                    src_id,
+                    output_port,
+                } => self.bind_output(*output_port, &map_id(src_id)?).await?,
+            }
+        }
+        // logged as a part of ConfigRestored
+        *self.lineage.write().await = config.lineage.clone();
+        *self.labels.write().await = config.labels.clone();
+        *self.seed.write().await = config.seed;
+
+        Ok(ids)
+    }
+
+    /// Store current topology and parameters of the network under the name.
+    /// Existing checkpoint with the same name is replaced.
+    pub async fn checkpoint(&self, name: &str) {
+        let config = self.get_config().await;
+        self.checkpoints
+            .write()
+            .await
+            .insert(name.to_string(), config);
+    }
+
+    /// Get stored checkpoint by name.
+    pub async fn get_checkpoint(&self, name: &str) -> Option<NetworkCfg> {
+        self.checkpoints.read().await.get(name).cloned()
+    }
+
+    /// Get names of stored checkpoints.
+    pub async fn get_checkpoint_names(&self) -> Vec<String> {
+        self.checkpoints.read().await.keys().cloned().collect()
+    }
+
+    /// Restore topology and parameters of the network from the checkpoint in place.
+    /// All neurons are rebuilt with the same ids, so dynamic state (accumulators,
+    /// counters, synapse capacities) is reset and previously taken port receivers
+    /// must be requested again.
+    pub async fn rollback(&self, network: Arc<Network>, name: &str) -> Result<(), Box<dyn Error>> {
+        let config = self
+            .get_checkpoint(name)
+            .await
+            .ok_or_else(|| Box::new(RnnError::CheckpointNotFound(name.to_string())))?;
+
+        self.restore_config(network, &config).await?;
+        Ok(())
+    }
+
+    /// Replace the whole topology and parameters of the network with configuration.
+    /// Returns mapping of configured neuron ids to ids of created neurons.
+    /// The invalid config is rejected before the current topology is dropped.
+    pub async fn restore_config(
+        &self,
+        network: Arc<Network>,
+        config: &NetworkCfg,
+    ) -> Result<BTreeMap<String, String>, Box<dyn Error>> {
+        let flat = Self::check_installable(config)?;
+        self.clear().await;
+        let ids = self.install_config(network, &flat).await?;
+        self.log_event(NetworkEvent::ConfigRestored {
+            config: config.clone(),
+        })
+        .await;
+        Ok(ids)
+    }
+
+    /// Write stored checkpoint into file in json format.
+    pub async fn save_checkpoint(&self, name: &str, path: &Path) -> Result<(), Box<dyn Error>> {
+        let config = self
+            .get_checkpoint(name)
+            .await
+            .ok_or_else(|| Box::new(RnnError::CheckpointNotFound(name.to_string())))?;
+        serde_json::to_writer(File::create(path)?, &config)?;
+        Ok(())
+    }
+
+    /// Read checkpoint from json file and store it under the name.
+    pub async fn load_checkpoint(&self, name: &str, path: &Path) -> Result<(), Box<dyn Error>> {
+        let config: NetworkCfg = serde_json::from_reader(File::open(path)?)?;
+        self.checkpoints
+            .write()
+            .await
+            .insert(name.to_string(), config);
+        Ok(())
+    }
+
+    /// Capture topology and dynamic state of all neurons (see NetworkSnapshot).
+    /// Signals in flight between neurons are lost, so pause the network first
+    /// when the exact state matters.
+    pub async fn snapshot(&self) -> NetworkSnapshot {
+        let config = self.get_config().await;
+        let neurons: Vec<Arc<Neuron>> = self.neurons.read().await.values().cloned().collect();
+        let mut states = vec![];
+        for neuron in neurons.iter() {
+            states.push(neuron.get_state().await);
+        }
+        states.sort_by(|a, b| a.id.cmp(&b.id));
+
+        NetworkSnapshot {
+            timestamp: self.get_clock().now(),
+            config,
+            neurons: states,
+        }
+    }
+
+    /// Replace the whole network with the snapshot's config and bring neurons into
+    /// captured states. Signals buffered by paused neurons are processed on resume.
+    pub async fn restore(
+        &self,
+        network: Arc<Network>,
+        snapshot: &NetworkSnapshot,
+    ) -> Result<(), Box<dyn Error>> {
+        let ids = self.restore_config(network, &snapshot.config).await?;
+        for state in snapshot.neurons.iter() {
+            let id = ids
+                .get(&state.id)
+                .ok_or_else(|| Box::new(RnnError::NeuronNotFound(state.id.clone())))?;
+            let neuron = self
+                .get_neuron(id)
+                .await
+                .ok_or_else(|| Box::new(RnnError::NeuronNotFound(id.clone())))?;
+            neuron.set_state(state).await;
+        }
+        Ok(())
+    }
+
+    /// Bring the replica of another network up to date with the snapshot delta
+    /// of that network. Neurons are matched by the number of their ids, so the
+    /// replica has to be built from the same config. The new config of the delta
+    /// is installed first.
+    pub async fn apply_delta(
+        &self,
+        network: Arc<Network>,
+        delta: &SnapshotDelta,
+    ) -> Result<(), Box<dyn Error>> {
+        if let Some(config) = &delta.config {
+            self.restore_config(network, config).await?;
+        }
+        for state in delta.neurons.iter() {
+            let id_num = get_component_id_fraction(&state.id, &SpecificationType::Neuron)?;
+            let id = gen_id_by_spec_type(&self.id, id_num, &SpecificationType::Neuron)?;
+            let neuron = self
+                .get_neuron(&id)
+                .await
+                .ok_or_else(|| Box::new(RnnError::NeuronNotFound(id.clone())))?;
+            neuron.set_state(state).await;
+        }
+        Ok(())
+    }
+
+    /// Unbind the neuron from ports and remove ports left without neurons.
+    /// Returns the number of ports the neuron was unbound from.
+    async fn remove_bound_ports(
+        interface: &RwLock<BTreeMap<usize, Arc<RwLock<PortCore>>>>,
+        neuron_id: &str,
+    ) -> usize {
+        let mut w_interface = interface.write().await;
+        let mut unbound_count = 0;
+        let mut free_ports = vec![];
+        for (port, port_core) in w_interface.iter() {
+            let mut w_port_core = port_core.write().await;
+            let parties_count = w_port_core.parties.len();
+            w_port_core.parties.retain(|party_id| party_id != neuron_id);
+            unbound_count += parties_count - w_port_core.parties.len();
+            if w_port_core.parties.is_empty() {
+                free_ports.push(*port);
+            }
+        }
+        for port in free_ports.iter() {
+            if let Some(port_core) = w_interface.remove(port) {
+                port_core.write().await.stop_pump();
+            }
+        }
+        unbound_count
+    }
+
+    async fn send_port_status(
+        monitoring_store: Arc<MonitoringStore>,
+        timestamp: DateTime<Utc>,
+        port_id: &str,
+        signal_hits: u64,
+        recent_signal: Signal,
+    ) {
+        monitoring_store
+            .push(Status::Port(PortInfo {
+                timestamp,
+                id: port_id.to_string(),
+                hit_count: signal_hits,
+                recent_signal,
+            }))
+            .await;
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The Network {} ", self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rnn::common::clock::TokioClock;
+    use crate::rnn::common::network_cfg::CompositeCfg;
+    use crate::rnn::common::runtime_cfg::{
+        ChannelCapacities, MonitoringOverflow, MonitoringStoreCfg,
+    };
+    use crate::rnn::tests::fixtures::{
+        gen_neuron_input_config_fixture, new_event_driven_network_fixture,
+        new_mailbox_network_fixture, new_network_fixture, new_sharded_network_fixture,
+        new_stepped_network_fixture,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_create_two_unique_networks() {
+        let n1 = Network::new().unwrap();
+        let n2 = Network::new().unwrap();
+
+        assert_ne!(n1.id, n2.id);
+    }
+
+    #[tokio::test]
+    async fn should_create_two_neurons_in_same_network() {
+        let net_orig = Arc::new(Network::new().unwrap());
+
+        for _ in 0..=1 {
+            let net = net_orig.clone();
+            assert!(net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .is_ok());
+        }
+
+        let net = net_orig.clone();
+        assert_eq!(net.len().await, 2);
+    }
+
+    #[tokio::test]
+    async fn should_create_more_then_ten_neurons_with_unique_ids() {
+        let net = Arc::new(new_network_fixture());
+
+        for _ in 0..12 {
+            assert!(net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .is_ok());
+        }
+
+        assert_eq!(net.len().await, 12);
+        assert_eq!(net.get_available_neuron_id().await, 12);
+    }
+
+    #[tokio::test]
+    async fn network_can_get_neuron_after_create() {
+        let net = Arc::new(Network::new().unwrap());
+
+        let neuron_rc = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        let neuron_id = neuron_rc.get_id();
+
+        assert_eq!(net.len().await, 1);
+        assert!(
+            net.get_neuron(neuron_id.as_str()).await.is_some(),
+            "Neuron not found"
+        );
+        assert!(
+            net.get_neuron("missed").await.is_none(),
+            "Should be nothing"
+        );
+    }
+
+    #[tokio::test]
+    async fn network_can_remove_neuron_after_create() {
+        let net = Arc::new(Network::new().unwrap());
+
+        let neuron_rc = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert_eq!(net.len().await, 1);
+
+        assert!(net.remove_neuron(&neuron_rc.get_id()).await.is_ok());
+        assert_eq!(net.len().await, 0);
+    }
+
+    #[tokio::test]
+    async fn removed_neuron_should_be_disconnected_from_parties_and_ports() {
+        for net in [
+            Arc::new(new_network_fixture()),
+            Arc::new(new_mailbox_network_fixture()),
+        ] {
+            net.set_monitoring_mode(MonitoringMode::Monitoring).await;
+            let src = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            let mid = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            let dst = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            assert!(net
+                .connect_neurons(&src.get_id(), &mid.get_id(), 0)
+                .await
+                .is_ok());
+            assert!(net
+                .connect_neurons(&mid.get_id(), &dst.get_id(), 0)
+                .await
+                .is_ok());
+            assert!(net.setup_output(0, &mid.get_id()).await.is_ok());
+            assert!(net.setup_input(0, &src.get_id(), 0).await.is_ok());
+            net.pop_monitoring_store().await;
+
+            assert!(net.remove_neuron(&mid.get_id()).await.is_ok());
+            tokio::task::yield_now().await;
+
+            assert!(dst.get_connections().await.is_empty());
+            assert!(net.get_output_ports().await.is_empty());
+            assert!(net.get_routing_table().read().unwrap().is_empty());
+            assert_eq!(mid.tasks().await, TaskReport::default());
+            match net.pop_monitoring_store().await.as_slice() {
+                [Status::Removal(info)] => {
+                    assert_eq!(info.id, mid.get_id());
+                    assert_eq!(info.disconnected_count, 3);
+                }
+                _ => panic!("removal status expected"),
+            }
+
+            assert!(net.remove_neuron(&src.get_id()).await.is_ok());
+            assert!(net.input(2, 0).await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn network_should_return_error_if_remove_by_incorrect_id() {
+        let net = Arc::new(Network::new().unwrap());
+
+        let neuron = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
         let neuron_id = neuron.get_id();
         assert_eq!(net.len().await, 1);
 
@@ -535,7 +2428,10 @@ mod tests {
     async fn network_can_verify_if_contains_neuron_with_specified_id() {
         let net = Arc::new(Network::new().unwrap());
 
-        let neuron = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+        let neuron = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
 
         assert!(net.has_neuron(neuron.get_id().as_str()).await);
         assert!(!net.has_neuron("missed").await);
@@ -550,7 +2446,10 @@ mod tests {
     #[tokio::test]
     async fn fn_get_current_neuron_statistics_should_return_some_value() {
         let net = Arc::new(new_network_fixture());
-        let neuron = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+        let neuron = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
         let id = neuron.get_id();
 
         assert!(net.get_current_neuron_status(&id).await.is_ok());
@@ -559,9 +2458,15 @@ mod tests {
     #[tokio::test]
     async fn should_connect_one_neuron_to_available_port_of_another_one() {
         let net = Arc::new(new_network_fixture());
-        let neuron1 = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+        let neuron1 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
         let src_id = neuron1.get_id();
-        let neuron2 = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+        let neuron2 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
         let dst_id = neuron2.get_id();
 
         let res = net.connect_neurons(&src_id, &dst_id, 0).await;
@@ -594,11 +2499,20 @@ mod tests {
     async fn should_not_connect_one_neuron_to_busy_port_of_another_one() {
         let net = Arc::new(new_network_fixture());
 
-        let neuron_alt = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+        let neuron_alt = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
         let alt_id = neuron_alt.get_id();
-        let neuron1 = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+        let neuron1 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
         let src_id = neuron1.get_id();
-        let neuron2 = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+        let neuron2 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
         let dst_id = neuron2.get_id();
 
         let res = net.connect_neurons(&src_id, &dst_id, 0).await;
@@ -612,11 +2526,14 @@ mod tests {
     async fn should_not_connect_one_neuron_to_missed_one() {
         let net = Arc::new(new_network_fixture());
 
-        let neuron1 = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+        let neuron1 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
         let src_id = neuron1.get_id();
         let dst_id = "M0Z555";
 
-        let res = net.connect_neurons(&src_id, &dst_id, 0).await;
+        let res = net.connect_neurons(&src_id, dst_id, 0).await;
         assert!(res.is_err());
     }
 
@@ -624,7 +2541,10 @@ mod tests {
     async fn should_not_allow_to_connect_self_if_only_one_dendrite_exists() {
         let net = Arc::new(new_network_fixture());
 
-        let neuron = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+        let neuron = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
         let id = neuron.get_id();
 
         let res = net.connect_neurons(&id, &id, 0).await;
@@ -636,7 +2556,12 @@ mod tests {
         let net = Arc::new(new_network_fixture());
 
         let neuron = net
-            .create_neuron(net.clone(), 1, gen_neuron_input_config_fixture(2))
+            .create_neuron(
+                net.clone(),
+                1,
+                gen_neuron_input_config_fixture(2),
+                NeuronKind::Excitatory,
+            )
             .await
             .unwrap();
         let id = neuron.get_id();
@@ -648,18 +2573,58 @@ mod tests {
     #[tokio::test]
     async fn should_set_correct_monitoring_mode_for_new_added_neuron() {
         let net = Arc::new(new_network_fixture());
-        let _n1 = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+        let _n1 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
         net.set_monitoring_mode(MonitoringMode::Monitoring).await;
 
-        let n2 = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+        let n2 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
         assert_eq!(n2.get_monitoring_mode().await, MonitoringMode::Monitoring);
     }
 
+    #[tokio::test]
+    async fn output_port_statuses_should_pass_monitoring_filter() {
+        let net = Arc::new(new_network_fixture());
+        let n = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+        assert!(net.setup_output(0, &n.get_id()).await.is_ok());
+
+        assert!(net.input(1, 0).await.is_ok());
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(net.pop_monitoring_store().await.is_empty());
+
+        net.set_monitoring_mode(MonitoringMode::Monitoring).await;
+        net.set_monitoring_filter(MonitoringFilter {
+            mask: StatusMask::NEURON,
+            ..MonitoringFilter::default()
+        })
+        .await;
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(net.input(1, 0).await.is_ok());
+        tokio::time::sleep(Duration::from_millis(1)).await;
+
+        let statuses = net.pop_monitoring_store().await;
+        assert!(!statuses.is_empty());
+        assert!(statuses
+            .iter()
+            .all(|status| matches!(status, Status::Neuron(_))));
+    }
+
     #[tokio::test]
     async fn should_store_monitoring_records_on_signal_operation() {
         let net = Arc::new(new_network_fixture());
         net.set_monitoring_mode(MonitoringMode::Monitoring).await;
-        let n = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+        let n = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
         assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
         assert!(net.setup_output(0, &n.get_id()).await.is_ok());
 
@@ -685,7 +2650,2037 @@ mod tests {
                     assert!(port_info.timestamp.timestamp().is_positive());
                     assert_eq!(port_info.hit_count, 1);
                 }
+                Status::Removal(_) => panic!("no neuron has been removed"),
+                Status::PortLagged(_) => panic!("no synapse lagged"),
             }
         }
     }
+
+    #[tokio::test]
+    async fn monitoring_stream_should_get_statuses_beyond_store_capacity() {
+        let net = Arc::new(
+            Network::with_runtime_cfg(RuntimeCfg {
+                monitoring_store: MonitoringStoreCfg {
+                    capacity: Some(1),
+                    ..MonitoringStoreCfg::default()
+                },
+                ..RuntimeCfg::default()
+            })
+            .unwrap(),
+        );
+        net.set_monitoring_mode(MonitoringMode::Monitoring).await;
+        let n = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+        assert!(net.setup_output(0, &n.get_id()).await.is_ok());
+        let mut stream = net.monitoring_stream();
+
+        assert!(net.input(1, 0).await.is_ok());
+
+        for _ in 0..3 {
+            assert!(
+                tokio::time::timeout(Duration::from_millis(20), stream.next())
+                    .await
+                    .unwrap()
+                    .is_some()
+            );
+        }
+        assert_eq!(net.pop_monitoring_store().await.len(), 1);
+        assert!(Network::with_runtime_cfg(RuntimeCfg {
+            monitoring_store: MonitoringStoreCfg {
+                stream_capacity: 0,
+                ..MonitoringStoreCfg::default()
+            },
+            ..RuntimeCfg::default()
+        })
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn monitoring_should_be_exported_as_json_lines_and_csv() {
+        let net = Arc::new(new_network_fixture());
+        net.set_monitoring_mode(MonitoringMode::Monitoring).await;
+        let n = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+
+        assert!(net.input(1, 0).await.is_ok());
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        let mut jsonl = vec![];
+        let count = net
+            .export_monitoring(&mut jsonl, ExportFormat::JsonLines)
+            .await
+            .unwrap();
+
+        let lines: Vec<&str> = std::str::from_utf8(&jsonl).unwrap().lines().collect();
+        assert!(count > 0);
+        assert_eq!(lines.len(), count);
+        assert!(lines
+            .iter()
+            .all(|line| serde_json::from_str::<Status>(line).is_ok()));
+
+        assert!(net.input(1, 0).await.is_ok());
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        let mut csv = vec![];
+        let count = net
+            .export_monitoring(&mut csv, ExportFormat::Csv)
+            .await
+            .unwrap();
+
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().next(), Some(STATUS_CSV_HEADER));
+        assert_eq!(csv.lines().count(), count + 1);
+    }
+
+    #[tokio::test]
+    async fn monitoring_filter_should_select_neurons_kinds_and_samples() {
+        let net = Arc::new(new_network_fixture());
+        net.set_monitoring_mode(MonitoringMode::Monitoring).await;
+        let n1 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        let n2 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n1.get_id(), 0).await.is_ok());
+        assert!(net.setup_input(1, &n2.get_id(), 0).await.is_ok());
+
+        net.monitor_only(&[&n2.get_id()]).await;
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(net.input_vec(&[1, 1]).await.is_ok());
+        tokio::time::sleep(Duration::from_millis(1)).await;
+
+        let statuses = net.pop_monitoring_store().await;
+        assert!(statuses
+            .iter()
+            .any(|status| matches!(status, Status::Port(_))));
+        assert!(statuses.iter().all(|status| match status {
+            Status::Neuron(info) => info.id == n2.get_id(),
+            _ => true,
+        }));
+
+        net.set_monitoring_filter(MonitoringFilter {
+            sample_every: 2,
+            mask: StatusMask::NEURON,
+            ..MonitoringFilter::default()
+        })
+        .await;
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        for _ in 0..4 {
+            assert!(net.input(1, 0).await.is_ok());
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        let statuses = net.pop_monitoring_store().await;
+        assert_eq!(statuses.len(), 2);
+        assert!(statuses
+            .iter()
+            .all(|status| matches!(status, Status::Neuron(info) if info.id == n1.get_id())));
+    }
+
+    #[tokio::test]
+    async fn full_monitoring_channel_should_be_handled_by_overflow_policy() {
+        for (monitoring_overflow, dropped, coalesced) in [
+            (MonitoringOverflow::Drop, 5, 0),
+            (MonitoringOverflow::Coalesce, 0, 4),
+        ] {
+            let net = Arc::new(
+                Network::with_runtime_cfg(RuntimeCfg {
+                    monitoring_overflow,
+                    ..RuntimeCfg::default()
+                })
+                .unwrap(),
+            );
+            let n = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+
+            // the store task does not get control, so the channel is filled by 5 statuses
+            for _ in 0..10 {
+                let _ = Neuron::send_monitoring_statistics(&n.get_id(), &n.get_core()).await;
+            }
+
+            let counters = net.get_monitoring_counters();
+            assert_eq!(counters.get_dropped(), dropped);
+            assert_eq!(counters.get_coalesced(), coalesced);
+        }
+    }
+
+    #[tokio::test]
+    async fn disconnected_neuron_should_not_receive_signals() {
+        for net in [
+            Arc::new(new_network_fixture()),
+            Arc::new(new_mailbox_network_fixture()),
+        ] {
+            let src = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            let dst = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            let (src_id, dst_id) = (src.get_id(), dst.get_id());
+            assert!(net.connect_neurons(&src_id, &dst_id, 0).await.is_ok());
+            assert!(net.setup_input(0, &src_id, 0).await.is_ok());
+            assert!(net.setup_output(0, &dst_id).await.is_ok());
+            let mut receiver = net.subscribe_output(0).await.unwrap();
+
+            assert!(net.disconnect_neurons(&dst_id, &dst_id, 0).await.is_err());
+            assert!(net.disconnect_neurons(&src_id, &dst_id, 0).await.is_ok());
+            assert!(net.disconnect_neurons(&src_id, &dst_id, 0).await.is_err());
+            assert!(dst.get_connections().await.is_empty());
+            assert!(net.get_routing_table().read().unwrap().is_empty());
+
+            assert!(net.input(2, 0).await.is_ok());
+            assert!(
+                tokio::time::timeout(Duration::from_millis(20), receiver.recv())
+                    .await
+                    .is_err()
+            );
+            assert!(net.connect_neurons(&src_id, &dst_id, 0).await.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn input_vec_should_feed_consecutive_ports() {
+        let net = Arc::new(new_network_fixture());
+        let n = net
+            .create_neuron(
+                net.clone(),
+                0,
+                gen_neuron_input_config_fixture(2),
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+        assert!(net.setup_input(1, &n.get_id(), 1).await.is_ok());
+        assert!(net.setup_output(0, &n.get_id()).await.is_ok());
+        let mut receiver = net.subscribe_output(0).await.unwrap();
+
+        assert!(net.input_vec(&[1, 1]).await.is_ok());
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), receiver.recv())
+                .await
+                .is_ok()
+        );
+        assert!(net.input_vec(&[1, 1, 1]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn output_port_stream_should_yield_signals_of_port() {
+        let net = Arc::new(new_network_fixture());
+        let n = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+        assert!(net.setup_output(0, &n.get_id()).await.is_ok());
+        let mut stream = net.output_port_stream(0).await.unwrap();
+
+        assert!(net.input(2, 0).await.is_ok());
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), stream.next())
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(net.output_port_stream(1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn output_stream_should_tag_signals_with_port() {
+        let net = Arc::new(new_network_fixture());
+        for port in 0..2 {
+            let n = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            assert!(net.setup_input(port, &n.get_id(), 0).await.is_ok());
+            assert!(net.setup_output(port, &n.get_id()).await.is_ok());
+        }
+        let mut stream = net.output_stream().await.unwrap();
+
+        assert!(net.input(2, 1).await.is_ok());
+        let (port, _) = tokio::time::timeout(Duration::from_millis(20), stream.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(port, 1);
+    }
+
+    #[tokio::test]
+    async fn input_pattern_should_feed_stimuli_with_interval() {
+        let net = Arc::new(new_network_fixture());
+        let n = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+        assert!(net.setup_output(0, &n.get_id()).await.is_ok());
+        let mut receiver = net.subscribe_output(0).await.unwrap();
+
+        let start = tokio::time::Instant::now();
+        let handle = net.input_pattern(
+            net.clone(),
+            vec![vec![1], vec![2], vec![3]],
+            Duration::from_millis(5),
+        );
+        assert!(handle.await.unwrap().is_ok());
+        assert!(start.elapsed() >= Duration::from_millis(10));
+        for _ in 0..3 {
+            assert!(receiver.recv().await.is_ok());
+        }
+
+        let handle = net.input_pattern(net.clone(), vec![vec![1, 1]], Duration::ZERO);
+        assert!(matches!(
+            handle.await.unwrap(),
+            Err(RnnError::PortNotFound(1))
+        ));
+    }
+
+    #[tokio::test]
+    async fn stepped_network_should_deliver_queued_inputs_on_step() {
+        let net = Arc::new(new_stepped_network_fixture());
+        let src = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        let dst = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net
+            .connect_neurons(&src.get_id(), &dst.get_id(), 0)
+            .await
+            .is_ok());
+        assert!(net.setup_input(0, &src.get_id(), 0).await.is_ok());
+        assert!(net.setup_output(0, &dst.get_id()).await.is_ok());
+
+        let hit_count = |id: String| {
+            let net = net.clone();
+            async move {
+                match net.get_current_neuron_status(&id).await.unwrap() {
+                    Status::Neuron(info) => info.hit_count,
+                    _ => panic!("neuron status expected"),
+                }
+            }
+        };
+
+        assert_eq!(net.input(2, 0).await.unwrap(), 0);
+        assert!(net.input(2, 1).await.is_err());
+        assert_eq!(hit_count(src.get_id()).await, 0);
+
+        let outputs = net.step().await.unwrap();
+        assert_eq!(outputs[&0].len(), 1);
+        assert_eq!(hit_count(src.get_id()).await, 1);
+        assert_eq!(hit_count(dst.get_id()).await, 1);
+
+        assert!(net.step().await.unwrap()[&0].is_empty());
+    }
+
+    #[tokio::test]
+    async fn in_flight_should_wake_waiter_when_settled() {
+        let in_flight = Arc::new(InFlight::default());
+        in_flight.add(2);
+        let waiter = tokio::spawn({
+            let in_flight = in_flight.clone();
+            async move { in_flight.settled().await }
+        });
+
+        in_flight.settle(1);
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        in_flight.settle(1);
+        assert!(time::timeout(Duration::from_millis(100), waiter)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn input_port_should_feed_several_neurons() {
+        let net = Arc::new(new_stepped_network_fixture());
+        let mut ids = vec![];
+        for port in 0..2 {
+            let id = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap()
+                .get_id();
+            assert!(net.setup_input(0, &id, 0).await.is_ok());
+            assert!(net.setup_output(port, &id).await.is_ok());
+            ids.push(id);
+        }
+
+        assert!(net.input(2, 0).await.is_ok());
+        let outputs = net.step().await.unwrap();
+        assert_eq!(outputs[&0].len(), 1);
+        assert_eq!(outputs[&1].len(), 1);
+        assert_eq!(net.get_in_flight().unwrap().get(), 0);
+
+        let config = net.get_config().await;
+        assert_eq!(config.inputs, 1);
+        assert!(config.validate().is_ok());
+        let copy = Arc::new(new_stepped_network_fixture());
+        assert!(copy.restore_config(copy.clone(), &config).await.is_ok());
+        assert_eq!(copy.get_config().await.links.len(), 4);
+
+        assert!(net.remove_neuron(&ids[0]).await.is_ok());
+        let port_core = net.input_interface.read().await[&0].clone();
+        assert_eq!(port_core.read().await.parties, vec![ids[1].clone()]);
+        assert!(net.free_input(0).await.is_ok());
+        assert!(net
+            .get_neuron(&ids[1])
+            .await
+            .unwrap()
+            .get_connections()
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn timestamps_should_be_taken_from_injected_clock() {
+        let origin = DateTime::<Utc>::UNIX_EPOCH;
+        let net = Arc::new(
+            Network::with_clock(RuntimeCfg::default(), Arc::new(TokioClock::new(origin))).unwrap(),
+        );
+        let n = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        tokio::time::advance(Duration::from_secs(2)).await;
+
+        assert_eq!(net.get_construction_log().await[0].timestamp, origin);
+        match net.get_current_neuron_status(&n.get_id()).await.unwrap() {
+            Status::Neuron(info) => {
+                assert_eq!(info.timestamp, origin + chrono::TimeDelta::seconds(2))
+            }
+            _ => panic!("neuron status expected"),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn signals_within_refractory_period_should_be_ignored() {
+        let net = Arc::new(
+            Network::with_clock(
+                RuntimeCfg {
+                    refractory_period: Duration::from_millis(50),
+                    ..RuntimeCfg::default()
+                },
+                Arc::new(TokioClock::new(Utc::now())),
+            )
+            .unwrap(),
+        );
+        let n = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+        let counts = || {
+            let net = net.clone();
+            let id = n.get_id();
+            async move {
+                match net.get_current_neuron_status(&id).await.unwrap() {
+                    Status::Neuron(info) => (info.reset_count, info.refractory_count),
+                    _ => panic!("neuron status expected"),
+                }
+            }
+        };
+
+        assert!(net.input(2, 0).await.is_ok());
+        time::sleep(Duration::from_millis(1)).await;
+        assert!(net.input(2, 0).await.is_ok());
+        time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(counts().await, (1, 1));
+
+        time::sleep(Duration::from_millis(60)).await;
+        assert!(net.input(2, 0).await.is_ok());
+        time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(counts().await, (2, 1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn leaky_accumulator_should_decay_toward_bias() {
+        let mut outputs = vec![];
+        for leak_time_constant in [None, Some(Duration::from_millis(10))] {
+            let net = Arc::new(
+                Network::with_clock(
+                    RuntimeCfg {
+                        leak_time_constant,
+                        ..RuntimeCfg::default()
+                    },
+                    Arc::new(TokioClock::new(Utc::now())),
+                )
+                .unwrap(),
+            );
+            let n = net
+                .create_neuron(
+                    net.clone(),
+                    1,
+                    vec![InputCfg::new(10, 10, 1).unwrap(); 2],
+                    NeuronKind::Excitatory,
+                )
+                .await
+                .unwrap();
+            assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+            assert!(net.setup_input(1, &n.get_id(), 1).await.is_ok());
+            assert!(net.setup_output(0, &n.get_id()).await.is_ok());
+            let mut receiver = net.subscribe_output(0).await.unwrap();
+
+            assert!(net.input(5, 0).await.is_ok());
+            time::sleep(Duration::from_millis(100)).await;
+            assert!(net.input(5, 1).await.is_ok());
+            outputs.push(receiver.recv().await.unwrap());
+        }
+
+        assert!(outputs[1] > 0);
+        assert!(outputs[1] < outputs[0]);
+    }
+
+    #[tokio::test]
+    async fn outbound_connections_of_inhibitory_neuron_should_be_negative() {
+        let neuron_cfg = |id: &str, kind: NeuronKind| NeuronCfg {
+            id: id.to_string(),
+            bias: 0,
+            input_configs: vec![InputCfg::new(3, 3, 2).unwrap(); 2],
+            lineage: None,
+            kind,
+        };
+        let config = NetworkCfg {
+            inputs: 0,
+            outputs: 0,
+            neurons: vec![
+                neuron_cfg("M0Z0", NeuronKind::Inhibitory),
+                neuron_cfg("M0Z1", NeuronKind::Excitatory),
+                neuron_cfg("M0Z2", NeuronKind::Excitatory),
+            ],
+            links: vec![
+                LinkCfg::Inner {
+                    src_id: String::from("M0Z0"),
+                    dst_id: String::from("M0Z1"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Inner {
+                    src_id: String::from("M0Z2"),
+                    dst_id: String::from("M0Z1"),
+                    dst_synapse_idx: 1,
+                },
+            ],
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
+        };
+        let net = Arc::new(new_network_fixture());
+        let ids = net.restore_config(net.clone(), &config).await.unwrap();
+
+        let dst = net.get_neuron(&ids["M0Z1"]).await.unwrap();
+        let weights: Vec<Weight> = dst
+            .get_config()
+            .await
+            .input_configs
+            .iter()
+            .map(|input_cfg| input_cfg.weight)
+            .collect();
+        assert_eq!(weights, vec![-2, 2]);
+        match net.get_current_neuron_status(&ids["M0Z0"]).await.unwrap() {
+            Status::Neuron(info) => assert_eq!(info.kind, NeuronKind::Inhibitory),
+            _ => panic!("neuron status expected"),
+        }
+        let restored = net.get_config().await;
+        assert_eq!(restored.neurons[0].kind, NeuronKind::Inhibitory);
+        assert_eq!(restored.neurons[1].kind, NeuronKind::Excitatory);
+    }
+
+    #[tokio::test]
+    async fn stepped_mode_should_not_support_broadcast_routing() {
+        assert!(Network::with_runtime_cfg(RuntimeCfg {
+            simulation: SimulationMode::Stepped,
+            ..RuntimeCfg::default()
+        })
+        .is_err());
+        assert!(new_network_fixture().step().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn event_driven_network_should_pass_signals_through_the_queue() {
+        let net = Arc::new(new_event_driven_network_fixture());
+        let src = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        let dst = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net
+            .connect_neurons(&src.get_id(), &dst.get_id(), 0)
+            .await
+            .is_ok());
+        assert!(net.setup_input(0, &src.get_id(), 0).await.is_ok());
+        assert!(net.setup_output(0, &dst.get_id()).await.is_ok());
+        let mut receiver = net.subscribe_output(0).await.unwrap();
+
+        assert!(net.input(2, 0).await.is_ok());
+        let signal = time::timeout(Duration::from_millis(100), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(signal > 0);
+
+        let report = net.tasks().await;
+        assert_eq!(report.event_workers, RuntimeCfg::default().event_workers);
+        assert_eq!(report.mailbox_readers, 0);
+        assert!(net.get_event_queue().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn event_driven_network_should_be_stepped() {
+        let net = Arc::new(
+            Network::with_runtime_cfg(RuntimeCfg {
+                routing: RoutingBackend::EventDriven,
+                simulation: SimulationMode::Stepped,
+                ..RuntimeCfg::default()
+            })
+            .unwrap(),
+        );
+        let src = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        let dst = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net
+            .connect_neurons(&src.get_id(), &dst.get_id(), 0)
+            .await
+            .is_ok());
+        assert!(net.setup_input(0, &src.get_id(), 0).await.is_ok());
+        assert!(net.setup_output(0, &dst.get_id()).await.is_ok());
+
+        assert!(net.input(2, 0).await.is_ok());
+        assert_eq!(net.step().await.unwrap()[&0].len(), 1);
+    }
+
+    #[tokio::test]
+    async fn sharded_network_should_group_neurons_onto_shard_workers() {
+        let net = Arc::new(new_sharded_network_fixture());
+        let src = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        let mut dst_ids = vec![];
+        for _ in 0..20 {
+            let dst = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            assert!(net
+                .connect_neurons(&src.get_id(), &dst.get_id(), 0)
+                .await
+                .is_ok());
+            dst_ids.push(dst.get_id());
+        }
+        assert!(net.setup_input(0, &src.get_id(), 0).await.is_ok());
+        assert!(net.setup_output(0, &dst_ids[19]).await.is_ok());
+        let mut receiver = net.subscribe_output(0).await.unwrap();
+
+        assert!(net.input(2, 0).await.is_ok());
+        let signal = time::timeout(Duration::from_millis(100), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(signal > 0);
+
+        let report = net.tasks().await;
+        assert_eq!(report.shard_workers, RuntimeCfg::default().shards);
+        assert_eq!(report.mailbox_readers, 0);
+        assert_eq!(report.synapse_listeners, 1);
+    }
+
+    #[tokio::test]
+    async fn full_shard_queue_should_drop_and_count_signals() {
+        let net = Arc::new(
+            Network::with_runtime_cfg(RuntimeCfg {
+                routing: RoutingBackend::Sharded,
+                shards: 1,
+                mailbox_capacity: 1,
+                ..RuntimeCfg::default()
+            })
+            .unwrap(),
+        );
+        let src = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        for _ in 0..3 {
+            let dst = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            assert!(net
+                .connect_neurons(&src.get_id(), &dst.get_id(), 0)
+                .await
+                .is_ok());
+        }
+        assert!(net.setup_input(0, &src.get_id(), 0).await.is_ok());
+
+        // the worker can't take signals while the fired neuron routes them
+        assert!(net.input(2, 0).await.is_ok());
+        time::sleep(Duration::from_millis(10)).await;
+
+        assert_eq!(net.get_routing_counters().get_dropped(), 2);
+    }
+
+    #[tokio::test]
+    async fn event_driven_network_should_have_workers() {
+        assert!(Network::with_runtime_cfg(RuntimeCfg {
+            routing: RoutingBackend::EventDriven,
+            event_workers: 0,
+            ..RuntimeCfg::default()
+        })
+        .is_err());
+        assert!(Network::with_runtime_cfg(RuntimeCfg {
+            routing: RoutingBackend::Sharded,
+            shards: 0,
+            ..RuntimeCfg::default()
+        })
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn full_input_port_should_reject_signal_with_error_policy() {
+        let net = Arc::new(
+            Network::with_runtime_cfg(RuntimeCfg {
+                channels: ChannelCapacities {
+                    input_port: 1,
+                    ..ChannelCapacities::default()
+                },
+                backpressure: BackpressurePolicy::Error,
+                ..RuntimeCfg::default()
+            })
+            .unwrap(),
+        );
+        let n = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+
+        assert!(net.input(2, 0).await.is_ok());
+        let error = net.input(2, 0).await.unwrap_err();
+        assert!(error.to_string().contains("ChannelFull"));
+
+        time::sleep(Duration::from_millis(10)).await;
+        assert!(net.input(2, 0).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn full_input_port_should_block_only_its_sender() {
+        let net = Arc::new(
+            Network::with_runtime_cfg(RuntimeCfg {
+                channels: ChannelCapacities {
+                    input_port: 1,
+                    ..ChannelCapacities::default()
+                },
+                backpressure: BackpressurePolicy::Block,
+                ..RuntimeCfg::default()
+            })
+            .unwrap(),
+        );
+        let n1 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        let n2 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n1.get_id(), 0).await.is_ok());
+        assert!(net.setup_input(1, &n2.get_id(), 0).await.is_ok());
+
+        // the listener takes the first signal and waits for the core,
+        // the second one fills the port
+        let core = n1.get_core();
+        let w_core = core.write().await;
+        assert!(net.input(2, 0).await.is_ok());
+        time::sleep(Duration::from_millis(10)).await;
+        assert!(net.input(2, 0).await.is_ok());
+
+        let blocked = tokio::spawn({
+            let net = net.clone();
+            async move { net.input(2, 0).await.is_ok() }
+        });
+        time::sleep(Duration::from_millis(10)).await;
+        assert!(!blocked.is_finished());
+        assert!(time::timeout(Duration::from_millis(100), net.input(2, 1))
+            .await
+            .is_ok_and(|result| result.is_ok()));
+
+        drop(w_core);
+        assert!(time::timeout(Duration::from_millis(100), blocked)
+            .await
+            .unwrap()
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn channels_should_have_capacity() {
+        assert!(Network::with_runtime_cfg(RuntimeCfg {
+            channels: ChannelCapacities {
+                axon: 0,
+                ..ChannelCapacities::default()
+            },
+            ..RuntimeCfg::default()
+        })
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn free_input_should_release_neuron_synapse() {
+        let net = Arc::new(new_network_fixture());
+        let n = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+
+        assert!(net.free_input(0).await.is_ok());
+        assert!(net.free_input(0).await.is_err());
+        assert!(net.input(2, 0).await.is_err());
+        assert!(n.get_connections().await.is_empty());
+        tokio::task::yield_now().await;
+        assert_eq!(n.tasks().await.synapse_listeners, 0);
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn tasks_should_report_live_tasks_by_role() {
+        for (net, mailbox_readers, synapse_listeners) in [
+            (Arc::new(new_network_fixture()), 0, 2),
+            (Arc::new(new_mailbox_network_fixture()), 2, 1),
+        ] {
+            let src = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            let dst = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            assert!(net
+                .connect_neurons(&src.get_id(), &dst.get_id(), 0)
+                .await
+                .is_ok());
+            assert!(net.setup_input(0, &src.get_id(), 0).await.is_ok());
+            assert!(net.setup_output(0, &dst.get_id()).await.is_ok());
+
+            assert_eq!(
+                net.tasks().await,
+                TaskReport {
+                    command_loops: 2,
+                    mailbox_readers,
+                    event_workers: 0,
+                    shard_workers: 0,
+                    synapse_listeners,
+                    output_port_pumps: 1,
+                    monitoring: 1,
+                }
+            );
+
+            src.shutdown().await;
+            tokio::task::yield_now().await;
+            let report = net.tasks().await;
+            assert_eq!(report.command_loops, 1);
+            assert_eq!(report.mailbox_readers, mailbox_readers / 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn output_port_pumps_should_stop_with_their_ports() {
+        let net = Arc::new(new_network_fixture());
+        let mut ids = vec![];
+        for port in 0..3 {
+            let id = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap()
+                .get_id();
+            assert!(net.setup_output(port, &id).await.is_ok());
+            ids.push(id);
+        }
+        assert_eq!(net.tasks().await.output_port_pumps, 3);
+
+        assert!(net.free_output(0).await.is_ok());
+        assert!(net.remove_neuron(&ids[1]).await.is_ok());
+        time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(net.tasks().await.output_port_pumps, 1);
+
+        net.clear().await;
+        time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(net.tasks().await.output_port_pumps, 0);
+    }
+
+    #[tokio::test]
+    async fn paused_network_should_process_buffered_signals_on_resume() {
+        let net = Arc::new(new_network_fixture());
+        let n = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+        assert!(net.setup_output(0, &n.get_id()).await.is_ok());
+        let mut receiver = net.subscribe_output(0).await.unwrap();
+
+        net.pause();
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(n.is_paused().await);
+        assert!(net.input(2, 0).await.is_ok());
+        assert!(net.input(2, 0).await.is_ok());
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), receiver.recv())
+                .await
+                .is_err()
+        );
+
+        net.resume();
+        let signal = tokio::time::timeout(Duration::from_millis(100), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(signal > 0);
+        assert!(!n.is_paused().await);
+    }
+
+    #[tokio::test]
+    async fn addressed_command_should_change_only_specified_neuron() {
+        let net = Arc::new(new_network_fixture());
+        let n1 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        let n2 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+
+        net.command_neuron(
+            &n1.get_id(),
+            NeuronCommand::SwitchMonitoringMode(MonitoringMode::Monitoring),
+        );
+        tokio::time::sleep(Duration::from_millis(1)).await;
+
+        assert_eq!(n1.get_monitoring_mode().await, MonitoringMode::Monitoring);
+        assert_eq!(n2.get_monitoring_mode().await, MonitoringMode::None);
+    }
+
+    #[tokio::test]
+    async fn update_synapse_should_keep_connection() {
+        let net = Arc::new(new_network_fixture());
+        let n = net
+            .create_neuron(
+                net.clone(),
+                0,
+                vec![InputCfg::new(3, 3, 1).unwrap()],
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+
+        net.update_synapse(&n.get_id(), 0, Some(7), Some(5)).await;
+        net.update_bias(&n.get_id(), 2).await;
+        let cfg = net.query_config(&n.get_id()).await.unwrap();
+        assert_eq!(cfg.bias, 2);
+        assert_eq!(cfg.input_configs, vec![InputCfg::new(5, 3, 7).unwrap()]);
+        assert_eq!(n.get_connections().await.len(), 1);
+
+        assert!(net
+            .send_confirmed(NeuronCommand::For(
+                n.get_id(),
+                Box::new(NeuronCommand::UpdateSynapse {
+                    port: 1,
+                    weight: Some(1),
+                    capacity: None,
+                }),
+            ))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn to_dot_should_describe_live_topology() {
+        let net = Arc::new(new_network_fixture());
+        let src_id = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap()
+            .get_id();
+        let dst_id = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap()
+            .get_id();
+        assert!(net.connect_neurons(&src_id, &dst_id, 0).await.is_ok());
+        assert!(net.setup_output(0, &dst_id).await.is_ok());
+
+        let dot = net.to_dot().await;
+
+        assert_eq!(dot, net.get_config().await.to_dot());
+        assert!(dot.contains(&format!("\"{src_id}\" -> \"{dst_id}\"")));
+        assert!(dot.contains(&format!("\"{dst_id}\" -> \"O0\"")));
+    }
+
+    #[tokio::test]
+    async fn analyze_should_report_dead_end_before_signals() {
+        let net = Arc::new(new_network_fixture());
+        let src_id = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap()
+            .get_id();
+        let dst_id = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap()
+            .get_id();
+        assert!(net.setup_input(0, &src_id, 0).await.is_ok());
+        assert!(net.connect_neurons(&src_id, &dst_id, 0).await.is_ok());
+
+        let report = net.analyze().await.unwrap();
+
+        assert!(report.is_acyclic());
+        assert!(report.unreachable.is_empty());
+        assert_eq!(report.dead_ends, vec![dst_id.clone()]);
+
+        assert!(net.setup_output(0, &dst_id).await.is_ok());
+        assert!(net.analyze().await.unwrap().is_sound());
+    }
+
+    #[tokio::test]
+    async fn doctor_should_combine_structural_and_runtime_findings() {
+        let net = Arc::new(new_network_fixture());
+        let id = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap()
+            .get_id();
+        assert!(net.setup_input(0, &id, 0).await.is_ok());
+
+        let report = net.doctor().await;
+        assert!(!report.is_healthy());
+        assert_eq!(report.findings[0].subject, id);
+
+        assert!(net.setup_output(0, &id).await.is_ok());
+        let report = net.doctor().await;
+        assert!(report.is_healthy());
+        assert!(report.findings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_query_config_of_live_neuron() {
+        let net = Arc::new(new_network_fixture());
+        let n = net
+            .create_neuron(
+                net.clone(),
+                3,
+                gen_neuron_input_config_fixture(2),
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            net.query_config(&n.get_id()).await.unwrap(),
+            n.get_config().await
+        );
+        assert!(net.query_config("M0Z100").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn send_confirmed_should_return_ids_of_neurons_applied_command() {
+        let net = Arc::new(new_network_fixture());
+        let n1 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        let n2 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+
+        let mut confirmed = net.send_confirmed(NeuronCommand::Pause).await.unwrap();
+        confirmed.sort();
+        assert_eq!(confirmed, vec![n1.get_id(), n2.get_id()]);
+        assert!(n1.is_paused().await);
+        assert!(n2.is_paused().await);
+
+        let confirmed = net
+            .send_confirmed(NeuronCommand::For(
+                n2.get_id(),
+                Box::new(NeuronCommand::Resume),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(confirmed, vec![n2.get_id()]);
+        assert!(n1.is_paused().await);
+        assert!(!n2.is_paused().await);
+
+        assert!(net
+            .send_confirmed(NeuronCommand::For(
+                String::from("M0Z100"),
+                Box::new(NeuronCommand::Resume),
+            ))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn pause_neuron_should_pause_only_specified_neuron() {
+        let net = Arc::new(new_network_fixture());
+        let n1 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+        let n2 = net
+            .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+            .await
+            .unwrap();
+
+        net.pause_neuron(&n2.get_id());
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(!n1.is_paused().await);
+        assert!(n2.is_paused().await);
+
+        net.resume_neuron(&n2.get_id());
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(!n2.is_paused().await);
+    }
+
+    #[tokio::test]
+    async fn reset_should_clear_runtime_state_and_monitoring_store() {
+        let net = Arc::new(new_network_fixture());
+        net.set_monitoring_mode(MonitoringMode::Monitoring).await;
+        let n = net
+            .create_neuron(
+                net.clone(),
+                3,
+                gen_neuron_input_config_fixture(2),
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap();
+        assert!(net.setup_input(0, &n.get_id(), 0).await.is_ok());
+        assert!(net.setup_input(1, &n.get_id(), 1).await.is_ok());
+        assert!(net.input(1, 0).await.is_ok());
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(!net.pop_monitoring_store().await.is_empty());
+        assert!(net.input(1, 0).await.is_ok());
+        tokio::time::sleep(Duration::from_millis(1)).await;
+
+        net.reset().await;
+        tokio::time::sleep(Duration::from_millis(1)).await;
+
+        assert!(net.pop_monitoring_store().await.is_empty());
+        match net.get_current_neuron_status(&n.get_id()).await.unwrap() {
+            Status::Neuron(info) => {
+                assert_eq!(info.accumulator, 3);
+                assert_eq!(info.hit_count, 0);
+                assert_eq!(info.reset_count, 0);
+                assert_eq!(info.dendrite_hit_count, 0);
+            }
+            _ => panic!("neuron status expected"),
+        }
+    }
+
+    mod for_neuron_groups {
+        use super::*;
+
+        #[tokio::test]
+        async fn should_create_group_of_neurons() {
+            let net = Arc::new(new_network_fixture());
+
+            let group = net
+                .create_group(
+                    net.clone(),
+                    "hidden",
+                    3,
+                    1,
+                    gen_neuron_input_config_fixture(2),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(group.ids.len(), 3);
+            assert_eq!(net.len().await, 3);
+            assert_eq!(net.get_group("hidden").await, Some(group));
+            assert!(net
+                .create_group(net.clone(), "hidden", 1, 1, vec![])
+                .await
+                .is_err());
+        }
+
+        #[tokio::test]
+        async fn should_connect_groups_with_fixed_fan_in() {
+            let net = Arc::new(new_network_fixture());
+            let src = net
+                .create_group(net.clone(), "src", 3, 1, vec![])
+                .await
+                .unwrap();
+            let dst = net
+                .create_group(net.clone(), "dst", 2, 1, gen_neuron_input_config_fixture(2))
+                .await
+                .unwrap();
+
+            let count = net
+                .connect_groups("src", "dst", &GroupConnection::FixedFanIn(2))
+                .await
+                .unwrap();
+
+            assert_eq!(count, 4);
+            let connections = net
+                .get_neuron(&dst.ids[1])
+                .await
+                .unwrap()
+                .get_connections()
+                .await;
+            assert_eq!(connections.get(&0), Some(&src.ids[1]));
+            assert_eq!(connections.get(&1), Some(&src.ids[2]));
+
+            // all dendrites are busy now
+            let count = net
+                .connect_groups("src", "dst", &GroupConnection::FixedFanIn(1))
+                .await
+                .unwrap();
+            assert_eq!(count, 0);
+        }
+
+        #[tokio::test]
+        async fn should_fail_to_connect_unknown_group() {
+            let net = Arc::new(new_network_fixture());
+            net.create_group(net.clone(), "src", 1, 1, vec![])
+                .await
+                .unwrap();
+
+            assert!(net
+                .connect_groups("src", "missed", &GroupConnection::FixedFanIn(1))
+                .await
+                .is_err());
+        }
+
+        #[tokio::test]
+        async fn bulk_operations_should_affect_group_neurons_only() {
+            let net = Arc::new(new_network_fixture());
+            let group = net
+                .create_group(net.clone(), "group", 2, 1, vec![])
+                .await
+                .unwrap();
+            let other = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+
+            assert!(net
+                .set_group_monitoring_mode("group", MonitoringMode::Monitoring)
+                .await
+                .is_ok());
+            assert!(net
+                .configure_group("group", gen_neuron_input_config_fixture(3))
+                .await
+                .is_ok());
+
+            let neuron = net.get_neuron(&group.ids[0]).await.unwrap();
+            assert_eq!(
+                neuron.get_monitoring_mode().await,
+                MonitoringMode::Monitoring
+            );
+            assert_eq!(neuron.get_config().await.input_configs.len(), 3);
+            assert_eq!(other.get_monitoring_mode().await, MonitoringMode::None);
+
+            assert!(net.remove_neuron(&group.ids[1]).await.is_ok());
+            assert_eq!(net.get_group("group").await.unwrap().ids.len(), 1);
+            assert!(net.remove_group("group").await.is_ok());
+            assert_eq!(net.len().await, 1);
+            assert!(net.get_group_names().await.is_empty());
+        }
+    }
+
+    mod for_checkpoints {
+        use super::*;
+
+        async fn two_neurons_chain_fixture(net: Arc<Network>) -> (String, String) {
+            let src_id = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap()
+                .get_id();
+            let dst_id = net
+                .create_neuron(
+                    net.clone(),
+                    1,
+                    gen_neuron_input_config_fixture(2),
+                    NeuronKind::Excitatory,
+                )
+                .await
+                .unwrap()
+                .get_id();
+            assert!(net.connect_neurons(&src_id, &dst_id, 1).await.is_ok());
+            assert!(net.setup_input(0, &src_id, 0).await.is_ok());
+            assert!(net.setup_output(0, &dst_id).await.is_ok());
+            (src_id, dst_id)
+        }
+
+        #[tokio::test]
+        async fn composite_should_be_flattened_when_config_restored() {
+            let inner_net = Arc::new(new_network_fixture());
+            two_neurons_chain_fixture(inner_net.clone()).await;
+            let config = NetworkCfg {
+                inputs: 1,
+                outputs: 1,
+                neurons: vec![],
+                links: vec![
+                    LinkCfg::Input {
+                        input_port: 0,
+                        dst_id: String::from("M0Z0"),
+                        dst_synapse_idx: 0,
+                    },
+                    LinkCfg::Output {
+                        src_id: String::from("M0Z0"),
+                        output_port: 0,
+                    },
+                ],
+                lineage: None,
+                composites: vec![CompositeCfg {
+                    id: String::from("M0Z0"),
+                    network: inner_net.get_config().await,
+                    axon_port: 0,
+                }],
+                labels: LabelMap::default(),
+                seed: None,
+            };
+            let net = Arc::new(new_network_fixture());
+
+            assert!(net.restore_config(net.clone(), &config).await.is_ok());
+            assert_eq!(net.len().await, 2);
+
+            let mut receiver = net.subscribe_output(0).await.unwrap();
+            assert!(net.input(2, 0).await.is_ok());
+            let signal = time::timeout(Duration::from_millis(100), receiver.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(signal > 0);
+        }
+
+        #[tokio::test]
+        async fn composite_input_should_fan_out_into_inner_neurons() {
+            // the inner input port feeds both inner neurons, the first one drives the axon
+            let inner_net = Arc::new(new_network_fixture());
+            let mut inner_ids = vec![];
+            for _ in 0..2 {
+                let id = inner_net
+                    .create_neuron(inner_net.clone(), 1, vec![], NeuronKind::Excitatory)
+                    .await
+                    .unwrap()
+                    .get_id();
+                assert!(inner_net.setup_input(0, &id, 0).await.is_ok());
+                inner_ids.push(id);
+            }
+            assert!(inner_net.setup_output(0, &inner_ids[0]).await.is_ok());
+            let config = NetworkCfg {
+                inputs: 1,
+                outputs: 1,
+                neurons: vec![],
+                links: vec![
+                    LinkCfg::Input {
+                        input_port: 0,
+                        dst_id: String::from("M0Z0"),
+                        dst_synapse_idx: 0,
+                    },
+                    LinkCfg::Output {
+                        src_id: String::from("M0Z0"),
+                        output_port: 0,
+                    },
+                ],
+                lineage: None,
+                composites: vec![CompositeCfg {
+                    id: String::from("M0Z0"),
+                    network: inner_net.get_config().await,
+                    axon_port: 0,
+                }],
+                labels: LabelMap::default(),
+                seed: None,
+            };
+            let net = Arc::new(new_network_fixture());
+
+            assert!(net.restore_config(net.clone(), &config).await.is_ok());
+            assert_eq!(net.get_input_fan_out(0).await, 2);
+            let input_links = net
+                .get_config()
+                .await
+                .links
+                .into_iter()
+                .filter(|link| matches!(link, LinkCfg::Input { input_port: 0, .. }))
+                .count();
+            assert_eq!(input_links, 2);
+
+            let mut receiver = net.subscribe_output(0).await.unwrap();
+            assert!(net.input(2, 0).await.is_ok());
+            let signal = time::timeout(Duration::from_millis(100), receiver.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(signal > 0);
+        }
+
+        #[tokio::test]
+        async fn get_config_should_describe_current_topology() {
+            let net = Arc::new(new_network_fixture());
+            let (src_id, dst_id) = two_neurons_chain_fixture(net.clone()).await;
+
+            let config = net.get_config().await;
+
+            assert_eq!(config.inputs, 1);
+            assert_eq!(config.outputs, 1);
+            assert_eq!(config.neurons.len(), 2);
+            assert_eq!(
+                config.links,
+                vec![
+                    LinkCfg::Input {
+                        input_port: 0,
+                        dst_id: src_id.clone(),
+                        dst_synapse_idx: 0,
+                    },
+                    LinkCfg::Inner {
+                        src_id: src_id.clone(),
+                        dst_id: dst_id.clone(),
+                        dst_synapse_idx: 1,
+                    },
+                    LinkCfg::Output {
+                        src_id: dst_id.clone(),
+                        output_port: 0,
+                    },
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn rollback_should_restore_checkpointed_topology_in_place() {
+            let net = Arc::new(new_network_fixture());
+            let (src_id, dst_id) = two_neurons_chain_fixture(net.clone()).await;
+            net.checkpoint("before-pruning").await;
+            let expected_config = net.get_config().await;
+
+            assert!(net.free_output(0).await.is_ok());
+            assert!(net.remove_neuron(&dst_id).await.is_ok());
+            assert!(net
+                .create_neuron(net.clone(), 3, vec![], NeuronKind::Excitatory)
+                .await
+                .is_ok());
+            assert_ne!(net.get_config().await, expected_config);
+
+            assert!(net.rollback(net.clone(), "before-pruning").await.is_ok());
+
+            assert_eq!(net.get_config().await, expected_config);
+            assert!(net.has_neuron(&src_id).await);
+            assert!(net.has_neuron(&dst_id).await);
+
+            let receiver = net.get_output_receiver(0).await.unwrap();
+            let mut w_receiver = receiver.write().await;
+            assert!(net.input(1, 0).await.is_ok());
+            let signal = time::timeout(Duration::from_millis(100), w_receiver.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(signal, 5);
+        }
+
+        #[tokio::test]
+        async fn rollback_should_fail_for_unknown_checkpoint() {
+            let net = Arc::new(new_network_fixture());
+            two_neurons_chain_fixture(net.clone()).await;
+
+            assert!(net.rollback(net.clone(), "missed").await.is_err());
+            assert_eq!(net.len().await, 2);
+        }
+
+        #[tokio::test]
+        async fn rollback_to_invalid_checkpoint_should_keep_topology() {
+            let net = Arc::new(new_network_fixture());
+            let (src_id, dst_id) = two_neurons_chain_fixture(net.clone()).await;
+            let before = net.get_config().await;
+            let mut invalid = before.clone();
+            invalid.links.push(LinkCfg::Inner {
+                src_id: src_id.clone(),
+                dst_id: String::from("M0Z555"),
+                dst_synapse_idx: 0,
+            });
+            net.checkpoints
+                .write()
+                .await
+                .insert(String::from("invalid"), invalid);
+
+            assert!(net.rollback(net.clone(), "invalid").await.is_err());
+            assert_eq!(net.get_config().await, before);
+            assert!(net.get_neuron(&src_id).await.is_some());
+            assert!(net.get_neuron(&dst_id).await.is_some());
+            assert!(net.input(1, 0).await.is_ok());
+        }
+
+        #[tokio::test]
+        async fn checkpoint_should_survive_save_and_load() {
+            let net = Arc::new(new_network_fixture());
+            two_neurons_chain_fixture(net.clone()).await;
+            net.checkpoint("saved").await;
+            let path = std::env::temp_dir().join(format!(
+                "runen-{}-{}-checkpoint.json",
+                std::process::id(),
+                net.get_id()
+            ));
+
+            assert!(net.save_checkpoint("saved", &path).await.is_ok());
+            assert!(net.load_checkpoint("loaded", &path).await.is_ok());
+            std::fs::remove_file(&path).unwrap();
+
+            assert_eq!(
+                net.get_checkpoint("loaded").await,
+                net.get_checkpoint("saved").await
+            );
+            assert_eq!(net.get_checkpoint_names().await, vec!["loaded", "saved"]);
+        }
+
+        #[tokio::test]
+        async fn restore_should_bring_back_dynamic_state_of_snapshot() {
+            let net = Arc::new(new_network_fixture());
+            let (_, dst_id) = two_neurons_chain_fixture(net.clone()).await;
+            let neuron = net.get_neuron(&dst_id).await.unwrap();
+            let mut state = neuron.get_state().await;
+            state.accumulator = 5;
+            state.reset_count = 3;
+            state.input_hits = BTreeSet::from([0]);
+            state.synapse_capacities = BTreeMap::from([(0, 0), (1, 9)]);
+            neuron.set_state(&state).await;
+
+            let snapshot = net.snapshot().await;
+            let copy = Arc::new(new_network_fixture());
+            assert!(copy.restore(copy.clone(), &snapshot).await.is_ok());
+            let restored = copy.snapshot().await;
+
+            assert_eq!(restored.config.links.len(), 3);
+            assert_eq!(restored.neurons.len(), 2);
+            let restored_state = &restored.neurons[1];
+            assert_eq!(restored_state.accumulator, 5);
+            assert_eq!(restored_state.reset_count, 3);
+            assert_eq!(restored_state.input_hits, BTreeSet::from([0]));
+            // the capacity is limited by the configured one
+            assert_eq!(
+                restored_state.synapse_capacities,
+                BTreeMap::from([(0, 0), (1, 2)])
+            );
+        }
+
+        #[tokio::test]
+        async fn lineage_should_be_restored_with_checkpoint() {
+            let net = Arc::new(new_network_fixture());
+            two_neurons_chain_fixture(net.clone()).await;
+            let parent = net.get_config().await;
+            let lineage = NetworkLineage::descend(&[&parent], "manual", vec![]);
+            net.set_lineage(Some(lineage.clone())).await;
+            net.checkpoint("descendant").await;
+            let path = std::env::temp_dir().join(format!(
+                "runen-{}-{}-descendant.json",
+                std::process::id(),
+                net.get_id()
+            ));
+            assert!(net.save_checkpoint("descendant", &path).await.is_ok());
+
+            let copy = Arc::new(new_network_fixture());
+            assert!(copy.load_checkpoint("descendant", &path).await.is_ok());
+            std::fs::remove_file(&path).unwrap();
+            assert!(copy.rollback(copy.clone(), "descendant").await.is_ok());
+
+            assert_eq!(copy.get_lineage().await, Some(lineage.clone()));
+            assert_eq!(copy.get_config().await.lineage, Some(lineage));
+        }
+
+        #[tokio::test]
+        async fn seed_should_be_restored_with_config() {
+            let net = Arc::new(new_network_fixture());
+            two_neurons_chain_fixture(net.clone()).await;
+            net.set_seed(Some(42)).await;
+
+            let copy = Arc::new(new_network_fixture());
+            assert!(copy
+                .restore_config(copy.clone(), &net.get_config().await)
+                .await
+                .is_ok());
+
+            assert_eq!(copy.get_config().await.seed, Some(42));
+            assert_eq!(copy.get_rng_provider().await, RngProvider::new(42));
+            assert!(new_network_fixture().get_config().await.seed.is_none());
+        }
+
+        #[tokio::test]
+        async fn labels_should_be_restored_with_config() {
+            let net = Arc::new(new_network_fixture());
+            two_neurons_chain_fixture(net.clone()).await;
+            assert!(net
+                .set_labels(LabelMap::one_hot(&["yes", "no"]))
+                .await
+                .is_err());
+            assert!(net.set_labels(LabelMap::one_hot(&["yes"])).await.is_ok());
+
+            let copy = Arc::new(new_network_fixture());
+            assert!(copy
+                .restore_config(copy.clone(), &net.get_config().await)
+                .await
+                .is_ok());
+
+            let labels = copy.get_labels().await;
+            assert_eq!(labels, LabelMap::one_hot(&["yes"]));
+            assert_eq!(labels.decode(&[5]), Some(String::from("yes")));
+        }
+
+        #[tokio::test]
+        async fn installed_neurons_should_keep_lineage_of_origin() {
+            let origin = Arc::new(new_network_fixture());
+            let (src_id, dst_id) = two_neurons_chain_fixture(origin.clone()).await;
+            origin.checkpoint("origin").await;
+            let path = std::env::temp_dir().join(format!(
+                "runen-{}-{}-lineage.json",
+                std::process::id(),
+                origin.get_id()
+            ));
+            assert!(origin.save_checkpoint("origin", &path).await.is_ok());
+
+            let copy = Arc::new(new_network_fixture());
+            assert!(copy.load_checkpoint("origin", &path).await.is_ok());
+            std::fs::remove_file(&path).unwrap();
+            assert!(copy.rollback(copy.clone(), "origin").await.is_ok());
+
+            let config = copy.get_config().await;
+            assert!(config.neurons.iter().all(|neuron| neuron.id != src_id));
+            let markers: Vec<&str> = config
+                .neurons
+                .iter()
+                .map(|neuron| neuron.lineage_marker())
+                .collect();
+            assert_eq!(markers, vec![src_id.as_str(), dst_id.as_str()]);
+
+            // rollback in place does not produce lineage markers
+            assert!(origin.rollback(origin.clone(), "origin").await.is_ok());
+            assert!(origin
+                .get_config()
+                .await
+                .neurons
+                .iter()
+                .all(|neuron| neuron.lineage.is_none()));
+        }
+
+        #[tokio::test]
+        async fn replay_should_repeat_rollback() {
+            let origin = Arc::new(new_network_fixture());
+            two_neurons_chain_fixture(origin.clone()).await;
+            origin.checkpoint("initial").await;
+            assert!(origin
+                .remove_neuron(&format!("{}Z1", origin.get_id()))
+                .await
+                .is_ok());
+            assert!(origin.rollback(origin.clone(), "initial").await.is_ok());
+
+            let copy = Arc::new(new_network_fixture());
+            let records = origin.get_construction_log().await;
+            assert!(copy
+                .replay_construction_log(copy.clone(), &records)
+                .await
+                .is_ok());
+
+            assert_eq!(copy.len().await, 2);
+            assert_eq!(copy.get_config().await.links.len(), 3);
+        }
+    }
+
+    mod for_construction_log {
+        use super::*;
+
+        #[tokio::test]
+        async fn should_record_only_successful_structural_mutations() {
+            let net = Arc::new(new_network_fixture());
+            let n1 = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            let n2 = net
+                .create_neuron(net.clone(), 2, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+
+            assert!(net
+                .connect_neurons(&n1.get_id(), &n2.get_id(), 0)
+                .await
+                .is_ok());
+            assert!(net
+                .connect_neurons(&n1.get_id(), &n2.get_id(), 0)
+                .await
+                .is_err());
+            assert!(net.remove_neuron("missed").await.is_err());
+
+            let log = net.get_construction_log().await;
+            assert_eq!(log.len(), 3);
+            assert_eq!(
+                log[1].event,
+                NetworkEvent::NeuronCreated {
+                    id: n2.get_id(),
+                    bias: 2,
+                    input_configs: vec![InputCfg::new(1, 1, 1).unwrap()],
+                    kind: NeuronKind::Excitatory,
+                }
+            );
+            assert_eq!(
+                log[2].event,
+                NetworkEvent::NeuronsConnected {
+                    src_id: n1.get_id(),
+                    dst_id: n2.get_id(),
+                    dst_synapse_idx: 0,
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn replay_should_reconstruct_topology_in_another_network() {
+            let origin = Arc::new(new_network_fixture());
+            let n0 = origin
+                .create_neuron(origin.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            let removed = origin
+                .create_neuron(origin.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            let n2 = origin
+                .create_neuron(
+                    origin.clone(),
+                    1,
+                    gen_neuron_input_config_fixture(1),
+                    NeuronKind::Excitatory,
+                )
+                .await
+                .unwrap();
+            assert!(origin.remove_neuron(&removed.get_id()).await.is_ok());
+            assert!(origin
+                .configure_neuron(&n2.get_id(), gen_neuron_input_config_fixture(2))
+                .await
+                .is_ok());
+            assert!(origin
+                .connect_neurons(&n0.get_id(), &n2.get_id(), 1)
+                .await
+                .is_ok());
+            assert!(origin.setup_input(0, &n0.get_id(), 0).await.is_ok());
+            assert!(origin.setup_output(0, &n2.get_id()).await.is_ok());
+
+            let records = origin.get_construction_log().await;
+            let copy = Arc::new(new_network_fixture());
+            let ids = copy
+                .replay_construction_log(copy.clone(), &records)
+                .await
+                .unwrap();
+
+            assert_eq!(copy.len().await, 2);
+            assert_eq!(ids.len(), 2);
+            assert!(!ids.contains_key(&removed.get_id()));
+            let n2_copy_id = ids.get(&n2.get_id()).unwrap();
+            if let Status::Neuron(info) = copy.get_current_neuron_status(n2_copy_id).await.unwrap()
+            {
+                assert_eq!(info.dendrite_count, 2);
+                assert_eq!(info.dendrite_connected_count, 1);
+            } else {
+                panic!("Incorrect state format");
+            }
+            assert!(copy.get_output_receiver(0).await.is_some());
+            assert_eq!(copy.get_construction_log().await.len(), records.len());
+        }
+
+        #[tokio::test]
+        async fn replay_should_reproduce_parameters_of_another_network() {
+            let origin = Arc::new(new_network_fixture());
+            let src_id = origin
+                .create_neuron(origin.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap()
+                .get_id();
+            let dst_id = origin
+                .create_neuron(
+                    origin.clone(),
+                    1,
+                    gen_neuron_input_config_fixture(2),
+                    NeuronKind::Excitatory,
+                )
+                .await
+                .unwrap()
+                .get_id();
+            assert!(origin.connect_neurons(&src_id, &dst_id, 1).await.is_ok());
+            assert!(origin.setup_output(0, &dst_id).await.is_ok());
+            origin.update_synapse(&dst_id, 1, Some(3), None).await;
+            origin.update_bias(&src_id, 4).await;
+            assert!(origin
+                .set_labels(LabelMap::one_hot(&["spike"]))
+                .await
+                .is_ok());
+            origin.set_seed(Some(7)).await;
+            let lineage = NetworkLineage::descend(&[&origin.get_config().await], "manual", vec![]);
+            origin.set_lineage(Some(lineage.clone())).await;
+
+            let records = origin.get_construction_log().await;
+            let copy = Arc::new(new_network_fixture());
+            let ids = copy
+                .replay_construction_log(copy.clone(), &records)
+                .await
+                .unwrap();
+
+            let src_cfg = copy.query_config(&ids[&src_id]).await.unwrap();
+            let dst_cfg = copy.query_config(&ids[&dst_id]).await.unwrap();
+            assert_eq!(src_cfg.bias, 4);
+            assert_eq!(dst_cfg.input_configs[1].weight, 3);
+            assert_eq!(copy.get_labels().await, LabelMap::one_hot(&["spike"]));
+            assert_eq!(copy.get_config().await.seed, Some(7));
+            assert_eq!(copy.get_lineage().await, Some(lineage));
+            assert_eq!(copy.get_construction_log().await.len(), records.len());
+        }
+
+        #[tokio::test]
+        async fn replay_should_keep_inhibitory_neurons_negative() {
+            let origin = Arc::new(new_network_fixture());
+            let src_id = origin
+                .create_neuron(origin.clone(), 1, vec![], NeuronKind::Inhibitory)
+                .await
+                .unwrap()
+                .get_id();
+            let dst_id = origin
+                .create_neuron(
+                    origin.clone(),
+                    1,
+                    gen_neuron_input_config_fixture(1),
+                    NeuronKind::Excitatory,
+                )
+                .await
+                .unwrap()
+                .get_id();
+            assert!(origin.connect_neurons(&src_id, &dst_id, 0).await.is_ok());
+
+            let records = origin.get_construction_log().await;
+            let copy = Arc::new(new_network_fixture());
+            let ids = copy
+                .replay_construction_log(copy.clone(), &records)
+                .await
+                .unwrap();
+
+            let src_cfg = copy.query_config(&ids[&src_id]).await.unwrap();
+            let dst_cfg = copy.query_config(&ids[&dst_id]).await.unwrap();
+            assert_eq!(src_cfg.kind, NeuronKind::Inhibitory);
+            assert!(dst_cfg.input_configs[0].weight < 0);
+        }
+    }
+
+    mod for_mailbox_routing {
+        use super::*;
+
+        #[tokio::test]
+        async fn should_not_create_network_with_zero_mailbox_capacity() {
+            let result = Network::with_runtime_cfg(RuntimeCfg {
+                routing: RoutingBackend::Mailbox,
+                mailbox_capacity: 0,
+                ..RuntimeCfg::default()
+            });
+
+            assert!(result.is_err());
+        }
+
+        #[tokio::test]
+        async fn only_mailbox_network_should_provide_neuron_mailbox() {
+            let net = Arc::new(new_network_fixture());
+            let neuron = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            assert!(neuron.get_mailbox().is_none());
+
+            let net = Arc::new(new_mailbox_network_fixture());
+            let neuron = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap();
+            assert!(neuron.get_mailbox().is_some());
+        }
+
+        #[tokio::test]
+        async fn should_connect_neurons_through_routing_table() {
+            let net = Arc::new(new_mailbox_network_fixture());
+            let src_id = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap()
+                .get_id();
+            let dst_id = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap()
+                .get_id();
+
+            assert!(net.connect_neurons(&src_id, &dst_id, 0).await.is_ok());
+            assert!(net.connect_neurons(&src_id, &dst_id, 0).await.is_err());
+
+            {
+                let r_routing_table = net.routing_table.read().unwrap();
+                let routes = r_routing_table.get_routes(&src_id);
+                assert_eq!(routes.len(), 1);
+                assert_eq!(routes[0].dst_id, dst_id);
+                assert_eq!(routes[0].dst_synapse_idx, 0);
+            }
+
+            if let Status::Neuron(info) = net.get_current_neuron_status(&src_id).await.unwrap() {
+                assert_eq!(info.receiver_count, 0);
+            } else {
+                panic!("Incorrect state format");
+            }
+            if let Status::Neuron(info) = net.get_current_neuron_status(&dst_id).await.unwrap() {
+                assert_eq!(info.dendrite_connected_count, 1);
+            } else {
+                panic!("Incorrect state format");
+            }
+        }
+
+        #[tokio::test]
+        async fn should_not_allow_to_connect_self_if_only_one_dendrite_exists() {
+            let net = Arc::new(new_mailbox_network_fixture());
+            let id = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap()
+                .get_id();
+
+            assert!(net.connect_neurons(&id, &id, 0).await.is_err());
+            assert!(net.routing_table.read().unwrap().is_empty());
+        }
+
+        #[tokio::test]
+        async fn should_propagate_signal_through_mailboxes() {
+            let net = Arc::new(new_mailbox_network_fixture());
+            let src_id = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap()
+                .get_id();
+            let dst_id = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap()
+                .get_id();
+
+            assert!(net.connect_neurons(&src_id, &dst_id, 0).await.is_ok());
+            assert!(net.setup_input(0, &src_id, 0).await.is_ok());
+            assert!(net.setup_output(0, &dst_id).await.is_ok());
+
+            let receiver = net.get_output_receiver(0).await.unwrap();
+            let mut w_receiver = receiver.write().await;
+
+            assert!(net.input(1, 0).await.is_ok());
+
+            let signal = time::timeout(Duration::from_millis(100), w_receiver.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(signal, 2);
+
+            if let Status::Neuron(info) = net.get_current_neuron_status(&dst_id).await.unwrap() {
+                assert_eq!(info.hit_count, 1);
+                assert_eq!(info.reset_count, 1);
+            } else {
+                panic!("Incorrect state format");
+            }
+        }
+
+        #[tokio::test]
+        async fn full_mailbox_should_drop_and_count_signal_keeping_axon() {
+            let net = Arc::new(new_mailbox_network_fixture());
+            let src_id = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap()
+                .get_id();
+            assert!(net.setup_input(0, &src_id, 0).await.is_ok());
+            assert!(net.setup_output(0, &src_id).await.is_ok());
+            // the mailbox nobody reads
+            let (mailbox, _mailbox_receiver) = mpsc::channel(1);
+            net.routing_table.write().unwrap().add_route(
+                &src_id,
+                Route {
+                    dst_id: String::from("M0Z9"),
+                    dst_synapse_idx: 0,
+                    mailbox: Some(mailbox),
+                },
+            );
+
+            let mut receiver = net.subscribe_output(0).await.unwrap();
+            for _ in 0..3 {
+                assert!(net.input(1, 0).await.is_ok());
+                let signal = time::timeout(Duration::from_millis(100), receiver.recv())
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(signal, 2);
+            }
+
+            assert_eq!(net.get_routing_counters().get_dropped(), 2);
+        }
+
+        #[tokio::test]
+        async fn full_mailbox_should_reject_signal_with_error_policy() {
+            let net = Arc::new(
+                Network::with_runtime_cfg(RuntimeCfg {
+                    routing: RoutingBackend::Mailbox,
+                    backpressure: BackpressurePolicy::Error,
+                    ..RuntimeCfg::default()
+                })
+                .unwrap(),
+            );
+            let src_id = net
+                .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+                .await
+                .unwrap()
+                .get_id();
+            assert!(net.setup_input(0, &src_id, 0).await.is_ok());
+            assert!(net.setup_output(0, &src_id).await.is_ok());
+            let (mailbox, _mailbox_receiver) = mpsc::channel(1);
+            net.routing_table.write().unwrap().add_route(
+                &src_id,
+                Route {
+                    dst_id: String::from("M0Z9"),
+                    dst_synapse_idx: 0,
+                    mailbox: Some(mailbox.clone()),
+                },
+            );
+
+            let mut receiver = net.subscribe_output(0).await.unwrap();
+            for _ in 0..2 {
+                assert!(net.input(1, 0).await.is_ok());
+                assert!(time::timeout(Duration::from_millis(100), receiver.recv())
+                    .await
+                    .is_ok());
+            }
+
+            // the rejected signal is not counted as dropped
+            assert_eq!(net.get_routing_counters().get_dropped(), 0);
+            assert_eq!(mailbox.capacity(), 0);
+        }
+    }
 }
@@ -0,0 +1,583 @@
+//! Registry of named networks with their life cycle: networks are created from
+//! configs, stopped (all neurons are shut down and the config is kept) and
+//! restarted from the kept config. The registry hands out `Arc<Network>` handles
+//! which stay valid across stop and restart, but receivers of output ports have
+//! to be subscribed again after restart.
+//!
+//! Rarely used networks can be archived: the config is written to storage and the
+//! network is dropped, only its metadata stays in the registry. Restoring builds
+//! a new network from the archive, so handles taken before archival are stale.
+//!
+//! For high availability the running network can have a warm standby replica
+//! which periodically receives snapshot deltas of the primary and is promoted
+//! by failover, after that the registry hands out the replica for the name.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time::{self, MissedTickBehavior};
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use crate::rnn::common::network_cfg::NetworkCfg;
+use crate::rnn::common::rnn_error::RnnError;
+
+use super::network::Network;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NetworkState {
+    Running,
+    Stopped,
+    Archived,
+}
+
+/// Metadata of the archived network kept in the registry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveInfo {
+    /// The file with config of the network in json format
+    pub path: PathBuf,
+    pub fingerprint: String,
+    pub inputs: usize,
+    pub outputs: usize,
+    pub neurons: usize,
+    pub archived_at: DateTime<Utc>,
+}
+
+struct RegistryEntry {
+    /// None while the network is archived
+    network: Option<Arc<Network>>,
+
+    /// The config which network is restarted from, None while archived
+    config: Option<NetworkCfg>,
+    state: NetworkState,
+    archive: Option<ArchiveInfo>,
+    standby: Option<Standby>,
+}
+
+/// Warm standby replica with the task streaming deltas of the primary into it.
+struct Standby {
+    network: Arc<Network>,
+    sync: JoinHandle<()>,
+    cancellation: CancellationToken,
+
+    /// The time of the primary's snapshot applied last
+    synced_at: Arc<RwLock<DateTime<Utc>>>,
+}
+
+impl Standby {
+    /// Stop streaming after the current sync is applied and return the replica.
+    async fn stop(self) -> Arc<Network> {
+        self.cancellation.cancel();
+        if let Err(err) = self.sync.await {
+            warn!(id = %self.network.get_id(), %err, "standby sync task failed");
+        }
+        self.network
+    }
+}
+
+#[derive(Default)]
+pub struct NetworkRegistry {
+    entries: RwLock<BTreeMap<String, RegistryEntry>>,
+}
+
+impl NetworkRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the running network from config and register it by name.
+    pub async fn create(
+        &self,
+        name: &str,
+        config: &NetworkCfg,
+    ) -> Result<Arc<Network>, Box<dyn Error>> {
+        if self.entries.read().await.contains_key(name) {
+            return Err(Box::new(RnnError::NetworkAlreadyExists(name.to_string())));
+        }
+        let network = Arc::new(Network::new()?);
+        network.restore_config(network.clone(), config).await?;
+        self.insert(name, network.clone()).await?;
+        Ok(network)
+    }
+
+    /// Register already running network by name.
+    pub async fn insert(&self, name: &str, network: Arc<Network>) -> Result<(), Box<dyn Error>> {
+        let config = network.get_config().await;
+        let mut w_entries = self.entries.write().await;
+        if w_entries.contains_key(name) {
+            return Err(Box::new(RnnError::NetworkAlreadyExists(name.to_string())));
+        }
+        w_entries.insert(
+            name.to_string(),
+            RegistryEntry {
+                network: Some(network),
+                config: Some(config),
+                state: NetworkState::Running,
+                archive: None,
+                standby: None,
+            },
+        );
+        Ok(())
+    }
+
+    /// The handle for signals input and output. None for archived networks.
+    pub async fn get(&self, name: &str) -> Option<Arc<Network>> {
+        self.entries
+            .read()
+            .await
+            .get(name)
+            .and_then(|entry| entry.network.clone())
+    }
+
+    pub async fn get_names(&self) -> Vec<String> {
+        self.entries.read().await.keys().cloned().collect()
+    }
+
+    pub async fn get_state(&self, name: &str) -> Option<NetworkState> {
+        self.entries.read().await.get(name).map(|entry| entry.state)
+    }
+
+    pub async fn get_archive_info(&self, name: &str) -> Option<ArchiveInfo> {
+        self.entries
+            .read()
+            .await
+            .get(name)
+            .and_then(|entry| entry.archive.clone())
+    }
+
+    /// The warm standby replica of the network.
+    pub async fn get_standby(&self, name: &str) -> Option<Arc<Network>> {
+        self.entries
+            .read()
+            .await
+            .get(name)
+            .and_then(|entry| entry.standby.as_ref())
+            .map(|standby| standby.network.clone())
+    }
+
+    /// The time of the primary's state which the standby replica has caught up with.
+    pub async fn get_standby_synced_at(&self, name: &str) -> Option<DateTime<Utc>> {
+        let synced_at = self
+            .entries
+            .read()
+            .await
+            .get(name)
+            .and_then(|entry| entry.standby.as_ref())
+            .map(|standby| standby.synced_at.clone())?;
+        let synced_at = *synced_at.read().await;
+        Some(synced_at)
+    }
+
+    /// Keep current config of the network and shut down all its neurons and ports.
+    pub async fn stop(&self, name: &str) -> Result<(), Box<dyn Error>> {
+        let mut w_entries = self.entries.write().await;
+        let entry = w_entries
+            .get_mut(name)
+            .ok_or_else(|| Box::new(RnnError::NetworkNotFound(name.to_string())))?;
+        if let Some(standby) = entry.standby.take() {
+            standby.stop().await.clear().await;
+        }
+        if let (NetworkState::Running, Some(network)) = (entry.state, &entry.network) {
+            entry.config = Some(network.get_config().await);
+            network.clear().await;
+            entry.state = NetworkState::Stopped;
+        }
+        Ok(())
+    }
+
+    /// Install the kept config into the stopped network. The running network
+    /// is stopped first, so its dynamic state is reset. The archived network
+    /// is restored from its archive.
+    pub async fn restart(&self, name: &str) -> Result<(), Box<dyn Error>> {
+        if self.get_state(name).await == Some(NetworkState::Archived) {
+            self.restore(name).await?;
+            return Ok(());
+        }
+        self.stop(name).await?;
+        let mut w_entries = self.entries.write().await;
+        let entry = w_entries
+            .get_mut(name)
+            .ok_or_else(|| Box::new(RnnError::NetworkNotFound(name.to_string())))?;
+        if let (Some(network), Some(config)) = (&entry.network, &entry.config) {
+            network.restore_config(network.clone(), config).await?;
+            entry.state = NetworkState::Running;
+        }
+        Ok(())
+    }
+
+    /// Stop the network, write its config into the file in json format and drop
+    /// the network keeping only its metadata. Archiving of the archived network
+    /// only returns its metadata.
+    pub async fn archive(&self, name: &str, path: &Path) -> Result<ArchiveInfo, Box<dyn Error>> {
+        self.stop(name).await?;
+        let mut w_entries = self.entries.write().await;
+        let entry = w_entries
+            .get_mut(name)
+            .ok_or_else(|| Box::new(RnnError::NetworkNotFound(name.to_string())))?;
+        let Some(config) = &entry.config else {
+            return entry.archive.clone().ok_or_else(|| {
+                Box::new(RnnError::ExpectedDataNotPresent(name.to_string())) as Box<dyn Error>
+            });
+        };
+
+        serde_json::to_writer(File::create(path)?, config)?;
+        let info = ArchiveInfo {
+            path: path.to_path_buf(),
+            fingerprint: config.fingerprint(),
+            inputs: config.inputs,
+            outputs: config.outputs,
+            neurons: config.neurons.len(),
+            archived_at: Utc::now(),
+        };
+        entry.network = None;
+        entry.config = None;
+        entry.state = NetworkState::Archived;
+        entry.archive = Some(info.clone());
+        Ok(info)
+    }
+
+    /// Build the running network from the archive of the archived network.
+    /// For other networks the current handle is returned.
+    pub async fn restore(&self, name: &str) -> Result<Arc<Network>, Box<dyn Error>> {
+        let mut w_entries = self.entries.write().await;
+        let entry = w_entries
+            .get_mut(name)
+            .ok_or_else(|| Box::new(RnnError::NetworkNotFound(name.to_string())))?;
+        if let Some(network) = &entry.network {
+            return Ok(network.clone());
+        }
+        let info = entry
+            .archive
+            .as_ref()
+            .ok_or_else(|| Box::new(RnnError::ExpectedDataNotPresent(name.to_string())))?;
+
+        let config: NetworkCfg = serde_json::from_reader(File::open(&info.path)?)?;
+        if config.fingerprint() != info.fingerprint {
+            return Err(Box::new(RnnError::ExpectedDataNotPresent(format!(
+                "config with fingerprint {} in {}",
+                info.fingerprint,
+                info.path.display()
+            ))));
+        }
+        let network = Arc::new(Network::new()?);
+        network.restore_config(network.clone(), &config).await?;
+        entry.network = Some(network.clone());
+        entry.config = Some(config);
+        entry.state = NetworkState::Running;
+        entry.archive = None;
+        Ok(network)
+    }
+
+    /// Start the warm standby replica of the running network. The replica is built
+    /// from the snapshot of the primary and every period receives the delta of its
+    /// weights and dynamic state of neurons, so on failover the replica lags behind
+    /// the primary at most for the period plus the time of applying the delta.
+    /// The replica gets no inputs until failover. Running standby is replaced.
+    pub async fn start_standby(
+        &self,
+        name: &str,
+        period: Duration,
+    ) -> Result<Arc<Network>, Box<dyn Error>> {
+        let mut w_entries = self.entries.write().await;
+        let entry = w_entries
+            .get_mut(name)
+            .ok_or_else(|| Box::new(RnnError::NetworkNotFound(name.to_string())))?;
+        let (NetworkState::Running, Some(primary), false) =
+            (entry.state, &entry.network, period.is_zero())
+        else {
+            return Err(Box::new(RnnError::NotSupportedArgValue));
+        };
+        if let Some(standby) = entry.standby.take() {
+            standby.stop().await.clear().await;
+        }
+
+        let replica = Arc::new(Network::with_runtime_cfg(primary.get_runtime_cfg())?);
+        let mut base = primary.snapshot().await;
+        replica.restore(replica.clone(), &base).await?;
+        let synced_at = Arc::new(RwLock::new(base.timestamp));
+        let cancellation = CancellationToken::new();
+
+        let sync = tokio::spawn({
+            let primary = primary.clone();
+            let replica = replica.clone();
+            let synced_at = synced_at.clone();
+            let cancellation = cancellation.clone();
+            async move {
+                let mut interval = time::interval(period);
+                interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                loop {
+                    tokio::select! {
+                        _ = cancellation.cancelled() => break,
+                        _ = interval.tick() => {}
+                    }
+                    let snapshot = primary.snapshot().await;
+                    let delta = snapshot.delta(&base);
+                    if !delta.is_empty() {
+                        if let Err(err) = replica.apply_delta(replica.clone(), &delta).await {
+                            warn!(id = %replica.get_id(), %err, "standby is not synced");
+                            continue;
+                        }
+                    }
+                    *synced_at.write().await = snapshot.timestamp;
+                    base = snapshot;
+                }
+            }
+        });
+
+        entry.standby = Some(Standby {
+            network: replica.clone(),
+            sync,
+            cancellation,
+            synced_at,
+        });
+        Ok(replica)
+    }
+
+    /// Stop streaming into the standby replica and shut it down.
+    pub async fn stop_standby(&self, name: &str) -> Result<(), Box<dyn Error>> {
+        let standby = self
+            .entries
+            .write()
+            .await
+            .get_mut(name)
+            .ok_or_else(|| Box::new(RnnError::NetworkNotFound(name.to_string())))?
+            .standby
+            .take();
+        if let Some(standby) = standby {
+            standby.stop().await.clear().await;
+        }
+        Ok(())
+    }
+
+    /// Promote the standby replica after failure of the primary: the registry hands
+    /// out the replica for the name from now on, so inputs are routed into it, and
+    /// the failed primary is shut down. Returns the handle of the new primary.
+    pub async fn failover(&self, name: &str) -> Result<Arc<Network>, Box<dyn Error>> {
+        let mut w_entries = self.entries.write().await;
+        let entry = w_entries
+            .get_mut(name)
+            .ok_or_else(|| Box::new(RnnError::NetworkNotFound(name.to_string())))?;
+        let standby = entry.standby.take().ok_or_else(|| {
+            Box::new(RnnError::ExpectedDataNotPresent(format!(
+                "standby of {name}"
+            )))
+        })?;
+
+        let replica = standby.stop().await;
+        if let Some(failed) = entry.network.replace(replica.clone()) {
+            failed.clear().await;
+        }
+        entry.config = Some(replica.get_config().await);
+        entry.state = NetworkState::Running;
+        Ok(replica)
+    }
+
+    /// Stop the network and forget it. Returns the handle unless the network
+    /// is archived, its archive is left in storage.
+    pub async fn remove(&self, name: &str) -> Result<Option<Arc<Network>>, Box<dyn Error>> {
+        self.stop(name).await?;
+        self.entries
+            .write()
+            .await
+            .remove(name)
+            .map(|entry| entry.network)
+            .ok_or_else(|| Box::new(RnnError::NetworkNotFound(name.to_string())) as Box<dyn Error>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time;
+
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::snapshot::NeuronState;
+    use crate::rnn::layouts::layered_builder::{ConnectionStrategy, Layer, LayeredNetworkBuilder};
+
+    fn config_fixture() -> NetworkCfg {
+        LayeredNetworkBuilder::new(1)
+            .layer(
+                Layer {
+                    width: 1,
+                    bias: 0,
+                    input_cfg: InputCfg::new(3, 3, 1).unwrap(),
+                },
+                ConnectionStrategy::Dense,
+            )
+            .build()
+            .unwrap()
+    }
+
+    async fn assert_signal_passes(network: &Network) {
+        let mut receiver = network.subscribe_output(0).await.unwrap();
+        assert!(network.input(2, 0).await.is_ok());
+        let signal = time::timeout(Duration::from_millis(100), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(signal > 0);
+    }
+
+    #[tokio::test]
+    async fn should_create_networks_by_unique_names() {
+        let registry = NetworkRegistry::new();
+
+        assert!(registry.create("xor", &config_fixture()).await.is_ok());
+        assert!(registry.create("and", &config_fixture()).await.is_ok());
+        assert!(registry.create("xor", &config_fixture()).await.is_err());
+
+        assert_eq!(registry.get_names().await, vec!["and", "xor"]);
+        assert_eq!(registry.get_state("xor").await, Some(NetworkState::Running));
+        assert_signal_passes(&registry.get("xor").await.unwrap()).await;
+    }
+
+    #[tokio::test]
+    async fn stopped_network_should_be_restarted_from_kept_config() {
+        let registry = NetworkRegistry::new();
+        let network = registry.create("net", &config_fixture()).await.unwrap();
+
+        assert!(registry.stop("net").await.is_ok());
+        assert_eq!(registry.get_state("net").await, Some(NetworkState::Stopped));
+        assert!(network.is_empty().await);
+        assert!(network.input(2, 0).await.is_err());
+
+        assert!(registry.restart("net").await.is_ok());
+        assert_eq!(registry.get_state("net").await, Some(NetworkState::Running));
+        assert_eq!(network.len().await, 1);
+        assert_signal_passes(&network).await;
+    }
+
+    #[tokio::test]
+    async fn should_remove_network() {
+        let registry = NetworkRegistry::new();
+        registry.create("net", &config_fixture()).await.unwrap();
+
+        assert!(registry.remove("net").await.is_ok());
+        assert!(registry.get("net").await.is_none());
+        assert!(registry.remove("net").await.is_err());
+        assert!(registry.stop("net").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn archived_network_should_be_restored_on_demand() {
+        let registry = NetworkRegistry::new();
+        let network = registry
+            .create("archived", &config_fixture())
+            .await
+            .unwrap();
+        let config = network.get_config().await;
+        let path = std::env::temp_dir().join(format!(
+            "runen-{}-{}-archived_network_should_be_restored_on_demand.json",
+            std::process::id(),
+            network.get_id()
+        ));
+
+        let info = registry.archive("archived", &path).await.unwrap();
+        assert_eq!(info.fingerprint, config.fingerprint());
+        assert_eq!((info.inputs, info.outputs, info.neurons), (1, 1, 1));
+        assert_eq!(
+            registry.get_state("archived").await,
+            Some(NetworkState::Archived)
+        );
+        assert_eq!(registry.get_archive_info("archived").await, Some(info));
+        assert!(registry.get("archived").await.is_none());
+        assert!(network.is_empty().await);
+        assert_eq!(registry.get_names().await, vec!["archived"]);
+
+        let restored = registry.restore("archived").await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            registry.get_state("archived").await,
+            Some(NetworkState::Running)
+        );
+        assert!(registry.get_archive_info("archived").await.is_none());
+        assert!(!Arc::ptr_eq(&restored, &network));
+        assert_eq!(restored.len().await, 1);
+        assert_signal_passes(&registry.get("archived").await.unwrap()).await;
+    }
+
+    #[tokio::test]
+    async fn should_not_restore_network_from_changed_archive() {
+        let registry = NetworkRegistry::new();
+        let network = registry.create("net", &config_fixture()).await.unwrap();
+        let path = std::env::temp_dir().join(format!(
+            "runen-{}-{}-should_not_restore_network_from_changed_archive.json",
+            std::process::id(),
+            network.get_id()
+        ));
+        registry.archive("net", &path).await.unwrap();
+
+        let mut config = config_fixture();
+        config.neurons[0].bias = 1;
+        serde_json::to_writer(File::create(&path).unwrap(), &config).unwrap();
+        assert!(registry.restore("net").await.is_err());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            registry.get_state("net").await,
+            Some(NetworkState::Archived)
+        );
+    }
+
+    #[tokio::test]
+    async fn standby_should_take_over_with_synced_state() {
+        let registry = NetworkRegistry::new();
+        let primary = registry.create("net", &config_fixture()).await.unwrap();
+        let replica = registry
+            .start_standby("net", Duration::from_millis(10))
+            .await
+            .unwrap();
+        assert!(Arc::ptr_eq(
+            &registry.get_standby("net").await.unwrap(),
+            &replica
+        ));
+
+        assert_signal_passes(&primary).await;
+        time::sleep(Duration::from_millis(50)).await;
+        let primary_state = primary.snapshot().await.neurons[0].clone();
+        let replica_state = replica.snapshot().await.neurons[0].clone();
+        assert_eq!(
+            NeuronState {
+                id: primary_state.id.clone(),
+                ..replica_state
+            },
+            primary_state
+        );
+        assert!(registry.get_standby_synced_at("net").await.is_some());
+
+        let promoted = registry.failover("net").await.unwrap();
+        assert!(Arc::ptr_eq(&promoted, &replica));
+        assert!(Arc::ptr_eq(&registry.get("net").await.unwrap(), &replica));
+        assert!(registry.get_standby("net").await.is_none());
+        assert!(primary.is_empty().await);
+        assert_signal_passes(&promoted).await;
+        assert!(registry.failover("net").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn standby_should_be_shut_down_with_primary() {
+        let registry = NetworkRegistry::new();
+        registry.create("net", &config_fixture()).await.unwrap();
+        let replica = registry
+            .start_standby("net", Duration::from_millis(10))
+            .await
+            .unwrap();
+
+        assert!(registry.stop("net").await.is_ok());
+        assert!(registry.get_standby("net").await.is_none());
+        assert!(replica.is_empty().await);
+        assert!(registry
+            .start_standby("net", Duration::from_millis(10))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::rng::SeededRng;
+
+/// Named population of network's neurons created with the same parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NeuronGroup {
+    pub name: String,
+
+    /// Ids of group's neurons in creation order
+    pub ids: Vec<String>,
+}
+
+/// The way neurons of the source group are connected to neurons of the destination one.
+/// Every link occupies the next free dendrite of the destination neuron; links which
+/// do not find free dendrite and links of neuron to itself are skipped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GroupConnection {
+    /// Every destination neuron receives signals from `fan_in` source neurons
+    /// taken in round-robin order starting from the neuron with the same index.
+    FixedFanIn(usize),
+
+    /// Every pair of source and destination neurons is connected with probability `p`.
+    /// The same seed produces the same links.
+    Probability { p: f64, seed: u64 },
+}
+
+impl GroupConnection {
+    /// Pairs of (source idx, destination idx) to connect.
+    pub fn pairs(&self, src_len: usize, dst_len: usize) -> Vec<(usize, usize)> {
+        match self {
+            GroupConnection::FixedFanIn(fan_in) => (0..dst_len)
+                .flat_map(|dst| {
+                    (0..(*fan_in).min(src_len)).map(move |shift| ((dst + shift) % src_len, dst))
+                })
+                .collect(),
+            GroupConnection::Probability { p, seed } => {
+                let mut rng = SeededRng::new(*seed);
+                (0..dst_len)
+                    .flat_map(|dst| (0..src_len).map(move |src| (src, dst)))
+                    .filter(|_| rng.gen_bool(*p))
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_fan_in_should_take_sources_in_round_robin_order() {
+        assert_eq!(
+            GroupConnection::FixedFanIn(2).pairs(3, 2),
+            vec![(0, 0), (1, 0), (1, 1), (2, 1)]
+        );
+        assert_eq!(
+            GroupConnection::FixedFanIn(5).pairs(2, 1),
+            vec![(0, 0), (1, 0)]
+        );
+    }
+
+    #[test]
+    fn probability_should_be_reproducible_by_seed() {
+        let connection = GroupConnection::Probability { p: 0.5, seed: 1 };
+        assert_eq!(connection.pairs(10, 10), connection.pairs(10, 10));
+
+        let all = GroupConnection::Probability { p: 1.0, seed: 1 };
+        assert_eq!(all.pairs(3, 3).len(), 9);
+        let none = GroupConnection::Probability { p: 0.0, seed: 1 };
+        assert!(none.pairs(3, 3).is_empty());
+    }
+}
@@ -0,0 +1,462 @@
+//! Best-effort import of PyNN-style network descriptions (populations, projections
+//! and their parameters) in yaml or json into NetworkCfg, for migration of small
+//! spiking models. Spike sources (PyNN `SpikeSource*`, Brian2 `SpikeGeneratorGroup`
+//! and `PoissonGroup`) become input ports, other populations become neurons, and
+//! populations recording spikes drive output ports labeled by the population.
+//! Parameters without runen counterparts are reported as warnings.
+//!
+//! ```yaml
+//! populations:
+//!   - label: stimulus
+//!     size: 2
+//!     cell_type: SpikeSourcePoisson
+//!     parameters: { rate: 20.0 }
+//!   - label: exc
+//!     size: 3
+//!     cell_type: IF_curr_exp
+//!     parameters: { i_offset: 1.0, tau_m: 20.0 }
+//!     record: [spikes]
+//! projections:
+//!   - pre: stimulus
+//!     post: exc
+//!     connector: { type: AllToAll }
+//!     synapse: { weight: 2.0, delay: 1.0 }
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::input_cfg::InputCfg;
+use crate::rnn::common::network_cfg::{LabelMap, LinkCfg, NetworkCfg, NeuronCfg, NeuronKind};
+use crate::rnn::common::rng::RngProvider;
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::signal::{Signal, Weight};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Population {
+    pub label: String,
+    pub size: usize,
+    pub cell_type: String,
+
+    #[serde(default)]
+    pub parameters: BTreeMap<String, f64>,
+
+    /// Recorded variables, `spikes` gives output ports to neurons of the population.
+    #[serde(default)]
+    pub record: Vec<String>,
+}
+
+impl Population {
+    fn is_source(&self) -> bool {
+        self.cell_type.starts_with("SpikeSource")
+            || matches!(
+                self.cell_type.as_str(),
+                "SpikeGeneratorGroup" | "PoissonGroup"
+            )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Connector {
+    AllToAll {
+        #[serde(default = "allowed")]
+        allow_self_connections: bool,
+    },
+    OneToOne,
+    FixedProbability {
+        p_connect: f64,
+
+        #[serde(default = "allowed")]
+        allow_self_connections: bool,
+    },
+    /// Pairs (pre index, post index)
+    FromList {
+        conn_list: Vec<(usize, usize)>,
+    },
+}
+
+fn allowed() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReceptorType {
+    #[default]
+    Excitatory,
+
+    /// Weights of the projection are negated.
+    Inhibitory,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Synapse {
+    pub weight: f64,
+
+    /// Milliseconds. Not supported: runen links have no delays.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Projection {
+    pub pre: String,
+    pub post: String,
+    pub connector: Connector,
+    pub synapse: Synapse,
+
+    #[serde(default)]
+    pub receptor_type: ReceptorType,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkDescription {
+    pub populations: Vec<Population>,
+
+    #[serde(default)]
+    pub projections: Vec<Projection>,
+
+    /// The seed of FixedProbability connectors and the imported network.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+/// Conversion of parameters into runen units.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ImportOptions {
+    /// Synaptic weights and neuron biases (`bias` or `i_offset`) are multiplied
+    /// by the scale and rounded.
+    pub weight_scale: f64,
+
+    pub capacity_max: Signal,
+    pub regeneration: Signal,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        ImportOptions {
+            weight_scale: 1.0,
+            capacity_max: 10,
+            regeneration: 10,
+        }
+    }
+}
+
+/// The imported config with the mapping of populations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Import {
+    pub config: NetworkCfg,
+
+    /// Input ports of spike source populations by label
+    pub inputs: BTreeMap<String, Vec<usize>>,
+
+    /// Neuron ids of other populations by label
+    pub neurons: BTreeMap<String, Vec<String>>,
+
+    /// Ignored parameters and features of the description
+    pub warnings: Vec<String>,
+}
+
+impl NetworkDescription {
+    /// Read the description from yaml or json file.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_yaml::from_reader(File::open(path)?)?)
+    }
+
+    pub fn import(&self, options: &ImportOptions) -> Result<Import, Box<dyn Error>> {
+        let to_weight = |value: f64| {
+            (value * options.weight_scale)
+                .round()
+                .clamp(Weight::MIN as f64, Weight::MAX as f64) as Weight
+        };
+        let mut import = Import {
+            config: NetworkCfg {
+                inputs: 0,
+                outputs: 0,
+                neurons: vec![],
+                links: vec![],
+                lineage: None,
+                composites: vec![],
+                labels: LabelMap::default(),
+                seed: self.seed,
+            },
+            inputs: BTreeMap::new(),
+            neurons: BTreeMap::new(),
+            warnings: vec![],
+        };
+
+        for population in self.populations.iter() {
+            if import.inputs.contains_key(&population.label)
+                || import.neurons.contains_key(&population.label)
+            {
+                return Err(Box::new(RnnError::GroupAlreadyExists(
+                    population.label.clone(),
+                )));
+            }
+            if population.is_source() {
+                let start = import.config.inputs;
+                import.config.inputs += population.size;
+                import.inputs.insert(
+                    population.label.clone(),
+                    (start..start + population.size).collect(),
+                );
+                for name in population.parameters.keys() {
+                    import.warnings.push(format!(
+                        "{}: stimulus parameter {name} is ignored, drive input ports instead",
+                        population.label
+                    ));
+                }
+                continue;
+            }
+
+            let mut bias = 0;
+            for (name, value) in population.parameters.iter() {
+                match name.as_str() {
+                    "bias" | "i_offset" => bias = to_weight(*value),
+                    _ => import
+                        .warnings
+                        .push(format!("{}: parameter {name} is ignored", population.label)),
+                }
+            }
+            let mut ids = vec![];
+            for _ in 0..population.size {
+                let id = format!("M0Z{}", import.config.neurons.len());
+                import.config.neurons.push(NeuronCfg {
+                    id: id.clone(),
+                    bias,
+                    input_configs: vec![],
+                    lineage: None,
+                    kind: NeuronKind::Excitatory,
+                });
+                ids.push(id);
+            }
+            import.neurons.insert(population.label.clone(), ids);
+        }
+
+        let rng_provider = RngProvider::new(self.seed.unwrap_or_default());
+        for (idx, projection) in self.projections.iter().enumerate() {
+            let pre_size = match (
+                import.inputs.get(&projection.pre),
+                import.neurons.get(&projection.pre),
+            ) {
+                (Some(ports), _) => ports.len(),
+                (_, Some(ids)) => ids.len(),
+                _ => return Err(Box::new(RnnError::GroupNotFound(projection.pre.clone()))),
+            };
+            let post_ids = match import.neurons.get(&projection.post) {
+                Some(ids) => ids.clone(),
+                None if import.inputs.contains_key(&projection.post) => {
+                    return Err(Box::new(RnnError::NotSupportedArgValue))
+                }
+                None => return Err(Box::new(RnnError::GroupNotFound(projection.post.clone()))),
+            };
+            if projection.synapse.delay.is_some() {
+                import.warnings.push(format!(
+                    "{} -> {}: delays are ignored",
+                    projection.pre, projection.post
+                ));
+            }
+
+            let same = projection.pre == projection.post;
+            let pairs: Vec<(usize, usize)> = match &projection.connector {
+                Connector::AllToAll {
+                    allow_self_connections,
+                } => (0..pre_size)
+                    .flat_map(|pre| (0..post_ids.len()).map(move |post| (pre, post)))
+                    .filter(|(pre, post)| *allow_self_connections || !same || pre != post)
+                    .collect(),
+                Connector::OneToOne => {
+                    if pre_size != post_ids.len() {
+                        return Err(Box::new(RnnError::NotSupportedArgValue));
+                    }
+                    (0..pre_size).map(|idx| (idx, idx)).collect()
+                }
+                Connector::FixedProbability {
+                    p_connect,
+                    allow_self_connections,
+                } => {
+                    if !(0.0..=1.0).contains(p_connect) {
+                        return Err(Box::new(RnnError::NotSupportedArgValue));
+                    }
+                    let mut rng = rng_provider.stream(&format!("projection{idx}"));
+                    (0..pre_size)
+                        .flat_map(|pre| (0..post_ids.len()).map(move |post| (pre, post)))
+                        .filter(|(pre, post)| *allow_self_connections || !same || pre != post)
+                        .filter(|_| rng.gen_bool(*p_connect))
+                        .collect()
+                }
+                Connector::FromList { conn_list } => {
+                    if conn_list
+                        .iter()
+                        .any(|(pre, post)| *pre >= pre_size || *post >= post_ids.len())
+                    {
+                        return Err(Box::new(RnnError::NotSupportedArgValue));
+                    }
+                    conn_list.clone()
+                }
+            };
+
+            let weight = match projection.receptor_type {
+                ReceptorType::Excitatory => to_weight(projection.synapse.weight),
+                ReceptorType::Inhibitory => to_weight(-projection.synapse.weight),
+            };
+            let input_cfg = InputCfg::new(options.capacity_max, options.regeneration, weight)?;
+            for (pre, post) in pairs {
+                let dst_id = post_ids[post].clone();
+                let Some(dst) = import.config.neurons.iter_mut().find(|n| n.id == dst_id) else {
+                    return Err(Box::new(RnnError::NeuronNotFound(dst_id)));
+                };
+                let dst_synapse_idx = dst.input_configs.len();
+                dst.input_configs.push(input_cfg.clone());
+                import.config.links.push(
+                    match (
+                        import.inputs.get(&projection.pre),
+                        import.neurons.get(&projection.pre),
+                    ) {
+                        (Some(ports), _) => LinkCfg::Input {
+                            input_port: ports[pre],
+                            dst_id,
+                            dst_synapse_idx,
+                        },
+                        (_, Some(ids)) => LinkCfg::Inner {
+                            src_id: ids[pre].clone(),
+                            dst_id,
+                            dst_synapse_idx,
+                        },
+                        _ => return Err(Box::new(RnnError::GroupNotFound(projection.pre.clone()))),
+                    },
+                );
+            }
+        }
+
+        for population in self.populations.iter() {
+            let recorded = population
+                .record
+                .iter()
+                .any(|variable| variable == "spikes");
+            let Some(ids) = import.neurons.get(&population.label).filter(|_| recorded) else {
+                continue;
+            };
+            let mut ports = BTreeSet::new();
+            for src_id in ids {
+                let output_port = import.config.outputs;
+                import.config.outputs += 1;
+                import.config.links.push(LinkCfg::Output {
+                    src_id: src_id.clone(),
+                    output_port,
+                });
+                ports.insert(output_port);
+            }
+            if !ports.is_empty() {
+                import.config.labels.insert(&population.label, ports)?;
+            }
+        }
+
+        import.config.validate()?;
+        Ok(import)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static DESCRIPTION: &str = "
+populations:
+  - label: stimulus
+    size: 2
+    cell_type: SpikeSourcePoisson
+    parameters: { rate: 20.0 }
+  - label: exc
+    size: 3
+    cell_type: IF_curr_exp
+    parameters: { i_offset: 1.0, tau_m: 20.0 }
+    record: [spikes]
+  - label: inh
+    size: 3
+    cell_type: IF_curr_exp
+projections:
+  - pre: stimulus
+    post: exc
+    connector: { type: AllToAll }
+    synapse: { weight: 2.0, delay: 1.0 }
+  - pre: exc
+    post: inh
+    connector: { type: OneToOne }
+    synapse: { weight: 1.5 }
+  - pre: inh
+    post: exc
+    connector: { type: FromList, conn_list: [[0, 2]] }
+    synapse: { weight: 3.0 }
+    receptor_type: inhibitory
+";
+
+    fn description_fixture() -> NetworkDescription {
+        serde_yaml::from_str(DESCRIPTION).unwrap()
+    }
+
+    #[test]
+    fn description_should_be_imported_into_config() {
+        let import = description_fixture()
+            .import(&ImportOptions::default())
+            .unwrap();
+        let config = &import.config;
+
+        assert_eq!((config.inputs, config.outputs), (2, 3));
+        assert_eq!(config.neurons.len(), 6);
+        assert_eq!(import.inputs["stimulus"], vec![0, 1]);
+        assert_eq!(import.neurons["inh"], vec!["M0Z3", "M0Z4", "M0Z5"]);
+        assert_eq!(config.neurons[0].bias, 1);
+        // 2 stimulus dendrites and the inhibitory one of M0Z2
+        assert_eq!(config.neurons[2].input_configs.len(), 3);
+        assert_eq!(config.neurons[2].input_configs[2].weight, -3);
+        assert!(config.links.contains(&LinkCfg::Inner {
+            src_id: String::from("M0Z1"),
+            dst_id: String::from("M0Z4"),
+            dst_synapse_idx: 0,
+        }));
+        assert_eq!(
+            config.labels.get_ports("exc"),
+            Some(&BTreeSet::from([0, 1, 2]))
+        );
+        assert_eq!(import.warnings.len(), 3);
+    }
+
+    #[test]
+    fn fixed_probability_should_be_reproducible_by_seed() {
+        let mut description = description_fixture();
+        description.seed = Some(7);
+        description.projections[0].connector = Connector::FixedProbability {
+            p_connect: 0.5,
+            allow_self_connections: true,
+        };
+        let options = ImportOptions::default();
+
+        assert_eq!(
+            description.import(&options).unwrap(),
+            description.import(&options).unwrap()
+        );
+    }
+
+    #[test]
+    fn inconsistent_descriptions_should_be_rejected() {
+        let options = ImportOptions::default();
+
+        let mut description = description_fixture();
+        description.projections[1].post = String::from("stimulus");
+        assert!(description.import(&options).is_err());
+
+        let mut description = description_fixture();
+        description.projections[1].pre = String::from("unknown");
+        assert!(description.import(&options).is_err());
+
+        let mut description = description_fixture();
+        description.populations[2].size = 2;
+        assert!(description.import(&options).is_err());
+    }
+}
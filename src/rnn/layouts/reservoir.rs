@@ -0,0 +1,138 @@
+//! Reservoir computing (echo state network) layout: the large random recurrent
+//! hidden population driven by input ports, and the linear read-out layer
+//! connected to output ports. Only read-out weights are trained
+//! (see training::reservoir_trainer).
+
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::input_cfg::InputCfg;
+use crate::rnn::common::network_cfg::{LinkCfg, NetworkCfg, NeuronCfg, NeuronKind};
+
+use super::topology::{erdos_renyi, TopologyParams};
+
+/// Settings of the reservoir.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reservoir {
+    /// The hidden population. `outputs` is the number of read-out neurons.
+    pub params: TopologyParams,
+
+    /// The probability of recurrent link between hidden neurons
+    pub connectivity: f64,
+
+    /// The initial config of every read-out dendrite. Read-out neurons have zero bias.
+    pub readout_cfg: InputCfg,
+}
+
+impl Reservoir {
+    /// Ids of hidden neurons: M0Z0 .. M0Z{neurons - 1}.
+    pub fn hidden_ids(&self) -> Vec<String> {
+        (0..self.params.neurons).map(neuron_id).collect()
+    }
+
+    /// Ids of read-out neurons in order of output ports, numbered after hidden ones.
+    pub fn readout_ids(&self) -> Vec<String> {
+        (0..self.params.outputs)
+            .map(|port| neuron_id(self.params.neurons + port))
+            .collect()
+    }
+
+    /// Generate config. Every read-out neuron has a dendrite per hidden neuron
+    /// (dendrite index is the hidden neuron index) and drives its own output port.
+    pub fn build(&self) -> Result<NetworkCfg, Box<dyn Error>> {
+        let hidden_params = TopologyParams {
+            outputs: 0,
+            ..self.params.clone()
+        };
+        let mut config = erdos_renyi(&hidden_params, self.connectivity)?;
+
+        let hidden_ids = self.hidden_ids();
+        for (output_port, readout_id) in self.readout_ids().into_iter().enumerate() {
+            for (dst_synapse_idx, src_id) in hidden_ids.iter().enumerate() {
+                config.links.push(LinkCfg::Inner {
+                    src_id: src_id.clone(),
+                    dst_id: readout_id.clone(),
+                    dst_synapse_idx,
+                });
+            }
+            config.links.push(LinkCfg::Output {
+                src_id: readout_id.clone(),
+                output_port,
+            });
+            config.neurons.push(NeuronCfg {
+                id: readout_id,
+                bias: 0,
+                input_configs: vec![self.readout_cfg.clone(); hidden_ids.len()],
+                lineage: None,
+                kind: NeuronKind::Excitatory,
+            });
+        }
+        config.outputs = self.params.outputs;
+
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+fn neuron_id(idx: usize) -> String {
+    format!("M0Z{idx}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reservoir_fixture(neurons: usize, outputs: usize) -> Reservoir {
+        Reservoir {
+            params: TopologyParams {
+                neurons,
+                inputs: 1,
+                outputs,
+                bias: 0,
+                input_cfg: InputCfg::new(1, 1, 1).unwrap(),
+                seed: 7,
+            },
+            connectivity: 0.3,
+            readout_cfg: InputCfg::new(1, 1, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn should_build_hidden_population_with_readout_layer() {
+        let reservoir = reservoir_fixture(10, 2);
+        let config = reservoir.build().unwrap();
+
+        assert_eq!(config.inputs, 1);
+        assert_eq!(config.outputs, 2);
+        assert_eq!(config.neurons.len(), 12);
+        assert_eq!(reservoir.readout_ids(), vec!["M0Z10", "M0Z11"]);
+        assert_eq!(config.neurons[11].input_configs.len(), 10);
+        assert_eq!(config.neurons[11].bias, 0);
+        assert!(config.links.contains(&LinkCfg::Output {
+            src_id: String::from("M0Z11"),
+            output_port: 1,
+        }));
+        assert!(config.links.contains(&LinkCfg::Inner {
+            src_id: String::from("M0Z3"),
+            dst_id: String::from("M0Z10"),
+            dst_synapse_idx: 3,
+        }));
+    }
+
+    #[test]
+    fn hidden_population_should_be_reproducible_by_seed() {
+        assert_eq!(
+            reservoir_fixture(10, 1).build().unwrap(),
+            reservoir_fixture(10, 1).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_not_build_with_incorrect_connectivity() {
+        let mut reservoir = reservoir_fixture(10, 1);
+        reservoir.connectivity = 2.0;
+
+        assert!(reservoir.build().is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+
+use tokio::sync::mpsc;
+
+use crate::rnn::common::signal::Signal;
+
+/// The message delivered into the neuron's mailbox: dendrite index and signal.
+pub type MailboxItem = (usize, Signal);
+
+/// The path from the neuron's axon to a synapse of another neuron.
+#[derive(Debug, Clone)]
+pub struct Route {
+    /// Destination neuron id
+    pub dst_id: String,
+
+    /// Destination dendrite index
+    pub dst_synapse_idx: usize,
+
+    /// Sender half of the destination neuron's mailbox.
+    /// None when the signal goes through the event queue (RoutingBackend::EventDriven)
+    /// or the shard of destination neuron (RoutingBackend::Sharded).
+    pub mailbox: Option<mpsc::Sender<MailboxItem>>,
+}
+
+/// The network's routing table which maps source neuron id to its routes.
+#[derive(Debug, Default)]
+pub struct RoutingTable {
+    routes: BTreeMap<String, Vec<Route>>,
+}
+
+impl RoutingTable {
+    pub fn add_route(&mut self, src_id: &str, route: Route) {
+        self.routes
+            .entry(src_id.to_string())
+            .or_default()
+            .push(route);
+    }
+
+    /// Remove the route to the destination synapse. Returns false when there is no such route.
+    pub fn remove_route(&mut self, src_id: &str, dst_id: &str, dst_synapse_idx: usize) -> bool {
+        let Some(routes) = self.routes.get_mut(src_id) else {
+            return false;
+        };
+        let len = routes.len();
+        routes.retain(|route| route.dst_id != dst_id || route.dst_synapse_idx != dst_synapse_idx);
+        let removed = routes.len() < len;
+        if routes.is_empty() {
+            self.routes.remove(src_id);
+        }
+        removed
+    }
+
+    /// Remove all routes from and to the neuron. Returns the number of removed routes.
+    pub fn remove_neuron(&mut self, id: &str) -> usize {
+        let len = self.len();
+        self.routes.remove(id);
+        for routes in self.routes.values_mut() {
+            routes.retain(|route| route.dst_id != id);
+        }
+        self.routes.retain(|_, routes| !routes.is_empty());
+        len - self.len()
+    }
+
+    /// Get routes from the axon of specified neuron.
+    pub fn get_routes(&self, src_id: &str) -> &[Route] {
+        self.routes
+            .get(src_id)
+            .map_or(&[], |routes| routes.as_slice())
+    }
+
+    /// Total number of routes in the table.
+    pub fn len(&self) -> usize {
+        self.routes.values().map(|routes| routes.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route_fixture(dst_id: &str, dst_synapse_idx: usize) -> Route {
+        let (mailbox, _) = mpsc::channel(1);
+        Route {
+            dst_id: dst_id.to_string(),
+            dst_synapse_idx,
+            mailbox: Some(mailbox),
+        }
+    }
+
+    #[test]
+    fn should_return_empty_routes_for_unknown_source() {
+        let table = RoutingTable::default();
+
+        assert!(table.get_routes("M0Z0").is_empty());
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn should_group_routes_by_source_id() {
+        let mut table = RoutingTable::default();
+        table.add_route("M0Z0", route_fixture("M0Z1", 0));
+        table.add_route("M0Z0", route_fixture("M0Z2", 1));
+        table.add_route("M0Z1", route_fixture("M0Z2", 0));
+
+        assert_eq!(table.get_routes("M0Z0").len(), 2);
+        assert_eq!(table.get_routes("M0Z1").len(), 1);
+        assert_eq!(table.get_routes("M0Z0")[1].dst_id, "M0Z2");
+        assert_eq!(table.len(), 3);
+    }
+
+    #[test]
+    fn should_remove_route_to_specified_synapse() {
+        let mut table = RoutingTable::default();
+        table.add_route("M0Z0", route_fixture("M0Z1", 0));
+        table.add_route("M0Z0", route_fixture("M0Z1", 1));
+
+        assert!(table.remove_route("M0Z0", "M0Z1", 1));
+        assert!(!table.remove_route("M0Z0", "M0Z1", 1));
+        assert_eq!(table.get_routes("M0Z0")[0].dst_synapse_idx, 0);
+        assert!(table.remove_route("M0Z0", "M0Z1", 0));
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn should_remove_routes_from_and_to_neuron() {
+        let mut table = RoutingTable::default();
+        table.add_route("M0Z0", route_fixture("M0Z1", 0));
+        table.add_route("M0Z1", route_fixture("M0Z2", 0));
+        table.add_route("M0Z2", route_fixture("M0Z0", 0));
+
+        assert_eq!(table.remove_neuron("M0Z1"), 2);
+        assert!(table.get_routes("M0Z0").is_empty());
+        assert_eq!(table.get_routes("M0Z2").len(), 1);
+    }
+}
@@ -0,0 +1,84 @@
+//! Delivery of signals used with RoutingBackend::Sharded. Neurons are grouped
+//! onto a bounded number of shards by the hash of their id. Every shard owns one
+//! mpsc queue and one worker task, so the number of tasks and wakeups does not
+//! grow with the number of neurons and connections.
+
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use crate::rnn::common::signal::Signal;
+
+/// The signal going into the synapse of destination neuron: (dst_id, dst_synapse_idx, signal).
+pub type ShardItem = (String, usize, Signal);
+
+#[derive(Debug)]
+pub struct ShardedExecutor {
+    senders: Vec<mpsc::Sender<ShardItem>>,
+}
+
+impl ShardedExecutor {
+    /// Creates the executor of `shards` queues with the capacity each.
+    /// Receivers are returned in order of shards to be read by workers.
+    pub fn new(shards: usize, capacity: usize) -> (Self, Vec<mpsc::Receiver<ShardItem>>) {
+        let (senders, receivers) = (0..shards).map(|_| mpsc::channel(capacity)).unzip();
+        (ShardedExecutor { senders }, receivers)
+    }
+
+    pub fn len(&self) -> usize {
+        self.senders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.senders.is_empty()
+    }
+
+    /// The shard index of the neuron. The same id always goes to the same shard,
+    /// so signals of one neuron are processed in order of arrival.
+    pub fn shard_of(&self, id: &str) -> usize {
+        let hash = id.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        (hash % self.senders.len() as u64) as usize
+    }
+
+    pub fn try_send(
+        &self,
+        dst_id: &str,
+        dst_synapse_idx: usize,
+        signal: Signal,
+    ) -> Result<(), TrySendError<ShardItem>> {
+        self.senders[self.shard_of(dst_id)].try_send((dst_id.to_string(), dst_synapse_idx, signal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn signals_of_neuron_should_go_into_its_shard() {
+        let (executor, mut receivers) = ShardedExecutor::new(3, 4);
+        let shard = executor.shard_of("M0Z1");
+
+        assert_eq!(executor.len(), 3);
+        assert_eq!(shard, executor.shard_of("M0Z1"));
+        assert!(executor.try_send("M0Z1", 0, 5).is_ok());
+        assert!(executor.try_send("M0Z1", 1, 7).is_ok());
+
+        assert_eq!(
+            receivers[shard].recv().await,
+            Some((String::from("M0Z1"), 0, 5))
+        );
+        assert_eq!(
+            receivers[shard].recv().await,
+            Some((String::from("M0Z1"), 1, 7))
+        );
+    }
+
+    #[test]
+    fn full_shard_should_reject_signals() {
+        let (executor, _receivers) = ShardedExecutor::new(1, 1);
+
+        assert!(executor.try_send("M0Z0", 0, 1).is_ok());
+        assert!(executor.try_send("M0Z1", 0, 1).is_err());
+    }
+}
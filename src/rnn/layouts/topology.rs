@@ -0,0 +1,436 @@
+//! Random topology generators for reservoir-style experiments.
+//! Generators produce directed links between neurons without self loops.
+//! Every neuron gets a dendrite per incoming link, the first `inputs` neurons get
+//! an additional dendrite connected to the input port, and the last `outputs`
+//! neurons are connected to output ports.
+//! The winner-take-all circuit is built here as well, as it is the common
+//! classification head of such networks.
+
+use std::collections::BTreeSet;
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::input_cfg::InputCfg;
+use crate::rnn::common::network_cfg::{LabelMap, LinkCfg, NetworkCfg, NeuronCfg, NeuronKind};
+use crate::rnn::common::rng::SeededRng;
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::signal::Weight;
+
+/// Common parameters of generated networks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopologyParams {
+    /// The number of neurons
+    pub neurons: usize,
+
+    /// The number of input ports
+    pub inputs: usize,
+
+    /// The number of output ports
+    pub outputs: usize,
+
+    /// The bias of every neuron
+    pub bias: Weight,
+
+    /// The config of every dendrite
+    pub input_cfg: InputCfg,
+
+    /// The same seed produces the same topology
+    pub seed: u64,
+}
+
+/// Erdős–Rényi graph: every ordered pair of neurons is linked with probability p.
+pub fn erdos_renyi(params: &TopologyParams, p: f64) -> Result<NetworkCfg, Box<dyn Error>> {
+    check_params(params)?;
+    if !(0.0..=1.0).contains(&p) {
+        return Err(Box::new(RnnError::NotSupportedArgValue));
+    }
+
+    let mut rng = SeededRng::new(params.seed);
+    let edges = (0..params.neurons)
+        .flat_map(|src| (0..params.neurons).map(move |dst| (src, dst)))
+        .filter(|(src, dst)| src != dst)
+        .filter(|_| rng.gen_bool(p))
+        .collect();
+
+    build_config(params, edges)
+}
+
+/// Watts–Strogatz small-world graph: the ring where every neuron is linked to
+/// `k` nearest following neurons, then every link is rewired to the random neuron
+/// with probability beta.
+pub fn watts_strogatz(
+    params: &TopologyParams,
+    k: usize,
+    beta: f64,
+) -> Result<NetworkCfg, Box<dyn Error>> {
+    check_params(params)?;
+    let n = params.neurons;
+    if k == 0 || k >= n || !(0.0..=1.0).contains(&beta) {
+        return Err(Box::new(RnnError::NotSupportedArgValue));
+    }
+
+    let mut rng = SeededRng::new(params.seed);
+    let mut edges: BTreeSet<(usize, usize)> = (0..n)
+        .flat_map(|src| (1..=k).map(move |shift| (src, (src + shift) % n)))
+        .collect();
+
+    for src in 0..n {
+        for shift in 1..=k {
+            let dst = (src + shift) % n;
+            if !rng.gen_bool(beta) {
+                continue;
+            }
+            let new_dst = rng.gen_index(n);
+            if new_dst != src && !edges.contains(&(src, new_dst)) {
+                edges.remove(&(src, dst));
+                edges.insert((src, new_dst));
+            }
+        }
+    }
+
+    build_config(params, edges)
+}
+
+/// Barabási–Albert scale-free graph: neurons are added one by one and every new neuron
+/// is linked to `m` already existing neurons chosen with probability proportional
+/// to their degree. The first `m` neurons are linked in the ring.
+pub fn barabasi_albert(params: &TopologyParams, m: usize) -> Result<NetworkCfg, Box<dyn Error>> {
+    check_params(params)?;
+    let n = params.neurons;
+    if m == 0 || m >= n {
+        return Err(Box::new(RnnError::NotSupportedArgValue));
+    }
+
+    let mut rng = SeededRng::new(params.seed);
+    let mut edges: BTreeSet<(usize, usize)> = BTreeSet::new();
+    // every neuron is present here as many times as its degree
+    let mut degrees_pool: Vec<usize> = vec![];
+    if m > 1 {
+        for src in 0..m {
+            edges.insert((src, (src + 1) % m));
+            degrees_pool.extend([src, (src + 1) % m]);
+        }
+    } else {
+        degrees_pool.push(0);
+    }
+
+    for src in m..n {
+        let mut targets: BTreeSet<usize> = BTreeSet::new();
+        while targets.len() < m {
+            targets.insert(degrees_pool[rng.gen_index(degrees_pool.len())]);
+        }
+        for dst in targets {
+            edges.insert((src, dst));
+            degrees_pool.extend([src, dst]);
+        }
+    }
+
+    build_config(params, edges)
+}
+
+/// Parameters of the winner-take-all circuit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WtaCfg {
+    /// The bias of every competing neuron
+    pub bias: Weight,
+
+    /// The config of dendrites connected to input ports
+    pub input_cfg: InputCfg,
+
+    /// The config of inhibitory dendrites. Its weight is used as negative.
+    pub inhibition_cfg: InputCfg,
+
+    /// The dendrite config of the shared inhibitory interneuron, if any
+    pub interneuron_cfg: Option<InputCfg>,
+}
+
+/// Winner-take-all circuit of n competing neurons M0Z0..M0Z{n-1}. Competing neuron i
+/// gets input port i into dendrite 0, is connected to output port i and inhibits
+/// every other competing neuron. With interneuron_cfg the inhibitory interneuron
+/// M0Z{n} is excited by all competing neurons and inhibits each of them through
+/// the last dendrite.
+pub fn winner_take_all(n: usize, cfg: &WtaCfg) -> Result<NetworkCfg, Box<dyn Error>> {
+    if n < 2 {
+        return Err(Box::new(RnnError::NotSupportedArgValue));
+    }
+
+    let id = |idx: usize| format!("M0Z{idx}");
+    let mut inhibition_cfg = cfg.inhibition_cfg.clone();
+    inhibition_cfg.weight = -inhibition_cfg.weight.saturating_abs();
+    let mut links = vec![];
+
+    for competitor in 0..n {
+        links.push(LinkCfg::Input {
+            input_port: competitor,
+            dst_id: id(competitor),
+            dst_synapse_idx: 0,
+        });
+        for (dst_synapse_idx, src) in (0..n).filter(|src| *src != competitor).enumerate() {
+            links.push(LinkCfg::Inner {
+                src_id: id(src),
+                dst_id: id(competitor),
+                dst_synapse_idx: dst_synapse_idx + 1,
+            });
+        }
+        links.push(LinkCfg::Output {
+            src_id: id(competitor),
+            output_port: competitor,
+        });
+    }
+
+    let mut competitor_input_configs = vec![cfg.input_cfg.clone()];
+    competitor_input_configs.extend(vec![inhibition_cfg.clone(); n - 1]);
+    let mut neurons: Vec<NeuronCfg> = (0..n)
+        .map(|competitor| NeuronCfg {
+            id: id(competitor),
+            bias: cfg.bias,
+            input_configs: competitor_input_configs.clone(),
+            lineage: None,
+            kind: NeuronKind::Excitatory,
+        })
+        .collect();
+
+    if let Some(interneuron_cfg) = cfg.interneuron_cfg.as_ref() {
+        for (competitor, neuron) in neurons.iter_mut().enumerate() {
+            links.push(LinkCfg::Inner {
+                src_id: id(competitor),
+                dst_id: id(n),
+                dst_synapse_idx: competitor,
+            });
+            links.push(LinkCfg::Inner {
+                src_id: id(n),
+                dst_id: id(competitor),
+                dst_synapse_idx: neuron.input_configs.len(),
+            });
+            neuron.input_configs.push(inhibition_cfg.clone());
+        }
+        neurons.push(NeuronCfg {
+            id: id(n),
+            bias: 0,
+            input_configs: vec![interneuron_cfg.clone(); n],
+            lineage: None,
+            kind: NeuronKind::Inhibitory,
+        });
+    }
+
+    let config = NetworkCfg {
+        inputs: n,
+        outputs: n,
+        neurons,
+        links,
+        lineage: None,
+        composites: vec![],
+        labels: LabelMap::default(),
+        seed: None,
+    };
+    config.validate()?;
+    Ok(config)
+}
+
+fn check_params(params: &TopologyParams) -> Result<(), Box<dyn Error>> {
+    if params.neurons < 2 || params.inputs > params.neurons || params.outputs > params.neurons {
+        Err(Box::new(RnnError::NotSupportedArgValue))
+    } else {
+        Ok(())
+    }
+}
+
+fn build_config(
+    params: &TopologyParams,
+    edges: BTreeSet<(usize, usize)>,
+) -> Result<NetworkCfg, Box<dyn Error>> {
+    let id = |idx: usize| format!("M0Z{idx}");
+    let mut dendrites_count = vec![0_usize; params.neurons];
+    let mut links = vec![];
+
+    for (input_port, count) in dendrites_count.iter_mut().take(params.inputs).enumerate() {
+        links.push(LinkCfg::Input {
+            input_port,
+            dst_id: id(input_port),
+            dst_synapse_idx: 0,
+        });
+        *count += 1;
+    }
+    for (src, dst) in edges {
+        links.push(LinkCfg::Inner {
+            src_id: id(src),
+            dst_id: id(dst),
+            dst_synapse_idx: dendrites_count[dst],
+        });
+        dendrites_count[dst] += 1;
+    }
+    for output_port in 0..params.outputs {
+        links.push(LinkCfg::Output {
+            src_id: id(params.neurons - params.outputs + output_port),
+            output_port,
+        });
+    }
+
+    let neurons = dendrites_count
+        .iter()
+        .enumerate()
+        .map(|(idx, count)| NeuronCfg {
+            id: id(idx),
+            bias: params.bias,
+            input_configs: vec![params.input_cfg.clone(); (*count).max(1)],
+            lineage: None,
+            kind: NeuronKind::Excitatory,
+        })
+        .collect();
+
+    let config = NetworkCfg {
+        inputs: params.inputs,
+        outputs: params.outputs,
+        neurons,
+        links,
+        lineage: None,
+        composites: vec![],
+        labels: LabelMap::default(),
+        seed: None,
+    };
+    config.validate()?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params_fixture(neurons: usize, seed: u64) -> TopologyParams {
+        TopologyParams {
+            neurons,
+            inputs: 2,
+            outputs: 1,
+            bias: 0,
+            input_cfg: InputCfg::new(1, 1, 1).unwrap(),
+            seed,
+        }
+    }
+
+    fn inner_links(config: &NetworkCfg) -> Vec<(String, String)> {
+        config
+            .links
+            .iter()
+            .filter_map(|link| match link {
+                LinkCfg::Inner { src_id, dst_id, .. } => Some((src_id.clone(), dst_id.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn generators_should_be_reproducible_by_seed() {
+        let params = params_fixture(20, 3);
+        assert_eq!(
+            erdos_renyi(&params, 0.2).unwrap(),
+            erdos_renyi(&params, 0.2).unwrap()
+        );
+        assert_ne!(
+            erdos_renyi(&params, 0.2).unwrap(),
+            erdos_renyi(&params_fixture(20, 4), 0.2).unwrap()
+        );
+        assert_eq!(
+            watts_strogatz(&params, 2, 0.3).unwrap(),
+            watts_strogatz(&params, 2, 0.3).unwrap()
+        );
+        assert_eq!(
+            barabasi_albert(&params, 2).unwrap(),
+            barabasi_albert(&params, 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn erdos_renyi_should_follow_probability_bounds() {
+        let params = params_fixture(5, 1);
+
+        assert!(inner_links(&erdos_renyi(&params, 0.0).unwrap()).is_empty());
+        assert_eq!(inner_links(&erdos_renyi(&params, 1.0).unwrap()).len(), 20);
+        assert!(erdos_renyi(&params, 1.5).is_err());
+    }
+
+    #[test]
+    fn watts_strogatz_without_rewiring_should_be_ring_lattice() {
+        let config = watts_strogatz(&params_fixture(4, 1), 1, 0.0).unwrap();
+
+        assert_eq!(
+            inner_links(&config),
+            vec![
+                (String::from("M0Z0"), String::from("M0Z1")),
+                (String::from("M0Z1"), String::from("M0Z2")),
+                (String::from("M0Z2"), String::from("M0Z3")),
+                (String::from("M0Z3"), String::from("M0Z0")),
+            ]
+        );
+        // input port dendrite plus ring link
+        assert_eq!(config.neurons[0].input_configs.len(), 2);
+        assert_eq!(config.neurons[3].input_configs.len(), 1);
+    }
+
+    #[test]
+    fn watts_strogatz_should_keep_number_of_links() {
+        let config = watts_strogatz(&params_fixture(30, 5), 2, 0.5).unwrap();
+
+        assert_eq!(inner_links(&config).len(), 60);
+    }
+
+    #[test]
+    fn barabasi_albert_should_link_every_new_neuron_m_times() {
+        let config = barabasi_albert(&params_fixture(30, 5), 2).unwrap();
+
+        // ring of 2 first neurons plus 2 links of every next neuron
+        assert_eq!(inner_links(&config).len(), 2 + 28 * 2);
+        assert!(inner_links(&config)
+            .iter()
+            .all(|(src_id, dst_id)| src_id != dst_id));
+    }
+
+    fn wta_cfg_fixture(interneuron_cfg: Option<InputCfg>) -> WtaCfg {
+        WtaCfg {
+            bias: 0,
+            input_cfg: InputCfg::new(10, 10, 2).unwrap(),
+            inhibition_cfg: InputCfg::new(10, 10, 3).unwrap(),
+            interneuron_cfg,
+        }
+    }
+
+    #[test]
+    fn winner_take_all_should_wire_mutual_inhibition() {
+        let config = winner_take_all(3, &wta_cfg_fixture(None)).unwrap();
+
+        assert_eq!((config.inputs, config.outputs), (3, 3));
+        assert_eq!(config.neurons.len(), 3);
+        assert_eq!(inner_links(&config).len(), 6);
+        assert!(config.neurons.iter().all(|neuron_cfg| {
+            let weights: Vec<Weight> = neuron_cfg
+                .input_configs
+                .iter()
+                .map(|input_cfg| input_cfg.weight)
+                .collect();
+            weights == vec![2, -3, -3]
+        }));
+    }
+
+    #[test]
+    fn winner_take_all_interneuron_should_inhibit_every_competitor() {
+        let config =
+            winner_take_all(3, &wta_cfg_fixture(Some(InputCfg::new(10, 10, 1).unwrap()))).unwrap();
+
+        let interneuron = &config.neurons[3];
+        assert_eq!(interneuron.kind, NeuronKind::Inhibitory);
+        assert_eq!(interneuron.input_configs.len(), 3);
+        assert_eq!(inner_links(&config).len(), 6 + 3 + 3);
+        assert!(config.neurons[..3]
+            .iter()
+            .all(|neuron_cfg| neuron_cfg.input_configs.len() == 4
+                && neuron_cfg.input_configs[3].weight == -3));
+    }
+
+    #[test]
+    fn should_not_generate_with_incorrect_params() {
+        assert!(winner_take_all(1, &wta_cfg_fixture(None)).is_err());
+        assert!(erdos_renyi(&params_fixture(1, 1), 0.5).is_err());
+        assert!(watts_strogatz(&params_fixture(4, 1), 4, 0.1).is_err());
+        assert!(barabasi_albert(&params_fixture(4, 1), 0).is_err());
+    }
+}
@@ -0,0 +1,284 @@
+//! MQTT adapters making the network an online processor of sensor streams.
+//! The source subscribes to topics and sends their numeric payloads into input
+//! ports through the encoder. Sinks publish spikes of output ports or values
+//! decoded from them. Every adapter runs as the tokio task until it is cancelled,
+//! the connection fails or the network is dropped.
+//!
+//! ```ignore
+//! let options = MqttOptions::new("runen", "localhost", 1883);
+//! let inputs = vec![TopicInput { topic: "sensors/temp".into(), port: 0, min: 10.0, max: 30.0 }];
+//! let source = spawn_source(options, inputs, encoder, &network).await?;
+//! ```
+
+use std::error::Error;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS, SubscribeFilter};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt;
+use tokio_util::sync::CancellationToken;
+
+use crate::rnn::analysis::comparison::OutputEvent;
+use crate::rnn::io::decoders::{decode, Decoder};
+use crate::rnn::io::encoders::{send_timed, Encoder};
+use crate::rnn::layouts::network::Network;
+use crate::rnn::training::encoding::Reconstructible;
+
+/// The capacity of requests queue of the client
+static REQUESTS_CAPACITY: usize = 64;
+
+/// The topic whose payloads go into the input port. Payloads are numbers in text
+/// (e.g. `21.5`), normalized from [min, max] into [0, 1] before encoding.
+/// Topic filters with wildcards are not supported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TopicInput {
+    pub topic: String,
+    pub port: usize,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl TopicInput {
+    /// Values of input ports for the payload: the normalized value at the index
+    /// of the port and zeros before it. None for non-numeric payloads.
+    fn values(&self, payload: &[u8]) -> Option<Vec<f64>> {
+        let value: f64 = std::str::from_utf8(payload).ok()?.trim().parse().ok()?;
+        let mut values = vec![0.0; self.port + 1];
+        values[self.port] = if self.max > self.min {
+            (value - self.min) / (self.max - self.min)
+        } else {
+            0.0
+        };
+        Some(values)
+    }
+}
+
+/// The topic receiving spikes of the output port as json OutputEvent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicOutput {
+    pub port: usize,
+    pub topic: String,
+}
+
+/// The handle of the running adapter.
+#[derive(Debug)]
+pub struct AdapterHandle {
+    processed: Arc<AtomicU64>,
+    cancel_token: CancellationToken,
+    task_handler: JoinHandle<Result<(), String>>,
+}
+
+impl AdapterHandle {
+    /// The number of messages sent into the network or published so far.
+    pub fn get_processed(&self) -> u64 {
+        self.processed.load(Ordering::Relaxed)
+    }
+
+    pub fn cancel(&self) {
+        self.cancel_token.cancel();
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.task_handler.is_finished()
+    }
+
+    /// Cancel the adapter and wait for its end. Returns the number of processed
+    /// messages or the error which stopped the adapter before.
+    pub async fn stop(self) -> Result<u64, Box<dyn Error>> {
+        self.cancel_token.cancel();
+        self.task_handler.await??;
+        Ok(self.processed.load(Ordering::Relaxed))
+    }
+}
+
+/// Subscribe to topics of inputs and send their payloads into the network.
+/// Messages with non-numeric payloads are skipped.
+pub async fn spawn_source(
+    options: MqttOptions,
+    inputs: Vec<TopicInput>,
+    encoder: impl Encoder + Send + Sync + 'static,
+    network: &Arc<Network>,
+) -> Result<AdapterHandle, Box<dyn Error>> {
+    let (client, mut eventloop) = AsyncClient::new(options, REQUESTS_CAPACITY);
+    if !inputs.is_empty() {
+        client
+            .subscribe_many(
+                inputs
+                    .iter()
+                    .map(|input| SubscribeFilter::new(input.topic.clone(), QoS::AtLeastOnce)),
+            )
+            .await?;
+    }
+
+    let processed = Arc::new(AtomicU64::new(0));
+    let cancel_token = CancellationToken::new();
+    let network: Weak<Network> = Arc::downgrade(network);
+
+    let processed_cloned = processed.clone();
+    let token_cloned = cancel_token.clone();
+    let task_handler = tokio::spawn(async move {
+        // the event loop ends when the client is dropped
+        let _client = client;
+        loop {
+            let event = tokio::select! {
+                _ = token_cloned.cancelled() => return Ok(()),
+                event = eventloop.poll() => event.map_err(|err| err.to_string())?,
+            };
+            let Event::Incoming(Packet::Publish(publish)) = event else {
+                continue;
+            };
+            let Some(network) = network.upgrade() else {
+                return Ok(());
+            };
+            for input in inputs.iter().filter(|input| input.topic == publish.topic) {
+                let Some(values) = input.values(&publish.payload) else {
+                    tracing::warn!(topic = %publish.topic, "non-numeric payload is skipped");
+                    continue;
+                };
+                send_timed(&network, &encoder.encode(&values))
+                    .await
+                    .map_err(|err| err.to_string())?;
+                processed_cloned.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    });
+
+    Ok(AdapterHandle {
+        processed,
+        cancel_token,
+        task_handler,
+    })
+}
+
+/// Publish every spike of output ports into their topics. Only output ports
+/// connected before the call are observed.
+pub async fn spawn_spike_sink(
+    options: MqttOptions,
+    outputs: Vec<TopicOutput>,
+    network: &Arc<Network>,
+) -> Result<AdapterHandle, Box<dyn Error>> {
+    let (client, mut eventloop) = AsyncClient::new(options, REQUESTS_CAPACITY);
+    let mut spikes = network.output_stream().await?;
+    let clock = network.get_clock();
+
+    let processed = Arc::new(AtomicU64::new(0));
+    let cancel_token = CancellationToken::new();
+
+    let processed_cloned = processed.clone();
+    let token_cloned = cancel_token.clone();
+    let task_handler = tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = token_cloned.cancelled() => return Ok(()),
+                event = eventloop.poll() => {
+                    event.map_err(|err| err.to_string())?;
+                }
+                spike = spikes.next() => {
+                    let Some((port, signal)) = spike else {
+                        return Ok(());
+                    };
+                    let event = OutputEvent { timestamp: clock.now(), port, signal };
+                    for output in outputs.iter().filter(|output| output.port == port) {
+                        publish(&client, &output.topic, &event)?;
+                        processed_cloned.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(AdapterHandle {
+        processed,
+        cancel_token,
+        task_handler,
+    })
+}
+
+/// Decode output spikes window by window (see io::decoders::decode()) and publish
+/// decoded values into the topic as json. Windows without value publish nothing.
+pub fn spawn_decoded_sink<O>(
+    options: MqttOptions,
+    topic: &str,
+    decoder: impl Decoder + Send + Sync + 'static,
+    network: &Arc<Network>,
+) -> AdapterHandle
+where
+    O: Reconstructible + Serialize + Send + 'static,
+{
+    let (client, mut eventloop) = AsyncClient::new(options, REQUESTS_CAPACITY);
+    let topic = topic.to_string();
+
+    let processed = Arc::new(AtomicU64::new(0));
+    let cancel_token = CancellationToken::new();
+    let network: Weak<Network> = Arc::downgrade(network);
+
+    let processed_cloned = processed.clone();
+    let token_cloned = cancel_token.clone();
+    let task_handler = tokio::spawn(async move {
+        loop {
+            let Some(network) = network.upgrade() else {
+                return Ok(());
+            };
+            let decoding = decode::<O>(&network, &decoder);
+            tokio::pin!(decoding);
+            let decoded = loop {
+                tokio::select! {
+                    _ = token_cloned.cancelled() => return Ok(()),
+                    event = eventloop.poll() => {
+                        event.map_err(|err| err.to_string())?;
+                    }
+                    decoded = &mut decoding => break decoded.map_err(|err| err.to_string())?,
+                }
+            };
+            if let Some(value) = decoded {
+                publish(&client, &topic, &value)?;
+                processed_cloned.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    });
+
+    AdapterHandle {
+        processed,
+        cancel_token,
+        task_handler,
+    }
+}
+
+/// Queue the message without waiting, as the event loop is polled by the same task.
+fn publish(client: &AsyncClient, topic: &str, value: &impl Serialize) -> Result<(), String> {
+    let payload = serde_json::to_vec(value).map_err(|err| err.to_string())?;
+    if let Err(err) = client.try_publish(topic, QoS::AtMostOnce, false, payload) {
+        tracing::warn!(topic, %err, "message is dropped");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_fixture() -> TopicInput {
+        TopicInput {
+            topic: String::from("sensors/temp"),
+            port: 2,
+            min: 10.0,
+            max: 30.0,
+        }
+    }
+
+    #[test]
+    fn numeric_payload_should_be_normalized_into_its_port() {
+        let input = input_fixture();
+
+        assert_eq!(input.values(b" 25\n"), Some(vec![0.0, 0.0, 0.75]));
+        assert_eq!(input.values(b"warm"), None);
+        assert_eq!(input.values(&[0xff, 0xfe]), None);
+        let flat = TopicInput {
+            max: 10.0,
+            ..input_fixture()
+        };
+        assert_eq!(flat.values(b"25"), Some(vec![0.0, 0.0, 0.0]));
+    }
+}
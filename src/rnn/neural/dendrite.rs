@@ -18,4 +18,7 @@ pub struct Dendrite {
 
     /// Receiver part of channel between axon and synapse
     pub synapse: Option<Arc<RwLock<Receiver<Signal>>>>,
+
+    /// The number of signals lost by the lagging synapse since the connection
+    pub dropped_count: u64,
 }
@@ -7,30 +7,51 @@ use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::sync::RwLock as StdRwLock;
 use std::sync::Weak;
 
-use chrono::Utc;
+use chrono::{DateTime, TimeDelta, Utc};
 use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::broadcast::Receiver;
 use tokio::sync::broadcast::Sender;
 use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::Notify;
 use tokio::sync::RwLock;
 use tokio::sync::RwLockWriteGuard;
 use tokio::task::JoinHandle;
 use tokio_util::task::TaskTracker;
+use tracing::{debug, info_span, trace, Instrument, Span};
 
 use super::dendrite::Dendrite;
+use crate::rnn::analysis::probe::ProbeSample;
+use crate::rnn::common::clock::Clock;
 use crate::rnn::common::command::NeuronCommand;
 use crate::rnn::common::input_cfg::InputCfg;
-use crate::rnn::common::network_cfg::NeuronCfg;
+use crate::rnn::common::network_cfg::{NeuronCfg, NeuronKind};
 use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::runtime_cfg::{
+    BackpressurePolicy, LagRecovery, MonitoringOverflow, OverflowPolicy, RoutingBackend,
+};
 use crate::rnn::common::signal::Signal;
 use crate::rnn::common::signal::Weight;
-use crate::rnn::common::status::NeuronInfo;
+use crate::rnn::common::snapshot::NeuronState;
 use crate::rnn::common::status::Status;
-use crate::rnn::layouts::network::MonitoringMode;
+use crate::rnn::common::status::{LagInfo, NeuronInfo, StatusMask};
+use crate::rnn::layouts::event_queue::{EventQueue, SignalEvent};
 use crate::rnn::layouts::network::Network;
+use crate::rnn::layouts::network::TaskReport;
+use crate::rnn::layouts::network::{InFlight, MonitoringCounters, RoutingCounters};
+use crate::rnn::layouts::network::{MonitoringFilter, MonitoringMode};
+use crate::rnn::layouts::routing_table::{MailboxItem, Route, RoutingTable};
+use crate::rnn::layouts::sharded_executor::ShardedExecutor;
+
+/// The number of signals buffered by the paused neuron. Later signals are dropped.
+static PAUSE_BUFFER_CAPACITY: usize = 64;
 
 /// The neuron's core, which contains data that is shared between concurrent tasks.
 #[derive(Debug)]
@@ -41,12 +62,29 @@ pub struct NeuronCore {
     /// The accumulator need to sum incoming signals.
     accumulator: Weight,
 
+    /// The copy of Neuron::kind reported in statuses
+    kind: NeuronKind,
+
     /// The counter of neuron resets.
     reset_counter: u64,
 
     /// The counter of signal hits.
     hit_counter: u64,
 
+    /// The counter of signals ignored during refractory periods.
+    refractory_counter: u64,
+
+    /// The time of the latest firing
+    fired_at: Option<DateTime<Utc>>,
+
+    refractory_period: TimeDelta,
+
+    /// The time constant of the accumulator decay in seconds (leaky integrate-and-fire).
+    leak_time_constant: Option<f64>,
+
+    /// The time of the latest accumulator update
+    accumulated_at: Option<DateTime<Utc>>,
+
     /// Neurons input which are received the signals from outside.
     dendrites: BTreeMap<usize, Dendrite>,
 
@@ -60,9 +98,16 @@ pub struct NeuronCore {
     /// a broadcast channel and sent to other recipients.
     axon: Arc<Option<Arc<Sender<Signal>>>>,
 
+    /// The network's routing table. The neuron delivers accumulated result
+    /// into mailboxes of connected neurons when RoutingBackend::Mailbox is used.
+    routing_table: Arc<StdRwLock<RoutingTable>>,
+
     /// The task handlers
     synapse_connection_handlers: HashMap<usize, JoinHandle<()>>,
 
+    /// The handlers of neuron's own tasks: commands receiver and mailbox reader.
+    service_handlers: Vec<JoinHandle<()>>,
+
     /// Each synapse that has a connection is waiting for signals in a separate task.
     /// Tracker is a collection of these tasks
     receivers_task_tracker: TaskTracker,
@@ -70,14 +115,85 @@ pub struct NeuronCore {
     monitoring_sender: mpsc::WeakSender<Status>,
 
     monitoring_mode: MonitoringMode,
+
+    monitoring_filter: MonitoringFilter,
+
+    /// The number of statuses passed the filter, used for sampling.
+    sampled_statuses: u64,
+
+    /// What to do with status when monitoring channel is full.
+    monitoring_overflow: MonitoringOverflow,
+
+    /// What to do when the accumulator overflows.
+    accumulator_overflow: OverflowPolicy,
+
+    axon_capacity: usize,
+
+    /// What to do when the axon is full.
+    backpressure: BackpressurePolicy,
+
+    /// What the lagging synapse listener does.
+    lag_recovery: LagRecovery,
+
+    /// The latest status waiting for free space in monitoring channel (MonitoringOverflow::Coalesce).
+    pending_status: Option<Status>,
+
+    monitoring_counters: Arc<MonitoringCounters>,
+
+    routing_counters: Arc<RoutingCounters>,
+
+    /// Signals (port, signal) received while the neuron is paused. None when it is not paused.
+    paused_signals: Option<Vec<(usize, Signal)>>,
+
+    /// The network's counter of signals delivered but not processed yet.
+    /// Present with SimulationMode::Stepped only.
+    in_flight: Option<Arc<InFlight>>,
+
+    /// The network's notification of taken signals. Present with BackpressurePolicy::Block only.
+    consumed: Option<Arc<Notify>>,
+
+    clock: Arc<dyn Clock>,
+
+    /// The network's event queue. Present with RoutingBackend::EventDriven only.
+    event_queue: Option<Arc<EventQueue>>,
+
+    /// The logical time of the latest received signal (RoutingBackend::EventDriven).
+    event_time: u64,
+
+    /// The network's shards. Present with RoutingBackend::Sharded only.
+    sharded_executor: Option<Arc<ShardedExecutor>>,
     // commands_receiver: broadcast::Receiver<NeuronCommands>,
 }
 
+/// Notifies input ports waiting for free space when the synapse listener stops,
+/// since the released receiver does not hold signals anymore.
+struct ReleaseNotifier(Option<Arc<Notify>>);
+
+impl Drop for ReleaseNotifier {
+    fn drop(&mut self) {
+        if let Some(consumed) = self.0.as_ref() {
+            consumed.notify_waiters();
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Neuron {
     id: String,
     network: Weak<Network>,
     core: Arc<RwLock<NeuronCore>>,
+
+    /// Sender half of the neuron's mailbox. Present with RoutingBackend::Mailbox only.
+    mailbox: Option<mpsc::Sender<MailboxItem>>,
+
+    /// The id of the neuron this one originates from (see NeuronCfg::lineage).
+    lineage: Option<String>,
+
+    /// Outbound connections of the inhibitory neuron have negative weights.
+    kind: NeuronKind,
+
+    /// The span of all neuron's tasks.
+    span: Span,
 }
 
 impl Neuron {
@@ -87,27 +203,61 @@ impl Neuron {
     async fn new(
         id: &str,
         bias: Weight,
+        kind: NeuronKind,
         network: Arc<Network>,
         monitoring_sender: mpsc::WeakSender<Status>,
     ) -> Self {
         let core = NeuronCore {
             bias,
             accumulator: 0,
+            kind,
             reset_counter: 0,
             hit_counter: 0,
+            refractory_counter: 0,
+            fired_at: None,
+            refractory_period: TimeDelta::from_std(network.get_runtime_cfg().refractory_period)
+                .unwrap_or(TimeDelta::MAX),
+            leak_time_constant: network
+                .get_runtime_cfg()
+                .leak_time_constant
+                .map(|tau| tau.as_secs_f64()),
+            accumulated_at: None,
             dendrites: BTreeMap::new(),
             input_hits: HashSet::new(),
             axon: Arc::new(None),
+            routing_table: network.get_routing_table(),
             receivers_task_tracker: TaskTracker::new(),
             synapse_connection_handlers: HashMap::new(),
+            service_handlers: vec![],
             monitoring_sender,
             monitoring_mode: network.get_monitoring_mode().await,
+            monitoring_filter: network.get_monitoring_filter().await,
+            sampled_statuses: 0,
+            monitoring_overflow: network.get_runtime_cfg().monitoring_overflow,
+            accumulator_overflow: network.get_runtime_cfg().accumulator_overflow,
+            axon_capacity: network.get_runtime_cfg().channels.axon,
+            backpressure: network.get_runtime_cfg().backpressure,
+            lag_recovery: network.get_runtime_cfg().lag_recovery,
+            pending_status: None,
+            monitoring_counters: network.get_monitoring_counters(),
+            routing_counters: network.get_routing_counters(),
+            paused_signals: None,
+            in_flight: network.get_in_flight(),
+            consumed: network.get_consumed(),
+            clock: network.get_clock(),
+            event_queue: network.get_event_queue(),
+            event_time: 0,
+            sharded_executor: network.get_sharded_executor(),
         };
 
         Neuron {
             id: String::from(id),
             network: Arc::downgrade(&network),
             core: Arc::new(RwLock::new(core)),
+            mailbox: None,
+            lineage: None,
+            kind,
+            span: info_span!("neuron", id),
         }
     }
 
@@ -118,58 +268,174 @@ impl Neuron {
             id,
             input_configs,
             bias,
+            lineage,
+            kind,
         } = config;
 
         let mut commands_receiver = network.get_commands_receiver();
         let monitoring_sender = network.get_monitoring_sender();
+        let runtime_cfg = network.get_runtime_cfg();
 
-        let neuron = Neuron::new(&id, bias, network, monitoring_sender).await;
+        let mut neuron = Neuron::new(&id, bias, kind, network, monitoring_sender).await;
+        neuron.lineage = lineage;
         neuron.config(input_configs).await;
 
+        if runtime_cfg.routing == RoutingBackend::Mailbox {
+            let (mailbox_tx, mut mailbox_rx) =
+                mpsc::channel::<MailboxItem>(runtime_cfg.mailbox_capacity);
+            neuron.mailbox = Some(mailbox_tx);
+
+            let core_cloned = neuron.core.clone();
+            let id_cloned = neuron.get_id();
+            let mut w_core = neuron.core.write().await;
+            let in_flight = w_core.in_flight.clone();
+            let task_handler = w_core.receivers_task_tracker.spawn(
+                async move {
+                    while let Some((port, signal)) = mailbox_rx.recv().await {
+                        let _write_me_into_log =
+                            Self::receive(&id_cloned, &core_cloned, signal, port).await;
+                        Self::settle(&in_flight, 1);
+                    }
+                }
+                .instrument(neuron.span.clone()),
+            );
+            w_core.service_handlers.push(task_handler);
+        }
+
         let neuron = Arc::new(neuron);
 
         let neuron_cloned = neuron.clone();
 
-        let _ = neuron
-            .core
-            .read()
-            .await
-            .receivers_task_tracker
-            .spawn(async move {
+        let mut w_core = neuron.core.write().await;
+        let task_handler = w_core.receivers_task_tracker.spawn(
+            async move {
                 while let Ok(command) = commands_receiver.recv().await {
-                    match command {
-                        NeuronCommand::SwitchMonitoringMode(mode) => {
-                            neuron_cloned.switch_monitoring_mode(mode).await;
-                        }
-                    }
+                    neuron_cloned.handle_command(command).await;
                 }
-            });
+            }
+            .instrument(neuron.span.clone()),
+        );
+        w_core.service_handlers.push(task_handler);
+        drop(w_core);
+        neuron.span.in_scope(|| debug!(bias, "neuron built"));
 
         neuron
     }
 
+    /// Apply the network command. Returns false when the command is addressed
+    /// to another neuron or can not be applied.
+    fn handle_command(
+        &self,
+        command: NeuronCommand,
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+        Box::pin(async move {
+            match command {
+                NeuronCommand::SwitchMonitoringMode(mode) => {
+                    self.switch_monitoring_mode(mode).await;
+                }
+                NeuronCommand::SetMonitoringFilter(filter) => {
+                    self.set_monitoring_filter(filter).await;
+                }
+                NeuronCommand::Reset => self.reset().await,
+                NeuronCommand::Pause => self.pause().await,
+                NeuronCommand::Resume => self.resume().await,
+                NeuronCommand::UpdateSynapse {
+                    port,
+                    weight,
+                    capacity,
+                } => return self.update_synapse(port, weight, capacity).await.is_ok(),
+                NeuronCommand::UpdateBias(bias) => self.update_bias(bias).await,
+                NeuronCommand::For(id, command) => {
+                    return id == self.id && self.handle_command(*command).await;
+                }
+                NeuronCommand::GetConfig { reply } => {
+                    let _send_reply_result = reply.send(self.get_config().await).await;
+                }
+                NeuronCommand::GetSample { reply } => {
+                    let _send_reply_result = reply.send(self.sample().await).await;
+                }
+                NeuronCommand::Confirmed { command, reply } => {
+                    let applied = self.handle_command(*command).await;
+                    if applied {
+                        let _send_reply_result = reply.send(self.get_id()).await;
+                    }
+                    return applied;
+                }
+            }
+            true
+        })
+    }
+
     /// Receive signal by neuron through port
     pub async fn receive(
         id: &str,
         core: &Arc<RwLock<NeuronCore>>,
         signal: Signal,
         port: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        Self::receive_at(id, core, signal, port, None).await
+    }
+
+    /// Receive the signal of the network's shard (RoutingBackend::Sharded).
+    pub async fn receive_signal(&self, signal: Signal, port: usize) -> Result<(), Box<dyn Error>> {
+        Self::receive(&self.id, &self.core, signal, port).await
+    }
+
+    /// Receive the signal event of the network's queue (RoutingBackend::EventDriven).
+    pub async fn receive_event(&self, event: &SignalEvent) -> Result<(), Box<dyn Error>> {
+        Self::receive_at(
+            &self.id,
+            &self.core,
+            event.signal,
+            event.dst_synapse_idx,
+            Some(event.time),
+        )
+        .await
+    }
+
+    /// Signals without the event time (from input ports) come at the current time of the queue.
+    async fn receive_at(
+        id: &str,
+        core: &Arc<RwLock<NeuronCore>>,
+        signal: Signal,
+        port: usize,
+        time: Option<u64>,
     ) -> Result<(), Box<dyn Error>> {
         let t_handler = {
             let mut w_core = core.write().await;
+            if let Some(time) = time {
+                w_core.event_time = time;
+            } else if let Some(event_queue) = w_core.event_queue.as_ref() {
+                w_core.event_time = event_queue.now();
+            }
+            if let Some(paused_signals) = w_core.paused_signals.as_mut() {
+                if paused_signals.len() < PAUSE_BUFFER_CAPACITY {
+                    paused_signals.push((port, signal));
+                }
+                return Ok(());
+            }
+            if Self::is_refractory(&w_core) {
+                w_core.refractory_counter += 1;
+                trace!(id, port, signal, "signal ignored in refractory period");
+                return Ok(());
+            }
+            trace!(id, port, signal, "signal received");
             {
                 w_core.hit_counter += 1;
             }
+            Self::leak(&mut w_core);
 
-            let monitoring_mode = w_core.monitoring_mode.clone();
+            let monitored = Self::is_monitored(id, &mut w_core, StatusMask::NEURON);
+            let policy = w_core.accumulator_overflow;
             if let Some(input) = w_core.dendrites.get_mut(&port) {
                 let signal = Self::synapse_accept_signal(input, signal);
 
-                let signal: Weight = Self::dendrite_weighting_signal(input, signal);
+                let signal = Self::dendrite_weighting_signal(input, signal, policy)
+                    .ok_or_else(|| Box::new(RnnError::AccumulatorOverflow(id.to_string())))?;
 
-                Self::process_signal(w_core, signal, port)?;
+                Self::process_signal(id, w_core, signal, port)?;
 
-                if monitoring_mode == MonitoringMode::Monitoring {
+                if monitored {
                     let id = String::from(id);
                     let core_cloned = core.clone();
                     tokio::task::spawn(async move {
@@ -186,6 +452,80 @@ impl Neuron {
         t_handler.await?.map_err(|e| Box::new(e) as Box<dyn Error>)
     }
 
+    /// Decay the accumulator toward the bias for the time since the latest update.
+    #[inline]
+    fn leak(core: &mut NeuronCore) {
+        let Some(tau) = core.leak_time_constant else {
+            return;
+        };
+        let now = core.clock.now();
+        if let Some(accumulated_at) = core.accumulated_at {
+            let elapsed = (now - accumulated_at).to_std().unwrap_or_default();
+            let decay = (-elapsed.as_secs_f64() / tau.max(f64::MIN_POSITIVE)).exp();
+            let excess = (core.accumulator as f64 - core.bias as f64) * decay;
+            core.accumulator = (core.bias as f64 + excess).round() as Weight;
+        }
+        core.accumulated_at = Some(now);
+    }
+
+    /// The status of the kind passes the monitoring mode, the filter and sampling.
+    #[inline]
+    fn is_monitored(id: &str, core: &mut NeuronCore, kind: StatusMask) -> bool {
+        if core.monitoring_mode != MonitoringMode::Monitoring
+            || !core.monitoring_filter.accepts(id, kind)
+        {
+            return false;
+        }
+        let sampled = core.sampled_statuses % core.monitoring_filter.sample_every.max(1) == 0;
+        core.sampled_statuses += 1;
+        sampled
+    }
+
+    #[inline]
+    fn is_refractory(core: &NeuronCore) -> bool {
+        core.fired_at.is_some_and(|fired_at| {
+            !core.refractory_period.is_zero()
+                && core.clock.now() - fired_at < core.refractory_period
+        })
+    }
+
+    /// Count the delivered signal as processed (SimulationMode::Stepped).
+    #[inline]
+    fn settle(in_flight: &Option<Arc<InFlight>>, count: usize) {
+        if let Some(in_flight) = in_flight {
+            in_flight.settle(count);
+        }
+    }
+
+    /// Count signals lost by the lagging synapse and report them as Status::PortLagged.
+    async fn lagged(
+        id: &str,
+        core: &Arc<RwLock<NeuronCore>>,
+        port: usize,
+        dropped_count: u64,
+    ) -> Result<(), RnnError> {
+        let (monitored, timestamp) = {
+            let mut w_core = core.write().await;
+            if let Some(dendrite) = w_core.dendrites.get_mut(&port) {
+                dendrite.dropped_count += dropped_count;
+            }
+            (
+                Self::is_monitored(id, &mut w_core, StatusMask::PORT_LAGGED),
+                w_core.clock.now(),
+            )
+        };
+        if !monitored {
+            return Ok(());
+        }
+        let status = Status::PortLagged(LagInfo {
+            timestamp,
+            id: id.to_string(),
+            port,
+            dropped_count,
+        });
+        Self::send_status(id, core, status).await
+    }
+
     /// Send only positive signal otherwise suppress transmission. Need to stop endless looping zero signals
     pub fn send(axon: Arc<Sender<Signal>>, signal: Signal) -> Result<usize, Box<dyn Error>> {
         if signal > 0 {
@@ -212,6 +552,7 @@ impl Neuron {
                 synapse_capacity: input_cfg.capacity_max,
                 connected: None,
                 synapse: None,
+                dropped_count: 0,
             };
             w_core.dendrites.insert(port, dendrite);
         }
@@ -222,17 +563,157 @@ impl Neuron {
         let r_core = self.core.read().await;
         let input_configs = r_core
             .dendrites
-            .iter()
-            .map(|(_idx, dendrite)| dendrite.config.clone())
+            .values()
+            .map(|dendrite| dendrite.config.clone())
             .collect::<Vec<InputCfg>>();
 
         NeuronCfg {
             id: self.get_id(),
             bias: r_core.bias,
             input_configs,
+            lineage: self.lineage.clone(),
+            kind: self.kind,
+        }
+    }
+
+    /// Current accumulator, fires counter and dendrite weights.
+    pub async fn sample(&self) -> ProbeSample {
+        let r_core = self.core.read().await;
+        ProbeSample {
+            timestamp: r_core.clock.now(),
+            id: self.get_id(),
+            accumulator: r_core.accumulator,
+            reset_count: r_core.reset_counter,
+            weights: r_core
+                .dendrites
+                .values()
+                .map(|dendrite| dendrite.config.weight)
+                .collect(),
+        }
+    }
+
+    /// Capture runtime state which is not described by the config.
+    pub async fn get_state(&self) -> NeuronState {
+        let r_core = self.core.read().await;
+        NeuronState {
+            id: self.get_id(),
+            accumulator: r_core.accumulator,
+            reset_count: r_core.reset_counter,
+            hit_count: r_core.hit_counter,
+            refractory_count: r_core.refractory_counter,
+            fired_at: r_core.fired_at,
+            accumulated_at: r_core.accumulated_at,
+            input_hits: r_core.input_hits.iter().copied().collect(),
+            synapse_capacities: r_core
+                .dendrites
+                .iter()
+                .map(|(port, dendrite)| (*port, dendrite.synapse_capacity))
+                .collect(),
+            dropped_signals: r_core
+                .dendrites
+                .iter()
+                .map(|(port, dendrite)| (*port, dendrite.dropped_count))
+                .collect(),
+            paused_signals: r_core.paused_signals.clone(),
+        }
+    }
+
+    /// Bring the neuron into the captured state. Ports missing in the neuron are
+    /// ignored and synapse capacities are limited by configured capacities.
+    pub async fn set_state(&self, state: &NeuronState) {
+        let mut w_core = self.core.write().await;
+        w_core.accumulator = state.accumulator;
+        w_core.reset_counter = state.reset_count;
+        w_core.hit_counter = state.hit_count;
+        w_core.refractory_counter = state.refractory_count;
+        w_core.fired_at = state.fired_at;
+        w_core.accumulated_at = state.accumulated_at;
+        w_core.input_hits = state
+            .input_hits
+            .iter()
+            .filter(|port| w_core.dendrites.contains_key(*port))
+            .copied()
+            .collect();
+        for (port, dendrite) in w_core.dendrites.iter_mut() {
+            if let Some(capacity) = state.synapse_capacities.get(port) {
+                dendrite.synapse_capacity = min(*capacity, dendrite.config.capacity_max);
+            }
+            if let Some(dropped_count) = state.dropped_signals.get(port) {
+                dendrite.dropped_count = *dropped_count;
+            }
+        }
+        w_core.paused_signals = state.paused_signals.clone();
+    }
+
+    pub fn get_kind(&self) -> NeuronKind {
+        self.kind
+    }
+
+    /// Change weight and capacity of the dendrite without dropping its connection.
+    /// Regeneration and current synapse capacity are limited by the new capacity.
+    pub async fn update_synapse(
+        &self,
+        port: usize,
+        weight: Option<Weight>,
+        capacity: Option<Signal>,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut w_core = self.core.write().await;
+        let dendrite = w_core
+            .dendrites
+            .get_mut(&port)
+            .ok_or_else(|| Box::new(RnnError::DendriteNotFound(port)))?;
+        if let Some(weight) = weight {
+            dendrite.config.weight = weight;
+        }
+        if let Some(capacity) = capacity {
+            dendrite.config.capacity_max = capacity;
+            dendrite.config.regeneration = min(dendrite.config.regeneration, capacity);
+            dendrite.synapse_capacity = min(dendrite.synapse_capacity, capacity);
+        }
+        Ok(())
+    }
+
+    /// Change the bias. It takes effect since the next accumulation cycle.
+    pub async fn update_bias(&self, bias: Weight) {
+        self.core.write().await.bias = bias;
+    }
+
+    /// Bring runtime state back to configured values: the accumulator holds the bias,
+    /// counters and input hits are cleared and synapses have maximal capacity.
+    pub async fn reset(&self) {
+        let mut w_core = self.core.write().await;
+        w_core.accumulator = w_core.bias;
+        w_core.reset_counter = 0;
+        w_core.hit_counter = 0;
+        w_core.refractory_counter = 0;
+        w_core.fired_at = None;
+        w_core.accumulated_at = None;
+        w_core.input_hits.clear();
+        for dendrite in w_core.dendrites.values_mut() {
+            dendrite.synapse_capacity = dendrite.config.capacity_max;
         }
     }
 
+    /// Buffer incoming signals instead of processing them.
+    pub async fn pause(&self) {
+        let mut w_core = self.core.write().await;
+        if w_core.paused_signals.is_none() {
+            w_core.paused_signals = Some(vec![]);
+        }
+    }
+
+    /// Process signals buffered while paused in arrival order and continue processing.
+    pub async fn resume(&self) {
+        let paused_signals = self.core.write().await.paused_signals.take();
+        for (port, signal) in paused_signals.into_iter().flatten() {
+            let _write_me_into_log = Self::receive(&self.id, &self.core, signal, port).await;
+        }
+    }
+
+    pub async fn is_paused(&self) -> bool {
+        self.core.read().await.paused_signals.is_some()
+    }
+
     pub async fn switch_monitoring_mode(&self, mode: MonitoringMode) {
         let mut w_core = self.core.write().await;
         w_core.monitoring_mode = mode;
@@ -242,29 +723,42 @@ impl Neuron {
         self.core.read().await.monitoring_mode.clone()
     }
 
+    pub async fn set_monitoring_filter(&self, filter: MonitoringFilter) {
+        let mut w_core = self.core.write().await;
+        w_core.monitoring_filter = filter;
+        w_core.sampled_statuses = 0;
+    }
+
     /// Provides access to a channel (axon) for receiving signals from a given neuron.
     pub async fn provide_output(&self) -> Arc<RwLock<Receiver<Signal>>> {
-        let rx = {
-            let mut w_core = self.core.write().await;
-            w_core.axon.clone().as_deref().map_or_else(
-                || {
-                    let (tx, rx) = broadcast::channel::<Signal>(5);
-                    w_core.axon = Arc::new(Some(Arc::new(tx)));
-                    rx
-                },
-                |tx| tx.subscribe(),
-            )
-        };
+        Arc::new(RwLock::new(self.subscribe().await))
+    }
 
-        Arc::new(RwLock::new(rx))
+    /// Subscribe to the neuron's axon. The axon is created with the first subscription.
+    pub async fn subscribe(&self) -> Receiver<Signal> {
+        let mut w_core = self.core.write().await;
+        w_core.axon.clone().as_deref().map_or_else(
+            || {
+                let (tx, rx) = broadcast::channel::<Signal>(w_core.axon_capacity);
+                w_core.axon = Arc::new(Some(Arc::new(tx)));
+                rx
+            },
+            |tx| tx.subscribe(),
+        )
     }
 
     /// Link to a specific input (synapse) of a neuron.
     /// A synapse can only have one connection.
     /// However, a neuron can have many synapses at the same time.
     pub async fn link_to(&self, party: Arc<Neuron>, port: usize) -> Result<(), Box<dyn Error>> {
+        self.check_closed_loop(&party.get_id()).await?;
         let out = self.provide_output().await;
-        let party_id = party.get_id();
+        party.connect(&self.id, port, out).await
+    }
+
+    /// Check if link to the party is allowed. The neuron may be connected to itself
+    /// only once and only when it has more then one dendrite.
+    pub async fn check_closed_loop(&self, party_id: &str) -> Result<(), Box<dyn Error>> {
         if party_id == self.id {
             let r_core = self.core.read().await;
             let dendrites = &r_core.dendrites;
@@ -273,14 +767,38 @@ impl Neuron {
                 .filter(|(_, d)| {
                     d.connected
                         .as_ref()
-                        .is_some_and(|connected| connected.to_string() == self.id)
+                        .is_some_and(|connected| *connected == self.id)
                 })
                 .count();
             if r_core.dendrites.len() < 2 || self_connected_dendrites_count > 0_usize {
                 return Err(Box::new(RnnError::ClosedLoop));
             }
         }
-        party.connect(&self.id, port, out).await
+        Ok(())
+    }
+
+    /// Occupy the synapse by the party whose signals come through the neuron's mailbox.
+    pub async fn attach(&self, src_id: &str, port: usize) -> Result<(), Box<dyn Error>> {
+        let mut w_core = self.core.write().await;
+        if let Some(dendrite) = w_core.dendrites.get_mut(&port) {
+            if dendrite.connected.is_none() {
+                dendrite.connected = Some(src_id.to_string());
+                dendrite.synapse_capacity = dendrite.config.capacity_max;
+                Ok(())
+            } else {
+                Err(Box::new(RnnError::PortBusy(format!(
+                    "input port {} already connected",
+                    port
+                ))))
+            }
+        } else {
+            Err(Box::new(RnnError::DendriteNotFound(port)))
+        }
+    }
+
+    /// Get sender half of the neuron's mailbox.
+    pub fn get_mailbox(&self) -> Option<mpsc::Sender<MailboxItem>> {
+        self.mailbox.clone()
     }
 
     pub async fn connect(
@@ -302,6 +820,7 @@ impl Neuron {
 
                     // Set synapse current capacity
                     dendrite.synapse_capacity = dendrite.config.capacity_max;
+                    dendrite.dropped_count = 0;
 
                     // Set receiver half of connecting channel
 
@@ -310,6 +829,9 @@ impl Neuron {
                     let synapse = dendrite.synapse.as_ref().unwrap().clone();
                     let core_cloned = self.core.clone();
                     let id_cloned = self.get_id();
+                    let in_flight = w_core.in_flight.clone();
+                    let consumed = w_core.consumed.clone();
+                    let lag_recovery = w_core.lag_recovery;
 
                     // Check if already has task_handler at specified port number
                     if let Entry::Occupied(task_entry) =
@@ -322,13 +844,51 @@ impl Neuron {
                         // clear entry
                         task_entry.remove();
                     }
-                    let task_handler = w_core.receivers_task_tracker.spawn(async move {
-                        let mut w_synapse = synapse.write().await;
-                        while let Ok(signal) = w_synapse.recv().await {
-                            let write_me_into_log =
-                                Self::receive(&id_cloned, &core_cloned, signal, port).await;
+                    let task_handler = w_core.receivers_task_tracker.spawn(
+                        async move {
+                            // Dropped last, after the receiver is released.
+                            let _released = ReleaseNotifier(consumed.clone());
+                            let synapse = synapse;
+                            let mut w_synapse = synapse.write().await;
+                            loop {
+                                let signal = match w_synapse.recv().await {
+                                    Ok(signal) => signal,
+                                    Err(RecvError::Lagged(skipped)) => {
+                                        let mut dropped_count = skipped;
+                                        let mut latest = None;
+                                        if lag_recovery == LagRecovery::SkipToLatest {
+                                            while let Ok(signal) = w_synapse.try_recv() {
+                                                dropped_count +=
+                                                    latest.replace(signal).is_some() as u64;
+                                            }
+                                        }
+                                        Self::settle(&in_flight, dropped_count as usize);
+                                        let _write_me_into_log = Self::lagged(
+                                            &id_cloned,
+                                            &core_cloned,
+                                            port,
+                                            dropped_count,
+                                        )
+                                        .await;
+                                        match latest {
+                                            Some(signal) => signal,
+                                            None => continue,
+                                        }
+                                    }
+                                    Err(RecvError::Closed) => break,
+                                };
+                                if let Some(consumed) = consumed.as_ref() {
+                                    consumed.notify_waiters();
+                                }
+                                let _write_me_into_log =
+                                    Self::receive(&id_cloned, &core_cloned, signal, port).await;
+                                Self::settle(&in_flight, 1);
+                            }
                         }
-                    });
+                        .instrument(self.span.clone()),
+                    );
+                    self.span
+                        .in_scope(|| debug!(src_id, port, "synapse connected"));
 
                     w_core
                         .synapse_connection_handlers
@@ -349,11 +909,82 @@ impl Neuron {
         }
     }
 
+    /// Free the synapse: stop its listener task and forget the connected party.
+    /// Returns id of the party which was connected.
+    pub async fn disconnect(&self, port: usize) -> Result<String, Box<dyn Error>> {
+        let mut w_core = self.core.write().await;
+        let dendrite = w_core
+            .dendrites
+            .get_mut(&port)
+            .ok_or_else(|| Box::new(RnnError::DendriteNotFound(port)))?;
+        let party_id = dendrite
+            .connected
+            .take()
+            .ok_or_else(|| Box::new(RnnError::PortAlreadyFree))?;
+        dendrite.synapse = None;
+        if let Some(task_handler) = w_core.synapse_connection_handlers.remove(&port) {
+            task_handler.abort();
+        }
+        w_core.input_hits.remove(&port);
+        self.span
+            .in_scope(|| debug!(party_id = %party_id, port, "synapse disconnected"));
+        Ok(party_id)
+    }
+
     /// Get network which contains this neuron
     pub fn get_network(&self) -> Option<Arc<Network>> {
         self.network.upgrade()
     }
 
+    /// Get parties connected to the neuron's synapses: dendrite index -> party id.
+    /// The party is another neuron or network's input port.
+    pub async fn get_connections(&self) -> BTreeMap<usize, String> {
+        self.core
+            .read()
+            .await
+            .dendrites
+            .iter()
+            .filter_map(|(port, dendrite)| dendrite.connected.clone().map(|id| (*port, id)))
+            .collect()
+    }
+
+    /// Stop all neuron's tasks: synapse listeners, mailbox reader and commands receiver.
+    pub async fn shutdown(&self) {
+        let mut w_core = self.core.write().await;
+        for (_, task_handler) in w_core.synapse_connection_handlers.drain() {
+            task_handler.abort();
+        }
+        for task_handler in w_core.service_handlers.drain(..) {
+            task_handler.abort();
+        }
+        w_core.receivers_task_tracker.close();
+        self.span.in_scope(|| debug!("neuron shut down"));
+    }
+
+    /// Live neuron's tasks: commands receiver, mailbox reader and synapse listeners.
+    pub async fn tasks(&self) -> TaskReport {
+        let r_core = self.core.read().await;
+        let live = |handlers: &[&JoinHandle<()>]| {
+            handlers
+                .iter()
+                .filter(|handler| !handler.is_finished())
+                .count()
+        };
+        // the mailbox reader is spawned before the commands receiver
+        let service_handlers: Vec<&JoinHandle<()>> = r_core.service_handlers.iter().collect();
+        let (mailbox_handlers, command_handlers) = service_handlers
+            .split_at((self.mailbox.is_some() as usize).min(service_handlers.len()));
+        let synapse_handlers: Vec<&JoinHandle<()>> =
+            r_core.synapse_connection_handlers.values().collect();
+
+        TaskReport {
+            command_loops: live(command_handlers),
+            mailbox_readers: live(mailbox_handlers),
+            synapse_listeners: live(&synapse_handlers),
+            ..TaskReport::default()
+        }
+    }
+
     /// Get neuron's id
     pub fn get_id(&self) -> String {
         self.id.clone()
@@ -369,22 +1000,70 @@ impl Neuron {
     }
 
     /// send neuron state to monitoring network receiver.
+    /// The full channel is handled according to MonitoringOverflow policy.
     pub async fn send_monitoring_statistics(
         id: &str,
         core: &Arc<RwLock<NeuronCore>>,
     ) -> Result<(), RnnError> {
         let statistics = Self::prepare_status(id, core).await;
+        Self::send_status(id, core, statistics).await
+    }
 
-        let r_core = core.read().await;
-        if let Some(sender) = r_core.monitoring_sender.upgrade() {
-            sender.send(statistics).await.map_err(|e| {
-                RnnError::MonitoringChannelClosed(format!(
-                    "Statistics sending error due channel closed. Lost stat: {:?}",
-                    e
-                ))
-            })
-        } else {
-            Ok(())
+    /// Send the status into the network's monitoring channel applying MonitoringOverflow.
+    async fn send_status(
+        id: &str,
+        core: &Arc<RwLock<NeuronCore>>,
+        statistics: Status,
+    ) -> Result<(), RnnError> {
+        let (sender, monitoring_overflow) = {
+            let r_core = core.read().await;
+            (
+                r_core.monitoring_sender.upgrade(),
+                r_core.monitoring_overflow,
+            )
+        };
+        let Some(sender) = sender else {
+            return Ok(());
+        };
+        let closed = |e: &dyn std::fmt::Debug| {
+            RnnError::MonitoringChannelClosed(format!(
+                "Statistics sending error due channel closed. Lost stat: {:?}",
+                e
+            ))
+        };
+
+        match monitoring_overflow {
+            MonitoringOverflow::Block => sender.send(statistics).await.map_err(|e| closed(&e)),
+            MonitoringOverflow::Drop => match sender.try_send(statistics) {
+                Ok(()) => Ok(()),
+                Err(TrySendError::Full(_)) => {
+                    core.read().await.monitoring_counters.count_dropped();
+                    Err(RnnError::MonitoringChannelFull(id.to_string()))
+                }
+                Err(TrySendError::Closed(statistics)) => Err(closed(&statistics)),
+            },
+            MonitoringOverflow::Coalesce => {
+                let mut w_core = core.write().await;
+                if let Some(pending_status) = w_core.pending_status.take() {
+                    match sender.try_send(pending_status) {
+                        Ok(()) => {}
+                        Err(TrySendError::Full(_)) => {
+                            w_core.monitoring_counters.count_coalesced();
+                            w_core.pending_status = Some(statistics);
+                            return Ok(());
+                        }
+                        Err(TrySendError::Closed(status)) => return Err(closed(&status)),
+                    }
+                }
+                match sender.try_send(statistics) {
+                    Ok(()) => Ok(()),
+                    Err(TrySendError::Full(statistics)) => {
+                        w_core.pending_status = Some(statistics);
+                        Ok(())
+                    }
+                    Err(TrySendError::Closed(statistics)) => Err(closed(&statistics)),
+                }
+            }
         }
     }
 
@@ -401,8 +1080,18 @@ impl Neuron {
         };
         let reset_count = r_core.reset_counter;
         let hit_count = r_core.hit_counter;
-        let total_weight = r_core.dendrites.values().map(|d| d.config.weight).sum();
-        let now = Utc::now();
+        let refractory_count = r_core.refractory_counter;
+        let kind = r_core.kind;
+        let total_weight = r_core.dendrites.values().fold(0 as Weight, |total, d| {
+            total.saturating_add(d.config.weight)
+        });
+        let now = r_core.clock.now();
+        let dropped_signals = r_core
+            .dendrites
+            .iter()
+            .filter(|(_, dendrite)| dendrite.dropped_count > 0)
+            .map(|(port, dendrite)| (*port, dendrite.dropped_count))
+            .collect();
 
         Status::Neuron(NeuronInfo {
             timestamp: now,
@@ -415,6 +1104,9 @@ impl Neuron {
             reset_count,
             hit_count,
             total_weight,
+            dropped_signals,
+            refractory_count,
+            kind,
         })
     }
 
@@ -438,26 +1130,152 @@ impl Neuron {
     }
 
     #[inline]
-    fn dendrite_weighting_signal(input: &Dendrite, signal: Signal) -> Weight {
-        (signal as Weight) * input.config.weight
+    fn dendrite_weighting_signal(
+        input: &Dendrite,
+        signal: Signal,
+        policy: OverflowPolicy,
+    ) -> Option<Weight> {
+        policy.mul(signal as Weight, input.config.weight)
+    }
+
+    /// Transmit the output signal through the axon and routes of the routing table.
+    /// The axon gets the signal even if some routes failed, their error is returned after.
+    #[inline]
+    fn emit(id: &str, core: &NeuronCore, output_signal: Signal) -> Result<(), Box<dyn Error>> {
+        trace!(id, output_signal, "neuron fired");
+        let routed = Self::route(id, core, output_signal);
+
+        // check if axon has connections
+        let sent = if let Some(axon) = core.axon.as_ref().clone() {
+            if core.backpressure == BackpressurePolicy::Error && axon.len() >= core.axon_capacity {
+                Err(Box::new(RnnError::ChannelFull(id.to_string())) as Box<dyn Error>)
+            } else {
+                // send output signal through the axon
+                Self::send(axon.clone(), output_signal).map(|_| ())
+            }
+        } else if matches!(routed, Ok(0)) {
+            // Axon does not have any connections
+            Err(Box::new(RnnError::DeadEndAxon))
+        } else {
+            Ok(())
+        };
+        routed.and(sent)
+    }
+
+    /// Deliver the output signal into mailboxes of connected neurons
+    /// or into the network's event queue. Returns number of routes from the neuron.
+    #[inline]
+    fn route(id: &str, core: &NeuronCore, output_signal: Signal) -> Result<usize, Box<dyn Error>> {
+        let r_routing_table = core.routing_table.read().unwrap();
+        let routes = r_routing_table.get_routes(id);
+        if routes.is_empty() {
+            return Ok(0);
+        }
+
+        // Same as Self::send. Suppress zero signals to stop endless looping.
+        if output_signal == 0 {
+            return Err(Box::new(RnnError::SignalSuppressed));
+        }
+
+        // count signals in flight before delivery, so the network can't see them settled
+        if let Some(in_flight) = &core.in_flight {
+            in_flight.add(routes.len());
+        }
+        // the rest of routes get the signal when one of them fails
+        let mut failed_count = 0;
+        let mut error = None;
+        for route in routes.iter() {
+            match Self::deliver(core, route, output_signal) {
+                Ok(true) => {}
+                Ok(false) => failed_count += 1,
+                Err(route_error) => {
+                    failed_count += 1;
+                    error.get_or_insert(route_error);
+                }
+            }
+        }
+        if let Some(in_flight) = &core.in_flight {
+            in_flight.settle(failed_count);
+        }
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(routes.len()),
+        }
+    }
+
+    /// Returns false when the full mailbox or queue dropped the signal. The queue
+    /// can't drop its oldest signal and the neuron can't wait for it holding the lock,
+    /// so the new signal is dropped and counted unless BackpressurePolicy::Error rejects it.
+    #[inline]
+    fn deliver(
+        core: &NeuronCore,
+        route: &Route,
+        output_signal: Signal,
+    ) -> Result<bool, Box<dyn Error>> {
+        if let Some(mailbox) = route.mailbox.as_ref() {
+            let sent = mailbox.try_send((route.dst_synapse_idx, output_signal));
+            Self::check_delivery(core, route, sent)
+        } else if let Some(event_queue) = core.event_queue.as_ref() {
+            let sent = event_queue
+                .push(
+                    core.event_time + 1,
+                    &route.dst_id,
+                    route.dst_synapse_idx,
+                    output_signal,
+                )
+                .map_err(TrySendError::Full);
+            Self::check_delivery(core, route, sent)
+        } else if let Some(sharded_executor) = core.sharded_executor.as_ref() {
+            let sent =
+                sharded_executor.try_send(&route.dst_id, route.dst_synapse_idx, output_signal);
+            Self::check_delivery(core, route, sent)
+        } else {
+            Err(Box::new(RnnError::SignalSendError))
+        }
+    }
+
+    #[inline]
+    fn check_delivery<T>(
+        core: &NeuronCore,
+        route: &Route,
+        sent: Result<(), TrySendError<T>>,
+    ) -> Result<bool, Box<dyn Error>> {
+        match sent {
+            Ok(()) => Ok(true),
+            Err(TrySendError::Full(_)) if core.backpressure == BackpressurePolicy::Error => {
+                Err(Box::new(RnnError::ChannelFull(route.dst_id.clone())))
+            }
+            Err(TrySendError::Full(_)) => {
+                core.routing_counters.count_dropped();
+                Ok(false)
+            }
+            Err(TrySendError::Closed(_)) => Err(Box::new(RnnError::SignalSendError)),
+        }
     }
 
     #[inline]
     fn process_signal(
+        id: &str,
         mut w_core: RwLockWriteGuard<NeuronCore>,
         weighted_signal: Weight,
         port: usize,
     ) -> Result<(), Box<dyn Error>> {
+        let policy = w_core.accumulator_overflow;
+        let overflow = || Box::new(RnnError::AccumulatorOverflow(id.to_string()));
         if w_core.input_hits.contains(&port) {
             // The Repeat signal case
             // A signal is being prepared for output through the axon
             let output_signal = max(w_core.accumulator, 0) as Signal;
 
             // Reset accumulator with new signal plus excitation level
-            w_core.accumulator = weighted_signal + w_core.bias;
+            w_core.accumulator = policy
+                .add(weighted_signal, w_core.bias)
+                .ok_or_else(overflow)?;
 
             // Increment neuron resets counter
             w_core.reset_counter += 1;
+            w_core.fired_at = Some(w_core.clock.now());
 
             // Reset hits register
             w_core.input_hits.clear();
@@ -465,17 +1283,12 @@ impl Neuron {
             // Store fact of signal hit to current port
             w_core.input_hits.insert(port);
 
-            // check if axon has connections
-            if let Some(axon) = w_core.axon.as_ref().clone() {
-                // send output signal through the axon
-                Self::send(axon.clone(), output_signal).map(|_| ())
-            } else {
-                // Axon does not have any connections
-                Err(Box::new(RnnError::DeadEndAxon))
-            }
+            Self::emit(id, &w_core, output_signal)
         } else {
             // Add signal value to accumulator
-            w_core.accumulator += weighted_signal as i16;
+            w_core.accumulator = policy
+                .add(w_core.accumulator, weighted_signal)
+                .ok_or_else(overflow)?;
 
             // Store fact of signal hit to current port
             w_core.input_hits.insert(port);
@@ -491,18 +1304,12 @@ impl Neuron {
 
                 // Increment neuron resets counter
                 w_core.reset_counter += 1;
+                w_core.fired_at = Some(w_core.clock.now());
 
                 // Reset hits register
                 w_core.input_hits.clear();
 
-                // check if axon has connections
-                if let Some(axon) = w_core.axon.as_ref().clone() {
-                    // send output signal through the axon
-                    Self::send(axon.clone(), output_signal).map(|_| ())
-                } else {
-                    // Axon does not have any connections
-                    Err(Box::new(RnnError::DeadEndAxon))
-                }
+                Self::emit(id, &w_core, output_signal)
             } else {
                 Ok(())
             }
@@ -676,7 +1483,7 @@ mod tests {
             if let Status::Neuron(stat) = net.get_current_neuron_status(&neuron_id).await.unwrap() {
                 assert_eq!(stat.receiver_count, 1);
             } else {
-                assert!(false);
+                panic!("Incorrect state format");
             }
         }
 
@@ -705,8 +1512,41 @@ mod tests {
                 assert_eq!(stat.hit_count, 1);
                 assert_eq!(stat.reset_count, 1);
             } else {
-                assert!(false);
+                panic!("Incorrect state format");
+            }
+        }
+
+        #[tokio::test]
+        async fn lagging_synapse_should_count_and_report_dropped_signals() {
+            let net = Arc::new(new_network_fixture());
+            net.set_monitoring_mode(MonitoringMode::Monitoring).await;
+            let neuron = new_neuron_fixture(net.clone(), 1, vec![]).await;
+            let neuron_id = neuron.get_id();
+
+            let (tx, rx) = broadcast::channel(1);
+            assert!(neuron
+                .connect("M0I0", 0, Arc::new(RwLock::new(rx)))
+                .await
+                .is_ok());
+            for _ in 0..3 {
+                assert!(tx.send(1).is_ok());
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+
+            if let Status::Neuron(stat) = net.get_current_neuron_status(&neuron_id).await.unwrap() {
+                assert_eq!(stat.dropped_signals, BTreeMap::from([(0, 2)]));
+                assert_eq!(stat.hit_count, 1);
+            } else {
+                panic!("Incorrect state format");
             }
+            assert!(net
+                .pop_monitoring_store()
+                .await
+                .iter()
+                .any(|status| matches!(
+                    status,
+                    Status::PortLagged(info) if info.port == 0 && info.dropped_count == 2
+                )));
         }
 
         #[tokio::test]
@@ -740,7 +1580,7 @@ mod tests {
                 assert_eq!(info.reset_count, 1);
                 assert_eq!(info.hit_count, 1);
             } else {
-                assert!(false);
+                panic!("Incorrect state format");
             }
         }
 
@@ -779,7 +1619,7 @@ mod tests {
                 assert_eq!(info.dendrite_connected_count, 0);
                 assert_eq!(info.total_weight, 6);
             } else {
-                assert!(false);
+                panic!("Incorrect state format");
             }
         }
 
@@ -812,5 +1652,72 @@ mod tests {
                     && cfg.weight == current_value as i16
             }));
         }
+
+        #[tokio::test]
+        async fn fn_update_synapse_should_change_only_specified_parameters() {
+            let net = Arc::new(new_network_fixture());
+            let neuron =
+                new_neuron_fixture(net.clone(), 1, gen_neuron_input_config_fixture(3)).await;
+
+            assert!(neuron.update_synapse(2, Some(-5), None).await.is_ok());
+            assert!(neuron.update_synapse(1, None, Some(1)).await.is_ok());
+            assert!(neuron.update_synapse(3, Some(1), None).await.is_err());
+            neuron.update_bias(4).await;
+
+            let cfg = neuron.get_config().await;
+            assert_eq!(cfg.bias, 4);
+            assert_eq!(cfg.input_configs[2], InputCfg::new(3, 3, -5).unwrap());
+            assert_eq!(cfg.input_configs[1], InputCfg::new(1, 1, 2).unwrap());
+            assert_eq!(cfg.input_configs[0], InputCfg::new(1, 1, 1).unwrap());
+        }
+    }
+
+    mod for_overflowed_accumulator {
+        use crate::rnn::common::runtime_cfg::RuntimeCfg;
+        use crate::rnn::tests::fixtures::new_neuron_fixture;
+
+        use super::*;
+
+        async fn neuron_fixture(policy: OverflowPolicy) -> Arc<Neuron> {
+            let net = Arc::new(
+                Network::with_runtime_cfg(RuntimeCfg {
+                    accumulator_overflow: policy,
+                    ..RuntimeCfg::default()
+                })
+                .unwrap(),
+            );
+            let neuron = new_neuron_fixture(
+                net.clone(),
+                0,
+                vec![InputCfg::new(255, 255, 200).unwrap(); 2],
+            )
+            .await;
+            for port in 0..2 {
+                assert!(net.setup_input(port, &neuron.get_id(), port).await.is_ok());
+            }
+            neuron
+        }
+
+        #[tokio::test]
+        async fn accumulator_should_follow_overflow_policy() {
+            for (policy, expected) in [
+                (OverflowPolicy::Saturate, Some(i16::MAX)),
+                (
+                    OverflowPolicy::Wrap,
+                    Some(1_i16.wrapping_add(255_i16.wrapping_mul(200))),
+                ),
+                (OverflowPolicy::Error, None),
+            ] {
+                let neuron = neuron_fixture(policy).await;
+                let core = neuron.get_core();
+                let result = Neuron::receive(&neuron.get_id(), &core, 255, 0).await;
+
+                assert_eq!(result.is_ok(), expected.is_some());
+                assert_eq!(core.read().await.accumulator, expected.unwrap_or(1));
+                if let Err(error) = result {
+                    assert!(error.to_string().contains("AccumulatorOverflow"));
+                }
+            }
+        }
     }
 }
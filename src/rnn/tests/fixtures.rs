@@ -1,7 +1,12 @@
 use std::sync::Arc;
 
 use crate::rnn::{
-    common::{input_cfg::InputCfg, signal::Weight},
+    common::{
+        input_cfg::InputCfg,
+        network_cfg::NeuronKind,
+        runtime_cfg::{RoutingBackend, RuntimeCfg, SimulationMode},
+        signal::Weight,
+    },
     layouts::network::Network,
     neural::neuron::Neuron,
 };
@@ -10,13 +15,46 @@ pub fn new_network_fixture() -> Network {
     Network::new().unwrap()
 }
 
+pub fn new_mailbox_network_fixture() -> Network {
+    Network::with_runtime_cfg(RuntimeCfg {
+        routing: RoutingBackend::Mailbox,
+        ..RuntimeCfg::default()
+    })
+    .unwrap()
+}
+
+pub fn new_event_driven_network_fixture() -> Network {
+    Network::with_runtime_cfg(RuntimeCfg {
+        routing: RoutingBackend::EventDriven,
+        ..RuntimeCfg::default()
+    })
+    .unwrap()
+}
+
+pub fn new_sharded_network_fixture() -> Network {
+    Network::with_runtime_cfg(RuntimeCfg {
+        routing: RoutingBackend::Sharded,
+        ..RuntimeCfg::default()
+    })
+    .unwrap()
+}
+
+pub fn new_stepped_network_fixture() -> Network {
+    Network::with_runtime_cfg(RuntimeCfg {
+        routing: RoutingBackend::Mailbox,
+        simulation: SimulationMode::Stepped,
+        ..RuntimeCfg::default()
+    })
+    .unwrap()
+}
+
 pub async fn new_neuron_fixture(
     network: Arc<Network>,
     bias: Weight,
     input_config: Vec<InputCfg>,
 ) -> Arc<Neuron> {
     network
-        .create_neuron(network.clone(), bias, input_config)
+        .create_neuron(network.clone(), bias, input_config, NeuronKind::Excitatory)
         .await
         .unwrap()
 }
@@ -25,7 +63,6 @@ pub async fn new_neuron_fixture(
 /// Each dendrite should have configuration with values plus one then before it.
 pub fn gen_neuron_input_config_fixture(size: u8) -> Vec<InputCfg> {
     (1..=size)
-        .into_iter()
         .map(|i| InputCfg {
             capacity_max: i,
             regeneration: i,
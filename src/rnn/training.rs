@@ -0,0 +1,8 @@
+pub mod checkpoint;
+pub mod crossover;
+pub mod encoding;
+pub mod homeostasis;
+pub mod inference;
+pub mod metrics;
+pub mod model;
+pub mod reservoir_trainer;
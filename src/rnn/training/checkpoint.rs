@@ -0,0 +1,168 @@
+//! Checkpointing hooks of the training loop (see ReservoirTrainer::fit()):
+//! TrainerCallbacks are called after every epoch and when the epoch error improves,
+//! BestCheckpointer saves the best-so-far config as the model file and keeps only
+//! the latest of them, so long runs are not lost on crash.
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::network_cfg::NetworkCfg;
+
+use super::model::{Model, TrainingMetadata, MODEL_EXTENSION};
+
+/// The result of the training epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochReport {
+    /// The number of the epoch from zero
+    pub epoch: u64,
+
+    /// The sum of absolute output errors over the epoch
+    pub error: u64,
+
+    /// The lowest error of epochs so far including this one
+    pub best_error: u64,
+}
+
+/// Hooks of the training loop. Errors returned by callbacks stop the training.
+pub trait TrainerCallbacks {
+    /// Called after every epoch.
+    fn on_epoch_end(&mut self, _report: &EpochReport) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    /// Called after on_epoch_end() when the epoch error is lower than errors of all
+    /// previous epochs, the config is the trained network after the epoch.
+    fn on_improvement(
+        &mut self,
+        _report: &EpochReport,
+        _config: &NetworkCfg,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
+
+/// How many saved checkpoints are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Retention {
+    All,
+
+    /// Only the given number of the latest (so the best) checkpoints, at least one
+    Latest(usize),
+}
+
+/// Saves the best-so-far config into `{dir}/{dataset}-epoch{epoch}.runen` model
+/// files with the epoch error as the "error" metric.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BestCheckpointer {
+    pub dir: PathBuf,
+
+    /// The name of the training data written into the model metadata
+    pub dataset: String,
+
+    pub retention: Retention,
+
+    /// Kept checkpoints from the oldest one
+    saved: VecDeque<PathBuf>,
+}
+
+impl BestCheckpointer {
+    pub fn new(dir: &Path, dataset: &str, retention: Retention) -> Self {
+        BestCheckpointer {
+            dir: dir.to_path_buf(),
+            dataset: dataset.to_string(),
+            retention,
+            saved: VecDeque::new(),
+        }
+    }
+
+    /// Paths of kept checkpoints from the oldest one.
+    pub fn get_saved(&self) -> Vec<PathBuf> {
+        self.saved.iter().cloned().collect()
+    }
+
+    /// Path of the best checkpoint saved so far.
+    pub fn get_best(&self) -> Option<&Path> {
+        self.saved.back().map(PathBuf::as_path)
+    }
+}
+
+impl TrainerCallbacks for BestCheckpointer {
+    fn on_improvement(
+        &mut self,
+        report: &EpochReport,
+        config: &NetworkCfg,
+    ) -> Result<(), Box<dyn Error>> {
+        let path = self.dir.join(format!(
+            "{}-epoch{}.{MODEL_EXTENSION}",
+            self.dataset, report.epoch
+        ));
+        let metadata = TrainingMetadata::new(&self.dataset, report.epoch + 1)
+            .with_metric("error", report.error as f64);
+        Model::new(config.clone(), metadata).save(&path)?;
+        self.saved.push_back(path);
+
+        if let Retention::Latest(count) = self.retention {
+            while self.saved.len() > count.max(1) {
+                if let Some(outdated) = self.saved.pop_front() {
+                    fs::remove_file(outdated)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rnn::common::network_cfg::LabelMap;
+
+    fn config_fixture(inputs: usize) -> NetworkCfg {
+        NetworkCfg {
+            inputs,
+            outputs: 0,
+            neurons: vec![],
+            links: vec![],
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn checkpointer_should_keep_only_latest_best_models() {
+        let dir = std::env::temp_dir().join(format!("runen-checkpointer-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut checkpointer = BestCheckpointer::new(&dir, "xor", Retention::Latest(2));
+
+        for (epoch, error) in [(0, 9), (2, 5), (3, 4)] {
+            let report = EpochReport {
+                epoch,
+                error,
+                best_error: error,
+            };
+            checkpointer
+                .on_improvement(&report, &config_fixture(epoch as usize))
+                .unwrap();
+        }
+
+        let saved = checkpointer.get_saved();
+        let outdated_exists = dir.join("xor-epoch0.runen").exists();
+        let best = Model::load(checkpointer.get_best().unwrap()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            saved,
+            vec![dir.join("xor-epoch2.runen"), dir.join("xor-epoch3.runen")]
+        );
+        assert!(!outdated_exists);
+        assert_eq!(best.config, config_fixture(3));
+        assert_eq!(best.metadata.epochs, 4);
+        assert_eq!(best.metadata.metrics["error"], 4.0);
+    }
+}
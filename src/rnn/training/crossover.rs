@@ -0,0 +1,427 @@
+//! Topology aware crossover of network configs considered as genomes.
+//! Neurons of parents are aligned by their lineage markers (see NeuronCfg::lineage).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+use crate::rnn::common::network_cfg::{LinkCfg, NetworkCfg, NetworkLineage, NeuronCfg};
+
+/// Produces offspring config of two parents.
+/// The offspring inherits the neuron set, links and output labels of the fitter parent.
+/// For every neuron present in both parents (matching gene) the bias and every
+/// common dendrite config are taken from the other parent when `take_other` returns true.
+/// Links of the other parent which are absent in the fitter one (disjoint genes)
+/// are inherited when `take_other` returns true and the link fits into offspring:
+/// both ends exist in offspring, dendrite and output port are free.
+/// The input port is never busy: it may feed dendrites of several neurons.
+/// Composites of parents are flattened first, so their inner neurons are aligned as well.
+/// The offspring is validated before return and has lineage which refers both parents.
+pub fn crossover(
+    fitter: &NetworkCfg,
+    other: &NetworkCfg,
+    mut take_other: impl FnMut() -> bool,
+) -> Result<NetworkCfg, Box<dyn Error>> {
+    let parents = [fitter, other];
+    let (fitter, other) = (&fitter.flatten()?, &other.flatten()?);
+    let mut taken_genes = 0;
+    let mut take_other = || {
+        let taken = take_other();
+        taken_genes += taken as usize;
+        taken
+    };
+
+    let other_neurons: BTreeMap<&str, &NeuronCfg> = other
+        .neurons
+        .iter()
+        .map(|neuron| (neuron.lineage_marker(), neuron))
+        .collect();
+
+    let neurons: Vec<NeuronCfg> = fitter
+        .neurons
+        .iter()
+        .map(|neuron| match other_neurons.get(neuron.lineage_marker()) {
+            Some(other_neuron) => mix_neuron(neuron, other_neuron, &mut take_other),
+            None => neuron.clone(),
+        })
+        .collect();
+
+    // offspring ids by lineage markers of the other parent's neuron ids
+    let offspring_ids: BTreeMap<&str, &str> = fitter
+        .neurons
+        .iter()
+        .map(|neuron| (neuron.lineage_marker(), neuron.id.as_str()))
+        .collect();
+    let map_other_id = |id: &str| -> Option<String> {
+        other
+            .neurons
+            .iter()
+            .find(|neuron| neuron.id == id)
+            .and_then(|neuron| offspring_ids.get(neuron.lineage_marker()))
+            .map(|id| id.to_string())
+    };
+
+    let mut links = fitter.links.clone();
+    let mut busy_dendrites: BTreeSet<(String, usize)> = BTreeSet::new();
+    let mut busy_outputs: BTreeSet<usize> = BTreeSet::new();
+    for link in links.iter() {
+        match link {
+            LinkCfg::Input {
+                dst_id,
+                dst_synapse_idx,
+                ..
+            }
+            | LinkCfg::Inner {
+                dst_id,
+                dst_synapse_idx,
+                ..
+            } => {
+                busy_dendrites.insert((dst_id.clone(), *dst_synapse_idx));
+            }
+            LinkCfg::Output { output_port, .. } => {
+                busy_outputs.insert(*output_port);
+            }
+        }
+    }
+
+    let dendrites_count = |id: &str| {
+        neurons
+            .iter()
+            .find(|neuron| neuron.id == id)
+            .map_or(0, |neuron| neuron.input_configs.len())
+    };
+    for link in other.links.iter() {
+        let candidate = match link {
+            LinkCfg::Input {
+                input_port,
+                dst_id,
+                dst_synapse_idx,
+            } => map_other_id(dst_id)
+                .filter(|_| *input_port < fitter.inputs)
+                .map(|dst_id| LinkCfg::Input {
+                    input_port: *input_port,
+                    dst_id,
+                    dst_synapse_idx: *dst_synapse_idx,
+                }),
+            LinkCfg::Inner {
+                src_id,
+                dst_id,
+                dst_synapse_idx,
+            } => map_other_id(src_id)
+                .zip(map_other_id(dst_id))
+                .filter(|(src_id, dst_id)| src_id != dst_id || dendrites_count(dst_id) > 1)
+                .map(|(src_id, dst_id)| LinkCfg::Inner {
+                    src_id,
+                    dst_id,
+                    dst_synapse_idx: *dst_synapse_idx,
+                }),
+            LinkCfg::Output {
+                src_id,
+                output_port,
+            } => map_other_id(src_id)
+                .filter(|_| *output_port < fitter.outputs)
+                .map(|src_id| LinkCfg::Output {
+                    src_id,
+                    output_port: *output_port,
+                }),
+        };
+
+        let Some(candidate) = candidate else {
+            continue;
+        };
+        if links.contains(&candidate) || !take_other() {
+            continue;
+        }
+
+        let fits = match &candidate {
+            LinkCfg::Input {
+                dst_id,
+                dst_synapse_idx,
+                ..
+            }
+            | LinkCfg::Inner {
+                dst_id,
+                dst_synapse_idx,
+                ..
+            } => {
+                *dst_synapse_idx < dendrites_count(dst_id)
+                    && busy_dendrites.insert((dst_id.clone(), *dst_synapse_idx))
+            }
+            LinkCfg::Output { output_port, .. } => busy_outputs.insert(*output_port),
+        };
+        if fits {
+            links.push(candidate);
+        }
+    }
+
+    let lineage = NetworkLineage::descend(
+        &parents,
+        "crossover",
+        vec![format!("{taken_genes} genes taken from other parent")],
+    );
+    let offspring = NetworkCfg {
+        inputs: fitter.inputs,
+        outputs: fitter.outputs,
+        neurons,
+        links,
+        lineage: Some(lineage),
+        composites: vec![],
+        labels: fitter.labels.clone(),
+        seed: None,
+    };
+    offspring.validate()?;
+    Ok(offspring)
+}
+
+fn mix_neuron(
+    neuron: &NeuronCfg,
+    other: &NeuronCfg,
+    take_other: &mut impl FnMut() -> bool,
+) -> NeuronCfg {
+    let bias = if take_other() {
+        other.bias
+    } else {
+        neuron.bias
+    };
+    let input_configs = neuron
+        .input_configs
+        .iter()
+        .enumerate()
+        .map(|(idx, input_cfg)| match other.input_configs.get(idx) {
+            Some(other_cfg) if take_other() => other_cfg.clone(),
+            _ => input_cfg.clone(),
+        })
+        .collect();
+
+    NeuronCfg {
+        bias,
+        input_configs,
+        ..neuron.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::{CompositeCfg, LabelMap, NeuronKind};
+
+    fn neuron_cfg(id: &str, lineage: Option<&str>, weight: i16, dendrites: usize) -> NeuronCfg {
+        NeuronCfg {
+            id: id.to_string(),
+            bias: weight,
+            input_configs: (0..dendrites)
+                .map(|_| InputCfg::new(1, 1, weight).unwrap())
+                .collect(),
+            lineage: lineage.map(|lineage| lineage.to_string()),
+            kind: NeuronKind::Excitatory,
+        }
+    }
+
+    /// I0 -> Z0 -> Z1 -> O0
+    fn fitter_fixture() -> NetworkCfg {
+        NetworkCfg {
+            inputs: 2,
+            outputs: 1,
+            neurons: vec![
+                neuron_cfg("M1Z0", Some("M0Z0"), 1, 1),
+                neuron_cfg("M1Z1", Some("M0Z1"), 1, 2),
+            ],
+            links: vec![
+                LinkCfg::Input {
+                    input_port: 0,
+                    dst_id: String::from("M1Z0"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Inner {
+                    src_id: String::from("M1Z0"),
+                    dst_id: String::from("M1Z1"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Output {
+                    src_id: String::from("M1Z1"),
+                    output_port: 0,
+                },
+            ],
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
+        }
+    }
+
+    /// The same origin as fitter with new neuron Z2 and extra input I1 -> Z1
+    fn other_fixture() -> NetworkCfg {
+        NetworkCfg {
+            inputs: 2,
+            outputs: 1,
+            neurons: vec![
+                neuron_cfg("M2Z0", Some("M0Z0"), 2, 1),
+                neuron_cfg("M2Z1", Some("M0Z1"), 2, 2),
+                neuron_cfg("M2Z2", None, 2, 1),
+            ],
+            links: vec![
+                LinkCfg::Input {
+                    input_port: 0,
+                    dst_id: String::from("M2Z0"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Input {
+                    input_port: 1,
+                    dst_id: String::from("M2Z1"),
+                    dst_synapse_idx: 1,
+                },
+                LinkCfg::Inner {
+                    src_id: String::from("M2Z0"),
+                    dst_id: String::from("M2Z1"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Inner {
+                    src_id: String::from("M2Z1"),
+                    dst_id: String::from("M2Z2"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Output {
+                    src_id: String::from("M2Z1"),
+                    output_port: 0,
+                },
+            ],
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn should_inherit_fitter_parent_when_other_genes_not_taken() {
+        let mut fitter = fitter_fixture();
+        fitter.bind_labels(LabelMap::one_hot(&["spike"])).unwrap();
+
+        let offspring = crossover(&fitter, &other_fixture(), || false).unwrap();
+
+        assert_eq!(offspring.neurons, fitter.neurons);
+        assert_eq!(offspring.links, fitter.links);
+        assert_eq!(offspring.labels, fitter.labels);
+        assert!(!offspring.labels.is_empty());
+    }
+
+    #[test]
+    fn offspring_should_refer_both_parents() {
+        let fitter = fitter_fixture();
+        let other = other_fixture();
+        let offspring = crossover(&fitter, &other, || true).unwrap();
+
+        let lineage = offspring.lineage.unwrap();
+        assert_eq!(
+            lineage.parents,
+            vec![fitter.fingerprint(), other.fingerprint()]
+        );
+        assert_eq!(lineage.generation, 1);
+        assert_eq!(lineage.trainer, "crossover");
+        assert_eq!(lineage.mutations, vec!["6 genes taken from other parent"]);
+    }
+
+    #[test]
+    fn should_take_matching_genes_and_fitting_links_from_other_parent() {
+        let offspring = crossover(&fitter_fixture(), &other_fixture(), || true).unwrap();
+
+        assert_eq!(offspring.neurons.len(), 2);
+        assert_eq!(offspring.neurons[0].id, "M1Z0");
+        assert_eq!(offspring.neurons[0].bias, 2);
+        assert_eq!(offspring.neurons[1].input_configs[1].weight, 2);
+
+        // link to neuron absent in fitter parent is not inherited
+        assert_eq!(offspring.links.len(), 4);
+        assert_eq!(
+            offspring.links[3],
+            LinkCfg::Input {
+                input_port: 1,
+                dst_id: String::from("M1Z1"),
+                dst_synapse_idx: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn should_not_inherit_link_into_busy_dendrite() {
+        let mut other = other_fixture();
+        other.links[1] = LinkCfg::Input {
+            input_port: 1,
+            dst_id: String::from("M2Z1"),
+            dst_synapse_idx: 0,
+        };
+
+        let offspring = crossover(&fitter_fixture(), &other, || true).unwrap();
+
+        assert_eq!(offspring.links.len(), 3);
+    }
+
+    #[test]
+    fn should_inherit_link_from_already_linked_input_port() {
+        // I0 feeds both Z0 and Z1 of the other parent
+        let mut other = other_fixture();
+        other.links[1] = LinkCfg::Input {
+            input_port: 0,
+            dst_id: String::from("M2Z1"),
+            dst_synapse_idx: 1,
+        };
+
+        let offspring = crossover(&fitter_fixture(), &other, || true).unwrap();
+
+        assert!(offspring.links.contains(&LinkCfg::Input {
+            input_port: 0,
+            dst_id: String::from("M1Z1"),
+            dst_synapse_idx: 1,
+        }));
+        assert_eq!(offspring.links.len(), 4);
+        assert!(offspring.validate().is_ok());
+    }
+
+    #[test]
+    fn should_align_inner_neurons_of_composite_parent() {
+        // Z1 of fitter is the composite with the single inner neuron
+        let mut fitter = fitter_fixture();
+        let mut inner = fitter_fixture();
+        inner.inputs = 1;
+        inner.neurons = vec![neuron_cfg("M0Z0", None, 1, 1)];
+        inner.links = vec![
+            LinkCfg::Input {
+                input_port: 0,
+                dst_id: String::from("M0Z0"),
+                dst_synapse_idx: 0,
+            },
+            LinkCfg::Output {
+                src_id: String::from("M0Z0"),
+                output_port: 0,
+            },
+        ];
+        fitter.neurons.pop();
+        fitter.composites.push(CompositeCfg {
+            id: String::from("M1Z1"),
+            network: inner,
+            axon_port: 0,
+        });
+        let mut other = fitter.flatten().unwrap();
+        other.neurons[1].bias = 2;
+
+        let offspring = crossover(&fitter, &other, || true).unwrap();
+
+        assert!(offspring.composites.is_empty());
+        assert_eq!(offspring.neurons[1].lineage_marker(), "M1Z1/M0Z0");
+        assert_eq!(offspring.neurons[1].bias, 2);
+        assert!(offspring.links.contains(&LinkCfg::Inner {
+            src_id: String::from("M1Z0"),
+            dst_id: offspring.neurons[1].id.clone(),
+            dst_synapse_idx: 0,
+        }));
+        assert_eq!(offspring.lineage.unwrap().parents[0], fitter.fingerprint());
+    }
+
+    #[test]
+    fn should_fail_on_invalid_fitter_parent() {
+        let mut fitter = fitter_fixture();
+        fitter.neurons.pop();
+
+        assert!(crossover(&fitter, &other_fixture(), || false).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+//! Conversion of values into signals for network's input ports and back
+//! from signals of output ports. Every bit is mapped to its own port:
+//! the least significant bit goes to the port with the lowest index.
+
+use crate::rnn::common::signal::Signal;
+
+/// The value which can be split into signals, one signal per input port.
+pub trait Splittable {
+    fn split(&self) -> Vec<Signal>;
+}
+
+/// The value which can be reconstructed from signals taken from output ports.
+pub trait Reconstructible: Sized {
+    /// Returns None when signals do not describe any value of the type.
+    fn reconstruct(signals: &[Signal]) -> Option<Self>;
+}
+
+/// Any positive signal is considered as set bit.
+#[inline]
+fn is_set(signal: Signal) -> bool {
+    signal > 0
+}
+
+fn split_bits(value: u64, width: usize) -> Vec<Signal> {
+    (0..width)
+        .map(|bit| ((value >> bit) & 1) as Signal)
+        .collect()
+}
+
+fn reconstruct_bits(signals: &[Signal], width: usize) -> Option<u64> {
+    if signals.len() != width {
+        return None;
+    }
+
+    Some(
+        signals
+            .iter()
+            .enumerate()
+            .filter(|(_, signal)| is_set(**signal))
+            .fold(0_u64, |value, (bit, _)| value | (1 << bit)),
+    )
+}
+
+impl Splittable for bool {
+    fn split(&self) -> Vec<Signal> {
+        vec![*self as Signal]
+    }
+}
+
+impl Reconstructible for bool {
+    fn reconstruct(signals: &[Signal]) -> Option<Self> {
+        match signals {
+            [signal] => Some(is_set(*signal)),
+            _ => None,
+        }
+    }
+}
+
+impl Splittable for u8 {
+    fn split(&self) -> Vec<Signal> {
+        split_bits(*self as u64, 8)
+    }
+}
+
+impl Reconstructible for u8 {
+    fn reconstruct(signals: &[Signal]) -> Option<Self> {
+        reconstruct_bits(signals, 8).map(|value| value as u8)
+    }
+}
+
+impl Splittable for u16 {
+    fn split(&self) -> Vec<Signal> {
+        split_bits(*self as u64, 16)
+    }
+}
+
+impl Reconstructible for u16 {
+    fn reconstruct(signals: &[Signal]) -> Option<Self> {
+        reconstruct_bits(signals, 16).map(|value| value as u16)
+    }
+}
+
+impl Splittable for Vec<bool> {
+    fn split(&self) -> Vec<Signal> {
+        self.iter().map(|bit| *bit as Signal).collect()
+    }
+}
+
+impl Reconstructible for Vec<bool> {
+    fn reconstruct(signals: &[Signal]) -> Option<Self> {
+        Some(signals.iter().map(|signal| is_set(*signal)).collect())
+    }
+}
+
+impl<const N: usize> Splittable for [bool; N] {
+    fn split(&self) -> Vec<Signal> {
+        self.iter().map(|bit| *bit as Signal).collect()
+    }
+}
+
+impl<const N: usize> Reconstructible for [bool; N] {
+    fn reconstruct(signals: &[Signal]) -> Option<Self> {
+        if signals.len() != N {
+            return None;
+        }
+
+        let mut bits = [false; N];
+        for (bit, signal) in bits.iter_mut().zip(signals) {
+            *bit = is_set(*signal);
+        }
+        Some(bits)
+    }
+}
+
+/// The string is split by its UTF-8 bytes, eight ports per byte.
+impl Splittable for String {
+    fn split(&self) -> Vec<Signal> {
+        self.as_bytes()
+            .iter()
+            .flat_map(|byte| byte.split())
+            .collect()
+    }
+}
+
+impl Reconstructible for String {
+    fn reconstruct(signals: &[Signal]) -> Option<Self> {
+        let chunks = signals.chunks_exact(8);
+        if !chunks.remainder().is_empty() {
+            return None;
+        }
+
+        let bytes = chunks.map(u8::reconstruct).collect::<Option<Vec<u8>>>()?;
+        String::from_utf8(bytes).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn u8_should_be_split_from_least_significant_bit() {
+        assert_eq!(5_u8.split(), vec![1, 0, 1, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn integers_should_survive_round_trip() {
+        for value in [0_u8, 1, 77, 255] {
+            assert_eq!(u8::reconstruct(&value.split()), Some(value));
+        }
+        for value in [0_u16, 256, 40_000, u16::MAX] {
+            assert_eq!(u16::reconstruct(&value.split()), Some(value));
+        }
+    }
+
+    #[test]
+    fn any_positive_signal_should_be_reconstructed_as_set_bit() {
+        assert_eq!(u8::reconstruct(&[3, 0, 7, 0, 0, 0, 0, 0]), Some(5));
+        assert_eq!(bool::reconstruct(&[9]), Some(true));
+        assert_eq!(bool::reconstruct(&[0]), Some(false));
+    }
+
+    #[test]
+    fn should_not_reconstruct_from_wrong_number_of_signals() {
+        assert_eq!(u8::reconstruct(&[1, 0]), None);
+        assert_eq!(bool::reconstruct(&[]), None);
+        assert_eq!(<[bool; 3]>::reconstruct(&[1, 1]), None);
+        assert_eq!(String::reconstruct(&[1, 0, 1]), None);
+    }
+
+    #[test]
+    fn bool_collections_should_survive_round_trip() {
+        let bits = vec![true, false, false, true];
+        assert_eq!(Vec::<bool>::reconstruct(&bits.split()), Some(bits));
+
+        let bits = [false, true, true];
+        assert_eq!(<[bool; 3]>::reconstruct(&bits.split()), Some(bits));
+    }
+
+    #[test]
+    fn string_should_survive_round_trip() {
+        let text = String::from("Нейрон");
+        let signals = text.split();
+
+        assert_eq!(signals.len(), text.len() * 8);
+        assert_eq!(String::reconstruct(&signals), Some(text));
+    }
+
+    #[test]
+    fn string_should_not_be_reconstructed_from_invalid_utf8() {
+        assert_eq!(String::reconstruct(&0xff_u8.split()), None);
+    }
+}
@@ -0,0 +1,186 @@
+//! Homeostatic synaptic scaling. Firing rates of neurons are measured over the
+//! window and weights of every dendrite of the neuron are moved by the step
+//! toward the target rate, so long running recurrent networks neither die out
+//! nor saturate.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::time;
+
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::signal::Weight;
+use crate::rnn::common::status::Status;
+use crate::rnn::layouts::network::Network;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Homeostasis {
+    /// The target number of firings per second
+    pub target_rate: f64,
+
+    /// Rates within target_rate ± tolerance are left as is
+    pub tolerance: f64,
+
+    /// The time of rates measuring
+    pub window: Duration,
+
+    /// The change of the weight magnitude per adjustment
+    pub step: Weight,
+}
+
+impl Homeostasis {
+    pub fn new(target_rate: f64, tolerance: f64, window: Duration, step: Weight) -> Self {
+        Homeostasis {
+            target_rate,
+            tolerance,
+            window,
+            step,
+        }
+    }
+
+    /// Measure firing rates of all neurons during the window and rescale weights
+    /// of neurons out of the tolerance. Excitatory and inhibitory weights keep
+    /// their sign, zero weights stay untouched. Returns measured rates by neuron id.
+    pub async fn regulate(
+        &self,
+        network: &Network,
+    ) -> Result<BTreeMap<String, f64>, Box<dyn Error>> {
+        if self.window.is_zero() || self.step <= 0 {
+            return Err(Box::new(RnnError::NotSupportedArgValue));
+        }
+
+        let ids: Vec<String> = network
+            .get_config()
+            .await
+            .neurons
+            .into_iter()
+            .map(|neuron_cfg| neuron_cfg.id)
+            .collect();
+        let mut before = BTreeMap::new();
+        for id in ids.iter() {
+            before.insert(id.clone(), reset_count(network, id).await?);
+        }
+
+        time::sleep(self.window).await;
+
+        let mut rates = BTreeMap::new();
+        for (id, count_before) in before {
+            let Some(neuron) = network.get_neuron(&id).await else {
+                continue;
+            };
+            let fired = reset_count(network, &id)
+                .await?
+                .saturating_sub(count_before);
+            let rate = fired as f64 / self.window.as_secs_f64();
+            let direction = if rate < self.target_rate - self.tolerance {
+                1
+            } else if rate > self.target_rate + self.tolerance {
+                -1
+            } else {
+                0
+            };
+            if direction != 0 {
+                for (port, input_cfg) in neuron.get_config().await.input_configs.iter().enumerate()
+                {
+                    let weight = self.scale(input_cfg.weight, direction);
+                    neuron.update_synapse(port, Some(weight), None).await?;
+                }
+            }
+            rates.insert(id, rate);
+        }
+        Ok(rates)
+    }
+
+    /// Grow (direction 1) or shrink (direction -1) the weight magnitude by the step.
+    /// Shrinking stops at the magnitude of one.
+    fn scale(&self, weight: Weight, direction: Weight) -> Weight {
+        if weight == 0 {
+            return 0;
+        }
+        let magnitude = weight.saturating_abs();
+        let magnitude = if direction > 0 {
+            magnitude.saturating_add(self.step)
+        } else {
+            magnitude.saturating_sub(self.step).max(1)
+        };
+        magnitude * weight.signum()
+    }
+}
+
+async fn reset_count(network: &Network, id: &str) -> Result<u64, Box<dyn Error>> {
+    match network.get_current_neuron_status(id).await? {
+        Status::Neuron(info) => Ok(info.reset_count),
+        _ => Err(Box::new(RnnError::ExpectedDataNotPresent(id.to_string()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+
+    fn weights(input_configs: &[InputCfg]) -> Vec<Weight> {
+        input_configs
+            .iter()
+            .map(|input_cfg| input_cfg.weight)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn silent_neuron_weights_should_grow_keeping_sign() {
+        let net = Arc::new(new_network_fixture());
+        let n = net
+            .create_neuron(
+                net.clone(),
+                0,
+                vec![
+                    InputCfg::new(3, 3, 2).unwrap(),
+                    InputCfg::new(3, 3, -2).unwrap(),
+                    InputCfg::new(3, 3, 0).unwrap(),
+                ],
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap();
+
+        let homeostasis = Homeostasis::new(10.0, 1.0, Duration::from_millis(10), 3);
+        let rates = homeostasis.regulate(&net).await.unwrap();
+
+        assert_eq!(rates[&n.get_id()], 0.0);
+        assert_eq!(weights(&n.get_config().await.input_configs), vec![5, -5, 0]);
+    }
+
+    #[tokio::test]
+    async fn weights_within_tolerance_should_stay() {
+        let net = Arc::new(new_network_fixture());
+        let n = net
+            .create_neuron(
+                net.clone(),
+                0,
+                vec![InputCfg::new(3, 3, 2).unwrap()],
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap();
+
+        let homeostasis = Homeostasis::new(0.0, 1.0, Duration::from_millis(10), 3);
+        assert!(homeostasis.regulate(&net).await.is_ok());
+
+        assert_eq!(weights(&n.get_config().await.input_configs), vec![2]);
+    }
+
+    #[test]
+    fn shrinking_should_stop_at_unit_magnitude() {
+        let homeostasis = Homeostasis::new(0.0, 0.0, Duration::from_millis(10), 3);
+
+        assert_eq!(homeostasis.scale(2, -1), 1);
+        assert_eq!(homeostasis.scale(-7, -1), -4);
+        assert_eq!(homeostasis.scale(Weight::MAX, 1), Weight::MAX);
+    }
+}
@@ -0,0 +1,195 @@
+//! Evaluation loop: every sample is encoded into input signals, the network is
+//! given the time budget to respond, the response of output ports is decoded and
+//! the runtime state of neurons is reset before the next sample.
+//! Only the first spike of every output port is decoded. The whole budget passes
+//! before the reset, so spikes still in flight do not leak into the next sample.
+
+use std::error::Error;
+use std::time::Duration;
+
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{self, Instant};
+
+use crate::rnn::common::signal::Signal;
+use crate::rnn::layouts::network::Network;
+
+/// The result of inference for the single sample.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inference<O> {
+    /// Decoded output. None when decoder does not accept signals.
+    pub output: Option<O>,
+
+    /// The first signal of every output port or zero when port did not respond
+    pub signals: Vec<Signal>,
+
+    /// The time from the stimulus to the response of the last output port
+    /// or the whole budget when some port did not respond
+    pub elapsed: Duration,
+
+    /// All output ports responded within the budget
+    pub completed: bool,
+}
+
+/// Feed samples one by one. Encoded signal with index i goes into input port i.
+/// Decoder receives signals of output ports ordered by port index.
+/// Encoders and decoders of training::encoding can be passed as
+/// `u8::split` and `u8::reconstruct`.
+/// Every sample takes the whole `per_sample_budget`, later spikes within it are ignored.
+pub async fn infer_batch<I, O>(
+    network: &Network,
+    samples: &[I],
+    encoder: impl Fn(&I) -> Vec<Signal>,
+    decoder: impl Fn(&[Signal]) -> Option<O>,
+    per_sample_budget: Duration,
+) -> Result<Vec<Inference<O>>, Box<dyn Error>> {
+    let output_ports = network.get_output_ports().await;
+    let mut results = vec![];
+
+    for sample in samples {
+        let mut receivers = vec![];
+        for port in output_ports.iter() {
+            receivers.push(network.subscribe_output(*port).await?);
+        }
+
+        let start = Instant::now();
+        let deadline = start + per_sample_budget;
+        network.input_vec(&encoder(sample)).await?;
+
+        let mut signals = vec![0; output_ports.len()];
+        let mut completed = true;
+        let mut elapsed = Duration::ZERO;
+        for (receiver, port_signal) in receivers.iter_mut().zip(signals.iter_mut()) {
+            loop {
+                match time::timeout_at(deadline, receiver.recv()).await {
+                    Ok(Ok(signal)) => {
+                        *port_signal = signal;
+                        elapsed = elapsed.max(start.elapsed());
+                        break;
+                    }
+                    Ok(Err(RecvError::Lagged(_))) => continue,
+                    Ok(Err(RecvError::Closed)) | Err(_) => {
+                        completed = false;
+                        break;
+                    }
+                }
+            }
+        }
+        if !completed {
+            elapsed = per_sample_budget;
+        }
+
+        drop(receivers);
+        time::sleep_until(deadline).await;
+        reset_neurons(network).await;
+        results.push(Inference {
+            output: decoder(&signals),
+            signals,
+            elapsed,
+            completed,
+        });
+    }
+
+    Ok(results)
+}
+
+/// Unlike Network::reset() the state is cleared before return.
+async fn reset_neurons(network: &Network) {
+    for neuron_cfg in network.get_config().await.neurons {
+        if let Some(neuron) = network.get_neuron(&neuron_cfg.id).await {
+            neuron.reset().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+    use crate::rnn::training::encoding::{Reconstructible, Splittable};
+
+    /// Two independent relays: I0 -> Z0 -> O0 and I1 -> Z1 -> O1
+    async fn relays_network_fixture() -> Arc<Network> {
+        let net = Arc::new(new_network_fixture());
+        for port in 0..2 {
+            let id = net
+                .create_neuron(
+                    net.clone(),
+                    0,
+                    vec![InputCfg::new(3, 3, 1).unwrap()],
+                    NeuronKind::Excitatory,
+                )
+                .await
+                .unwrap()
+                .get_id();
+            assert!(net.setup_input(port, &id, 0).await.is_ok());
+            assert!(net.setup_output(port, &id).await.is_ok());
+        }
+        reset_neurons(&net).await;
+        net
+    }
+
+    #[tokio::test]
+    async fn should_decode_responses_of_every_sample() {
+        let net = relays_network_fixture().await;
+        let samples = vec![[true, true], [true, false], [false, false]];
+
+        let results = infer_batch(
+            &net,
+            &samples,
+            <[bool; 2]>::split,
+            <[bool; 2]>::reconstruct,
+            Duration::from_millis(20),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].output, Some([true, true]));
+        assert!(results[0].completed);
+        assert!(results[0].elapsed < Duration::from_millis(20));
+        assert_eq!(results[1].output, Some([true, false]));
+        assert!(!results[1].completed);
+        assert_eq!(results[1].elapsed, Duration::from_millis(20));
+        assert_eq!(results[2].signals, vec![0, 0]);
+    }
+
+    #[tokio::test]
+    async fn every_sample_should_take_whole_budget() {
+        let net = relays_network_fixture().await;
+        let start = Instant::now();
+
+        let results = infer_batch(
+            &net,
+            &[[true, true], [true, true]],
+            <[bool; 2]>::split,
+            <[bool; 2]>::reconstruct,
+            Duration::from_millis(20),
+        )
+        .await
+        .unwrap();
+
+        assert!(results.iter().all(|result| result.completed));
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn should_reset_state_between_samples() {
+        let net = relays_network_fixture().await;
+
+        let results = infer_batch(
+            &net,
+            &[1_u8, 1],
+            |signal| vec![*signal, 0],
+            |signals| Some(signals.to_vec()),
+            Duration::from_millis(20),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results[0].signals, results[1].signals);
+    }
+}
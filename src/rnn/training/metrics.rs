@@ -0,0 +1,221 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Quality metrics of a single class (solution variant).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassMetrics {
+    pub precision: f64,
+    pub recall: f64,
+    pub f1: f64,
+
+    /// The number of samples where class was expected.
+    pub support: u64,
+}
+
+/// Accumulates predicted solutions against expected ones.
+/// Label is any comparable representation of solution: reconstructed value,
+/// output port index, etc.
+#[derive(Debug, Clone)]
+pub struct ConfusionMatrix<L: Ord + Clone> {
+    /// The number of samples by pair (expected, predicted)
+    counts: BTreeMap<(L, L), u64>,
+
+    labels: BTreeSet<L>,
+
+    total: u64,
+}
+
+impl<L: Ord + Clone> Default for ConfusionMatrix<L> {
+    fn default() -> Self {
+        ConfusionMatrix {
+            counts: BTreeMap::new(),
+            labels: BTreeSet::new(),
+            total: 0,
+        }
+    }
+}
+
+impl<L: Ord + Clone> ConfusionMatrix<L> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register one prediction.
+    pub fn record(&mut self, expected: L, predicted: L) {
+        self.labels.insert(expected.clone());
+        self.labels.insert(predicted.clone());
+        *self.counts.entry((expected, predicted)).or_insert(0) += 1;
+        self.total += 1;
+    }
+
+    /// The number of samples with specified expected and predicted labels.
+    pub fn count(&self, expected: &L, predicted: &L) -> u64 {
+        self.counts
+            .get(&(expected.clone(), predicted.clone()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// All labels met as expected or predicted ones.
+    pub fn labels(&self) -> Vec<L> {
+        self.labels.iter().cloned().collect()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// The number of correct predictions.
+    pub fn correct(&self) -> u64 {
+        self.counts
+            .iter()
+            .filter(|((expected, predicted), _)| expected == predicted)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// The share of correct predictions. Zero when nothing recorded.
+    pub fn accuracy(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.correct() as f64 / self.total as f64
+        }
+    }
+
+    /// Get precision, recall and F1 score of the class.
+    /// Metrics which have zero denominator are considered as zero.
+    pub fn class_metrics(&self, label: &L) -> ClassMetrics {
+        let true_positive = self.count(label, label);
+        let predicted: u64 = self.column_sum(label);
+        let support: u64 = self.row_sum(label);
+
+        let precision = Self::ratio(true_positive, predicted);
+        let recall = Self::ratio(true_positive, support);
+        let f1 = if precision + recall > 0.0 {
+            2.0 * precision * recall / (precision + recall)
+        } else {
+            0.0
+        };
+
+        ClassMetrics {
+            precision,
+            recall,
+            f1,
+            support,
+        }
+    }
+
+    /// Unweighted mean of F1 scores of all classes.
+    pub fn macro_f1(&self) -> f64 {
+        if self.labels.is_empty() {
+            return 0.0;
+        }
+
+        let sum: f64 = self
+            .labels
+            .iter()
+            .map(|label| self.class_metrics(label).f1)
+            .sum();
+        sum / self.labels.len() as f64
+    }
+
+    /// Forget all recorded predictions, e.g. before the next epoch.
+    pub fn clear(&mut self) {
+        self.counts.clear();
+        self.labels.clear();
+        self.total = 0;
+    }
+
+    fn row_sum(&self, expected: &L) -> u64 {
+        self.counts
+            .iter()
+            .filter(|((row, _), _)| row == expected)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    fn column_sum(&self, predicted: &L) -> u64 {
+        self.counts
+            .iter()
+            .filter(|((_, column), _)| column == predicted)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    fn ratio(numerator: u64, denominator: u64) -> f64 {
+        if denominator == 0 {
+            0.0
+        } else {
+            numerator as f64 / denominator as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix_fixture() -> ConfusionMatrix<u8> {
+        // expected 0: predicted 0 three times and 1 once
+        // expected 1: predicted 1 twice and 0 twice
+        let mut matrix = ConfusionMatrix::new();
+        for (expected, predicted) in [
+            (0, 0),
+            (0, 0),
+            (0, 0),
+            (0, 1),
+            (1, 1),
+            (1, 1),
+            (1, 0),
+            (1, 0),
+        ] {
+            matrix.record(expected, predicted);
+        }
+        matrix
+    }
+
+    #[test]
+    fn empty_matrix_should_have_zero_metrics() {
+        let matrix: ConfusionMatrix<u8> = ConfusionMatrix::new();
+
+        assert_eq!(matrix.accuracy(), 0.0);
+        assert_eq!(matrix.macro_f1(), 0.0);
+        assert_eq!(matrix.class_metrics(&0).support, 0);
+    }
+
+    #[test]
+    fn should_count_predictions_by_pairs() {
+        let matrix = matrix_fixture();
+
+        assert_eq!(matrix.total(), 8);
+        assert_eq!(matrix.correct(), 5);
+        assert_eq!(matrix.count(&0, &1), 1);
+        assert_eq!(matrix.count(&1, &0), 2);
+        assert_eq!(matrix.labels(), vec![0, 1]);
+        assert_eq!(matrix.accuracy(), 5.0 / 8.0);
+    }
+
+    #[test]
+    fn should_calculate_class_metrics() {
+        let matrix = matrix_fixture();
+
+        let metrics = matrix.class_metrics(&0);
+        assert_eq!(metrics.precision, 3.0 / 5.0);
+        assert_eq!(metrics.recall, 3.0 / 4.0);
+        assert_eq!(metrics.support, 4);
+        assert!((metrics.f1 - 2.0 / 3.0).abs() < 1e-9);
+
+        let metrics = matrix.class_metrics(&1);
+        assert_eq!(metrics.precision, 2.0 / 3.0);
+        assert_eq!(metrics.recall, 0.5);
+    }
+
+    #[test]
+    fn clear_should_forget_predictions() {
+        let mut matrix = matrix_fixture();
+        matrix.clear();
+
+        assert_eq!(matrix.total(), 0);
+        assert!(matrix.labels().is_empty());
+    }
+}
@@ -0,0 +1,205 @@
+//! The trained model artifact: the network config bundled with training metadata
+//! and optional runtime snapshot in a single json file (`.runen` by convention),
+//! so trained networks can be shared and reproduced.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::rnn::common::network_cfg::NetworkCfg;
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::snapshot::NetworkSnapshot;
+use crate::rnn::layouts::network::Network;
+
+/// The conventional extension of model files.
+pub static MODEL_EXTENSION: &str = "runen";
+
+/// The version of the model file format written by this crate.
+pub static MODEL_FORMAT_VERSION: u32 = 1;
+
+/// How the model was trained.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrainingMetadata {
+    pub dataset: String,
+
+    pub epochs: u64,
+
+    /// Final metrics by name, e.g. accuracy or macro F1
+    #[serde(default)]
+    pub metrics: BTreeMap<String, f64>,
+
+    /// The version of the crate which produced the model
+    pub crate_version: String,
+
+    pub created_at: DateTime<Utc>,
+}
+
+impl TrainingMetadata {
+    pub fn new(dataset: &str, epochs: u64) -> Self {
+        TrainingMetadata {
+            dataset: dataset.to_string(),
+            epochs,
+            metrics: BTreeMap::new(),
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_metric(mut self, name: &str, value: f64) -> Self {
+        self.metrics.insert(name.to_string(), value);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Model {
+    pub format_version: u32,
+
+    pub config: NetworkCfg,
+
+    pub metadata: TrainingMetadata,
+
+    /// Dynamic state of the trained network. The config is installed with
+    /// reset state when it is absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<NetworkSnapshot>,
+}
+
+impl Model {
+    pub fn new(config: NetworkCfg, metadata: TrainingMetadata) -> Self {
+        Model {
+            format_version: MODEL_FORMAT_VERSION,
+            config,
+            metadata,
+            snapshot: None,
+        }
+    }
+
+    /// The model of the network's current topology. The runtime state is captured
+    /// as well with `with_state`.
+    pub async fn from_network(
+        network: &Network,
+        metadata: TrainingMetadata,
+        with_state: bool,
+    ) -> Self {
+        if with_state {
+            let snapshot = network.snapshot().await;
+            let mut model = Model::new(snapshot.config.clone(), metadata);
+            model.snapshot = Some(snapshot);
+            model
+        } else {
+            Model::new(network.get_config().await, metadata)
+        }
+    }
+
+    /// Replace the whole network with the model (see Network::restore() and
+    /// Network::restore_config()).
+    pub async fn install(&self, network: Arc<Network>) -> Result<(), Box<dyn Error>> {
+        match &self.snapshot {
+            Some(snapshot) => network.restore(network.clone(), snapshot).await,
+            None => network
+                .restore_config(network.clone(), &self.config)
+                .await
+                .map(|_| ()),
+        }
+    }
+
+    /// Write the model into file in json format.
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer(File::create(path)?, self)?;
+        Ok(())
+    }
+
+    /// Read the model from json file. Files of newer format versions are rejected.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let model: Model = serde_json::from_reader(File::open(path)?)?;
+        if model.format_version > MODEL_FORMAT_VERSION {
+            return Err(Box::new(RnnError::UnsupportedModelVersion(
+                model.format_version,
+            )));
+        }
+        Ok(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+
+    async fn trained_network_fixture() -> Arc<Network> {
+        let net = Arc::new(new_network_fixture());
+        let id = net
+            .create_neuron(
+                net.clone(),
+                1,
+                vec![InputCfg::new(2, 2, 1).unwrap()],
+                NeuronKind::Excitatory,
+            )
+            .await
+            .unwrap()
+            .get_id();
+        assert!(net.setup_input(0, &id, 0).await.is_ok());
+        assert!(net.setup_output(0, &id).await.is_ok());
+        net
+    }
+
+    #[tokio::test]
+    async fn model_should_survive_save_and_load() {
+        let net = trained_network_fixture().await;
+        let metadata = TrainingMetadata::new("xor", 12).with_metric("accuracy", 0.75);
+        let model = Model::from_network(&net, metadata, true).await;
+        let path = std::env::temp_dir().join(format!(
+            "runen-{}-{}.{MODEL_EXTENSION}",
+            std::process::id(),
+            net.get_id()
+        ));
+
+        assert!(model.save(&path).is_ok());
+        let loaded = Model::load(&path).unwrap();
+        let _remove_result = std::fs::remove_file(&path);
+
+        assert_eq!(loaded, model);
+        assert_eq!(loaded.metadata.crate_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(loaded.metadata.metrics["accuracy"], 0.75);
+        assert_eq!(loaded.snapshot.as_ref().unwrap().neurons.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn model_should_be_installed_into_another_network() {
+        let net = trained_network_fixture().await;
+        let model = Model::from_network(&net, TrainingMetadata::new("xor", 1), false).await;
+        assert!(model.snapshot.is_none());
+
+        let copy = Arc::new(new_network_fixture());
+        assert!(model.install(copy.clone()).await.is_ok());
+
+        assert_eq!(copy.len().await, 1);
+        assert_eq!(copy.get_output_ports().await, vec![0]);
+    }
+
+    #[tokio::test]
+    async fn newer_format_version_should_be_rejected() {
+        let net = trained_network_fixture().await;
+        let mut model = Model::from_network(&net, TrainingMetadata::new("xor", 0), false).await;
+        model.format_version = MODEL_FORMAT_VERSION + 1;
+        let path = std::env::temp_dir().join(format!(
+            "runen-{}-{}-newer.{MODEL_EXTENSION}",
+            std::process::id(),
+            net.get_id()
+        ));
+
+        assert!(model.save(&path).is_ok());
+        let loaded = Model::load(&path);
+        let _remove_result = std::fs::remove_file(&path);
+
+        assert!(loaded.is_err());
+    }
+}
@@ -0,0 +1,338 @@
+//! Training of the reservoir read-out layer. Hidden population stays untouched,
+//! weights of read-out dendrites are adjusted by the delta rule on activity
+//! of hidden neurons.
+
+use std::error::Error;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::TryRecvError;
+use tokio::sync::broadcast::Receiver;
+use tokio::time;
+
+use crate::rnn::common::rnn_error::RnnError;
+use crate::rnn::common::signal::{Signal, Weight};
+use crate::rnn::common::spec_type::SpecificationType;
+use crate::rnn::common::utils::{gen_id_by_spec_type, get_component_id_fraction};
+use crate::rnn::layouts::network::Network;
+use crate::rnn::layouts::reservoir::Reservoir;
+
+use super::checkpoint::{EpochReport, TrainerCallbacks};
+
+/// The training example.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReservoirSample {
+    /// Signals sent into input ports (signal per port)
+    pub inputs: Vec<Signal>,
+
+    /// Expected last signals of output ports (signal per port)
+    pub targets: Vec<Signal>,
+}
+
+/// Trains read-out weights of the network built from the reservoir.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReservoirTrainer {
+    pub reservoir: Reservoir,
+
+    /// The change of the weight per sample
+    pub learning_rate: Weight,
+
+    /// The time of activity collection after the sample is sent
+    pub window: Duration,
+}
+
+impl ReservoirTrainer {
+    pub fn new(reservoir: Reservoir, learning_rate: Weight, window: Duration) -> Self {
+        ReservoirTrainer {
+            reservoir,
+            learning_rate,
+            window,
+        }
+    }
+
+    /// Present every sample once. For every read-out neuron whose output differs
+    /// from the target, weights of dendrites connected to fired hidden neurons are
+    /// moved by learning rate towards the target. Returns the sum of absolute
+    /// output errors over the epoch.
+    pub async fn train_epoch(
+        &self,
+        network: &Network,
+        samples: &[ReservoirSample],
+    ) -> Result<u64, Box<dyn Error>> {
+        let mut epoch_error = 0;
+        for sample in samples {
+            epoch_error += self.train_sample(network, sample).await?;
+        }
+        Ok(epoch_error)
+    }
+
+    /// Train the number of epochs calling callbacks after every epoch and on
+    /// improvement of the epoch error. Returns errors of epochs.
+    pub async fn fit(
+        &self,
+        network: &Network,
+        samples: &[ReservoirSample],
+        epochs: u64,
+        callbacks: &mut impl TrainerCallbacks,
+    ) -> Result<Vec<u64>, Box<dyn Error>> {
+        let mut errors: Vec<u64> = vec![];
+        for epoch in 0..epochs {
+            let error = self.train_epoch(network, samples).await?;
+            let improved = errors.iter().all(|best| error < *best);
+            errors.push(error);
+            let report = EpochReport {
+                epoch,
+                error,
+                best_error: errors.iter().copied().min().unwrap_or(error),
+            };
+
+            callbacks.on_epoch_end(&report)?;
+            if improved {
+                callbacks.on_improvement(&report, &network.get_config().await)?;
+            }
+        }
+        Ok(errors)
+    }
+
+    async fn train_sample(
+        &self,
+        network: &Network,
+        sample: &ReservoirSample,
+    ) -> Result<u64, Box<dyn Error>> {
+        let readout_ids = network_ids(network, &self.reservoir.readout_ids())?;
+        if sample.inputs.len() != self.reservoir.params.inputs
+            || sample.targets.len() != readout_ids.len()
+        {
+            return Err(Box::new(RnnError::NotSupportedArgValue));
+        }
+
+        let mut hidden_receivers = vec![];
+        for id in network_ids(network, &self.reservoir.hidden_ids())? {
+            let neuron = network
+                .get_neuron(&id)
+                .await
+                .ok_or_else(|| Box::new(RnnError::NeuronNotFound(id.clone())))?;
+            hidden_receivers.push(neuron.subscribe().await);
+        }
+        let mut output_receivers = vec![];
+        for port in 0..readout_ids.len() {
+            output_receivers.push(network.subscribe_output(port).await?);
+        }
+
+        network.input_vec(&sample.inputs).await?;
+        time::sleep(self.window).await;
+
+        let fired: Vec<bool> = hidden_receivers
+            .iter_mut()
+            .map(|receiver| last_signal(receiver).is_some_and(|signal| signal > 0))
+            .collect();
+
+        let mut sample_error = 0;
+        for ((readout_id, receiver), target) in readout_ids
+            .iter()
+            .zip(output_receivers.iter_mut())
+            .zip(sample.targets.iter())
+        {
+            let actual = last_signal(receiver).unwrap_or(0);
+            let error = *target as i32 - actual as i32;
+            sample_error += error.unsigned_abs() as u64;
+            if error == 0 {
+                continue;
+            }
+
+            let delta = self.learning_rate.saturating_mul(error.signum() as Weight);
+            let mut input_configs = network
+                .get_neuron(readout_id)
+                .await
+                .ok_or_else(|| Box::new(RnnError::NeuronNotFound(readout_id.clone())))?
+                .get_config()
+                .await
+                .input_configs;
+            for (input_cfg, _) in input_configs
+                .iter_mut()
+                .zip(fired.iter())
+                .filter(|(_, fired)| **fired)
+            {
+                input_cfg.weight = input_cfg.weight.saturating_add(delta);
+            }
+            network.configure_neuron(readout_id, input_configs).await?;
+        }
+
+        Ok(sample_error)
+    }
+}
+
+/// Ids of reservoir's neurons installed into the network (see Network::restore_config()).
+fn network_ids(network: &Network, cfg_ids: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+    cfg_ids
+        .iter()
+        .map(|id| {
+            let id_num = get_component_id_fraction(id, &SpecificationType::Neuron)?;
+            gen_id_by_spec_type(&network.get_id(), id_num, &SpecificationType::Neuron)
+        })
+        .collect()
+}
+
+/// The last signal received during the window, if any.
+fn last_signal(receiver: &mut Receiver<Signal>) -> Option<Signal> {
+    let mut last = None;
+    loop {
+        match receiver.try_recv() {
+            Ok(signal) => last = Some(signal),
+            Err(TryRecvError::Lagged(_)) => continue,
+            Err(_) => return last,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::NetworkCfg;
+    use crate::rnn::layouts::topology::TopologyParams;
+    use crate::rnn::tests::fixtures::new_network_fixture;
+
+    /// Hidden neurons without recurrent links: only M0Z0 receives input signals.
+    fn trainer_fixture() -> ReservoirTrainer {
+        let reservoir = Reservoir {
+            params: TopologyParams {
+                neurons: 3,
+                inputs: 1,
+                outputs: 1,
+                bias: 0,
+                input_cfg: InputCfg::new(10, 10, 1).unwrap(),
+                seed: 1,
+            },
+            connectivity: 0.0,
+            readout_cfg: InputCfg::new(10, 10, 0).unwrap(),
+        };
+        ReservoirTrainer::new(reservoir, 2, Duration::from_millis(30))
+    }
+
+    #[tokio::test]
+    async fn should_adjust_only_readout_weights_of_fired_hidden_neurons() {
+        let trainer = trainer_fixture();
+        let net = Arc::new(new_network_fixture());
+        let ids = net
+            .restore_config(net.clone(), &trainer.reservoir.build().unwrap())
+            .await
+            .unwrap();
+        let hidden_before = net
+            .get_neuron(&ids["M0Z0"])
+            .await
+            .unwrap()
+            .get_config()
+            .await;
+
+        let samples = vec![ReservoirSample {
+            inputs: vec![5],
+            targets: vec![200],
+        }];
+        let error = trainer.train_epoch(&net, &samples).await.unwrap();
+
+        assert!(error > 0);
+        let readout = net
+            .get_neuron(&ids["M0Z3"])
+            .await
+            .unwrap()
+            .get_config()
+            .await;
+        let weights: Vec<Weight> = readout
+            .input_configs
+            .iter()
+            .map(|input_cfg| input_cfg.weight)
+            .collect();
+        assert_eq!(weights, vec![2, 0, 0]);
+        assert_eq!(
+            net.get_neuron(&ids["M0Z0"])
+                .await
+                .unwrap()
+                .get_config()
+                .await,
+            hidden_before
+        );
+    }
+
+    #[tokio::test]
+    async fn should_reject_samples_of_wrong_size() {
+        let trainer = trainer_fixture();
+        let net = Arc::new(new_network_fixture());
+        net.restore_config(net.clone(), &trainer.reservoir.build().unwrap())
+            .await
+            .unwrap();
+
+        let samples = vec![ReservoirSample {
+            inputs: vec![5, 5],
+            targets: vec![200],
+        }];
+        assert!(trainer.train_epoch(&net, &samples).await.is_err());
+    }
+
+    #[derive(Default)]
+    struct RecordingCallbacks {
+        epochs: Vec<EpochReport>,
+        improvements: Vec<(u64, NetworkCfg)>,
+    }
+
+    impl TrainerCallbacks for RecordingCallbacks {
+        fn on_epoch_end(&mut self, report: &EpochReport) -> Result<(), Box<dyn Error>> {
+            self.epochs.push(*report);
+            Ok(())
+        }
+
+        fn on_improvement(
+            &mut self,
+            report: &EpochReport,
+            config: &NetworkCfg,
+        ) -> Result<(), Box<dyn Error>> {
+            self.improvements.push((report.epoch, config.clone()));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn fit_should_call_back_on_epoch_end_and_improvement() {
+        let trainer = trainer_fixture();
+        let net = Arc::new(new_network_fixture());
+        net.restore_config(net.clone(), &trainer.reservoir.build().unwrap())
+            .await
+            .unwrap();
+        let samples = vec![ReservoirSample {
+            inputs: vec![5],
+            targets: vec![200],
+        }];
+        let mut callbacks = RecordingCallbacks::default();
+
+        let errors = trainer
+            .fit(&net, &samples, 3, &mut callbacks)
+            .await
+            .unwrap();
+
+        assert_eq!(errors.len(), 3);
+        assert_eq!(
+            callbacks
+                .epochs
+                .iter()
+                .map(|report| report.error)
+                .collect::<Vec<_>>(),
+            errors
+        );
+        assert_eq!(
+            callbacks.epochs[2].best_error,
+            errors.iter().copied().min().unwrap()
+        );
+        assert_eq!(callbacks.improvements[0].0, 0);
+        // every improvement reaches the best error so far
+        assert!(callbacks.improvements.iter().all(|(epoch, _)| {
+            let report = callbacks.epochs[*epoch as usize];
+            report.error == report.best_error
+        }));
+        assert_eq!(
+            callbacks.improvements.last().unwrap().1.neurons.len(),
+            trainer.reservoir.build().unwrap().neurons.len()
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Terminal dashboard of the live network: firing rates of neurons, throughput
+//! of network ports and recent monitoring events. The dashboard consumes
+//! the monitoring stream, so it switches the network into MonitoringMode::Monitoring.
+//!
+//! ```ignore
+//! librunen::rnn::tui::run(network).await?;
+//! ```
+
+use std::collections::{BTreeMap, VecDeque};
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use ratatui::crossterm::event::{self, Event, KeyCode};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::widgets::{Block, List, Row, Table};
+use ratatui::Frame;
+use tokio::time;
+use tokio_stream::StreamExt;
+
+use crate::rnn::common::status::Status;
+use crate::rnn::layouts::network::{MonitoringMode, Network};
+
+/// The number of the latest events shown by the dashboard.
+static EVENTS_CAPACITY: usize = 100;
+
+/// The period of redrawing and polling keys.
+static TICK_PERIOD: u64 = 100;
+
+/// The rate of the counter between two statuses of the same component.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Activity {
+    pub count: u64,
+
+    /// Counted events per second since the previous status
+    pub rate: f64,
+
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl Activity {
+    fn update(&mut self, count: u64, timestamp: DateTime<Utc>) {
+        if let Some(updated_at) = self.updated_at {
+            let seconds = (timestamp - updated_at)
+                .to_std()
+                .unwrap_or_default()
+                .as_secs_f64();
+            if seconds > 0.0 {
+                self.rate = count.saturating_sub(self.count) as f64 / seconds;
+            }
+        }
+        self.count = count;
+        self.updated_at = Some(timestamp);
+    }
+}
+
+/// The state shown by the dashboard, built from monitoring statuses.
+#[derive(Debug, Clone, Default)]
+pub struct Dashboard {
+    /// Fires of neurons by id
+    pub neurons: BTreeMap<String, Activity>,
+
+    /// Signals passed through ports by port id
+    pub ports: BTreeMap<String, Activity>,
+
+    /// The latest events, the newest one is the last
+    pub events: VecDeque<String>,
+}
+
+impl Dashboard {
+    pub fn apply(&mut self, status: &Status) {
+        match status {
+            Status::Neuron(info) => {
+                self.neurons
+                    .entry(info.id.clone())
+                    .or_default()
+                    .update(info.reset_count, info.timestamp);
+            }
+            Status::Port(info) => {
+                self.ports
+                    .entry(info.id.clone())
+                    .or_default()
+                    .update(info.hit_count, info.timestamp);
+            }
+            Status::Removal(info) => {
+                self.neurons.remove(&info.id);
+                self.push_event(format!(
+                    "{} {} removed, {} links disconnected",
+                    info.timestamp.format("%H:%M:%S%.3f"),
+                    info.id,
+                    info.disconnected_count
+                ));
+            }
+            Status::PortLagged(info) => {
+                self.push_event(format!(
+                    "{} {} synapse {} lost {} signals",
+                    info.timestamp.format("%H:%M:%S%.3f"),
+                    info.id,
+                    info.port,
+                    info.dropped_count
+                ));
+            }
+        }
+    }
+
+    fn push_event(&mut self, event: String) {
+        if self.events.len() == EVENTS_CAPACITY {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    /// Neurons and ports tables side by side above the events list.
+    pub fn render(&self, frame: &mut Frame) {
+        let rows = Layout::vertical([Constraint::Percentage(70), Constraint::Percentage(30)])
+            .split(frame.area());
+        let columns = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(rows[0]);
+
+        frame.render_widget(
+            activity_table(&self.neurons, "Neurons", "fires"),
+            columns[0],
+        );
+        frame.render_widget(activity_table(&self.ports, "Ports", "signals"), columns[1]);
+        frame.render_widget(
+            List::new(self.events.iter().rev().map(String::as_str))
+                .block(Block::bordered().title("Events (q to quit)")),
+            rows[1],
+        );
+    }
+}
+
+fn activity_table<'a>(
+    activities: &'a BTreeMap<String, Activity>,
+    title: &'a str,
+    counted: &'a str,
+) -> Table<'a> {
+    let rows = activities.iter().map(|(id, activity)| {
+        Row::new(vec![
+            id.clone(),
+            activity.count.to_string(),
+            format!("{:.1}", activity.rate),
+        ])
+    });
+    Table::new(
+        rows,
+        [
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+        ],
+    )
+    .header(Row::new(vec!["id", counted, "per second"]))
+    .block(Block::bordered().title(title))
+}
+
+/// Show the dashboard of the network in the terminal until q is pressed.
+/// The terminal is restored on exit.
+pub async fn run(network: Arc<Network>) -> Result<(), Box<dyn Error>> {
+    network
+        .set_monitoring_mode(MonitoringMode::Monitoring)
+        .await;
+    let mut statuses = network.monitoring_stream();
+    let mut dashboard = Dashboard::default();
+    let mut ticks = time::interval(Duration::from_millis(TICK_PERIOD));
+    let mut terminal = ratatui::init();
+
+    let result: Result<(), Box<dyn Error>> = loop {
+        tokio::select! {
+            Some(status) = statuses.next() => dashboard.apply(&status),
+            _ = ticks.tick() => {
+                if let Err(err) = terminal.draw(|frame| dashboard.render(frame)) {
+                    break Err(err.into());
+                }
+                match quit_pressed() {
+                    Ok(false) => {}
+                    Ok(true) => break Ok(()),
+                    Err(err) => break Err(err),
+                }
+            }
+        }
+    };
+
+    ratatui::restore();
+    result
+}
+
+fn quit_pressed() -> Result<bool, Box<dyn Error>> {
+    while event::poll(Duration::ZERO)? {
+        if let Event::Key(key) = event::read()? {
+            if key.code == KeyCode::Char('q') {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    use super::*;
+    use crate::rnn::common::network_cfg::NeuronKind;
+    use crate::rnn::common::status::{LagInfo, NeuronInfo};
+
+    fn neuron_status(timestamp: DateTime<Utc>, reset_count: u64) -> Status {
+        Status::Neuron(NeuronInfo {
+            timestamp,
+            id: String::from("M0Z0"),
+            dendrite_count: 1,
+            dendrite_connected_count: 1,
+            dendrite_hit_count: 0,
+            reset_count,
+            hit_count: reset_count,
+            accumulator: 0,
+            receiver_count: 1,
+            total_weight: 1,
+            dropped_signals: BTreeMap::new(),
+            refractory_count: 0,
+            kind: NeuronKind::Excitatory,
+        })
+    }
+
+    #[test]
+    fn dashboard_should_rate_fires_between_statuses() {
+        let now = Utc::now();
+        let mut dashboard = Dashboard::default();
+
+        dashboard.apply(&neuron_status(now, 2));
+        dashboard.apply(&neuron_status(now + TimeDelta::milliseconds(250), 7));
+        dashboard.apply(&Status::PortLagged(LagInfo {
+            timestamp: now,
+            id: String::from("M0Z0"),
+            port: 0,
+            dropped_count: 3,
+        }));
+
+        assert_eq!(dashboard.neurons["M0Z0"].count, 7);
+        assert_eq!(dashboard.neurons["M0Z0"].rate, 20.0);
+        assert_eq!(dashboard.events.len(), 1);
+    }
+
+    #[test]
+    fn dashboard_should_render_neurons_and_events() {
+        let mut dashboard = Dashboard::default();
+        dashboard.apply(&neuron_status(Utc::now(), 1));
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+
+        terminal.draw(|frame| dashboard.render(frame)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Neurons"));
+        assert!(screen.contains("M0Z0"));
+        assert!(screen.contains("Events"));
+    }
+}
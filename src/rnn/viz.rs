@@ -0,0 +1,258 @@
+//! The egui widget drawing the network topology with live activity: neurons and
+//! their outbound links are highlighted after fires and fade during the highlight
+//! period. Nodes are placed by analysis::drawing::layered_layout().
+//!
+//! ```ignore
+//! let view = Arc::new(Mutex::new(NetworkView::new(network.get_config().await)));
+//! viz::spawn_feed(network.clone(), view.clone(), ctx.clone());
+//! // in eframe::App::update()
+//! egui::CentralPanel::default().show(ctx, |ui| view.lock().unwrap().show(ui));
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use egui::{Align2, Color32, FontId, Pos2, Rect, Sense, Stroke, Ui, Vec2};
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt;
+
+use crate::rnn::analysis::drawing::{Drawing, Node};
+use crate::rnn::common::network_cfg::NetworkCfg;
+use crate::rnn::common::status::Status;
+use crate::rnn::layouts::network::{MonitoringMode, Network};
+
+/// The time of fading after the fire.
+static DEFAULT_HIGHLIGHT: Duration = Duration::from_millis(500);
+
+static NODE_RADIUS: f32 = 10.0;
+static MARGIN: f32 = 30.0;
+
+static IDLE_COLOR: Color32 = Color32::from_rgb(90, 90, 110);
+static FIRED_COLOR: Color32 = Color32::from_rgb(255, 200, 40);
+static PORT_COLOR: Color32 = Color32::from_rgb(70, 130, 180);
+static LABEL_COLOR: Color32 = Color32::from_rgb(200, 200, 200);
+
+pub struct NetworkView {
+    drawing: Drawing,
+
+    /// The latest fire of neurons by id
+    fired_at: BTreeMap<String, Instant>,
+
+    /// The latest known number of fires by neuron id
+    reset_counts: BTreeMap<String, u64>,
+
+    highlight: Duration,
+}
+
+impl NetworkView {
+    pub fn new(topology: NetworkCfg) -> Self {
+        NetworkView {
+            drawing: Drawing::new(topology),
+            fired_at: BTreeMap::new(),
+            reset_counts: BTreeMap::new(),
+            highlight: DEFAULT_HIGHLIGHT,
+        }
+    }
+
+    pub fn with_highlight(mut self, highlight: Duration) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Replace the drawn topology keeping the activity of remaining neurons.
+    pub fn set_topology(&mut self, topology: NetworkCfg) {
+        self.drawing = Drawing::new(topology);
+    }
+
+    pub fn apply(&mut self, status: &Status) {
+        self.apply_at(status, Instant::now());
+    }
+
+    /// The neuron fired when its number of fires grows.
+    pub fn apply_at(&mut self, status: &Status, now: Instant) {
+        match status {
+            Status::Neuron(info) => {
+                let previous = self.reset_counts.insert(info.id.clone(), info.reset_count);
+                if previous.is_some_and(|count| count < info.reset_count) {
+                    self.fired_at.insert(info.id.clone(), now);
+                }
+            }
+            Status::Removal(info) => {
+                self.reset_counts.remove(&info.id);
+                self.fired_at.remove(&info.id);
+            }
+            Status::Port(_) | Status::PortLagged(_) => {}
+        }
+    }
+
+    /// 1.0 right after the fire, fading to 0.0 at the end of the highlight period.
+    pub fn intensity_at(&self, id: &str, now: Instant) -> f32 {
+        self.fired_at.get(id).map_or(0.0, |fired_at| {
+            let elapsed = now.saturating_duration_since(*fired_at);
+            (1.0 - elapsed.as_secs_f32() / self.highlight.as_secs_f32()).max(0.0)
+        })
+    }
+
+    /// Is any neuron still highlighted, so the view has to be repainted.
+    pub fn is_active_at(&self, now: Instant) -> bool {
+        self.fired_at
+            .keys()
+            .any(|id| self.intensity_at(id, now) > 0.0)
+    }
+
+    /// Paint the network into all available space of the ui.
+    pub fn show(&self, ui: &mut Ui) -> egui::Response {
+        let now = Instant::now();
+        let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::hover());
+        let area = response.rect.shrink(MARGIN);
+
+        let positions = &self.drawing.layout.positions;
+        let max_x = positions.iter().map(|p| p.x).fold(0.0, f64::max).max(1.0);
+        let min_y = positions.iter().map(|p| p.y).fold(0.0, f64::min);
+        let max_y = positions.iter().map(|p| p.y).fold(0.0, f64::max);
+        let height = (max_y - min_y).max(1.0);
+        let to_screen = |node: &Node| {
+            self.drawing.layout.get_position(node).map(|(x, y)| {
+                Pos2::new(
+                    area.left() + (x / max_x) as f32 * area.width(),
+                    area.top() + ((y - min_y) / height) as f32 * area.height(),
+                )
+            })
+        };
+        let node_color = |node: &Node| match node {
+            Node::Neuron(id) => mix(IDLE_COLOR, FIRED_COLOR, self.intensity_at(id, now)),
+            Node::Input(_) | Node::Output(_) => PORT_COLOR,
+        };
+
+        let (nodes, edges) = self.drawing.topology.graph_elements();
+        for (src, dst, _) in edges.iter() {
+            if let (Some(from), Some(to)) = (to_screen(src), to_screen(dst)) {
+                painter.line_segment([from, to], Stroke::new(1.5, node_color(src)));
+            }
+        }
+        for node in nodes.iter() {
+            let Some(center) = to_screen(node) else {
+                continue;
+            };
+            match node {
+                Node::Neuron(_) => {
+                    painter.circle_filled(center, NODE_RADIUS, node_color(node));
+                }
+                Node::Input(_) | Node::Output(_) => {
+                    painter.rect_filled(
+                        Rect::from_center_size(center, Vec2::splat(NODE_RADIUS * 2.0)),
+                        2.0,
+                        node_color(node),
+                    );
+                }
+            }
+            painter.text(
+                center + Vec2::new(0.0, NODE_RADIUS + 2.0),
+                Align2::CENTER_TOP,
+                node.to_string(),
+                FontId::proportional(10.0),
+                LABEL_COLOR,
+            );
+        }
+
+        if self.is_active_at(now) {
+            ui.ctx().request_repaint();
+        }
+        response
+    }
+}
+
+/// Feed the view by the monitoring stream of the network (it switches the network
+/// into MonitoringMode::Monitoring) and repaint the context on every status.
+pub fn spawn_feed(
+    network: Arc<Network>,
+    view: Arc<Mutex<NetworkView>>,
+    ctx: egui::Context,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        network
+            .set_monitoring_mode(MonitoringMode::Monitoring)
+            .await;
+        let mut statuses = network.monitoring_stream();
+        drop(network);
+        while let Some(status) = statuses.next().await {
+            let Ok(mut view) = view.lock() else {
+                return;
+            };
+            view.apply(&status);
+            ctx.request_repaint();
+        }
+    })
+}
+
+fn mix(from: Color32, to: Color32, t: f32) -> Color32 {
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Color32::from_rgb(
+        channel(from.r(), to.r()),
+        channel(from.g(), to.g()),
+        channel(from.b(), to.b()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::rnn::common::network_cfg::{LabelMap, NeuronKind};
+    use crate::rnn::common::status::NeuronInfo;
+
+    fn neuron_status(reset_count: u64) -> Status {
+        Status::Neuron(NeuronInfo {
+            timestamp: Utc::now(),
+            id: String::from("M0Z0"),
+            dendrite_count: 1,
+            dendrite_connected_count: 1,
+            dendrite_hit_count: 0,
+            reset_count,
+            hit_count: reset_count,
+            accumulator: 0,
+            receiver_count: 1,
+            total_weight: 1,
+            dropped_signals: BTreeMap::new(),
+            refractory_count: 0,
+            kind: NeuronKind::Excitatory,
+        })
+    }
+
+    fn empty_topology() -> NetworkCfg {
+        NetworkCfg {
+            inputs: 0,
+            outputs: 0,
+            neurons: vec![],
+            links: vec![],
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn fired_neuron_should_fade_during_highlight() {
+        let mut view =
+            NetworkView::new(empty_topology()).with_highlight(Duration::from_millis(100));
+        let start = Instant::now();
+
+        view.apply_at(&neuron_status(3), start);
+        assert_eq!(view.intensity_at("M0Z0", start), 0.0);
+
+        view.apply_at(&neuron_status(4), start);
+        assert_eq!(view.intensity_at("M0Z0", start), 1.0);
+        let halfway = view.intensity_at("M0Z0", start + Duration::from_millis(50));
+        assert!((halfway - 0.5).abs() < 1e-3);
+        assert!(!view.is_active_at(start + Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn colors_should_be_mixed_by_intensity() {
+        assert_eq!(mix(IDLE_COLOR, FIRED_COLOR, 0.0), IDLE_COLOR);
+        assert_eq!(mix(IDLE_COLOR, FIRED_COLOR, 1.0), FIRED_COLOR);
+    }
+}
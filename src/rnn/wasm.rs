@@ -0,0 +1,131 @@
+//! wasm-bindgen API for browsers. The library is built for the web with
+//! `cargo build --lib --target wasm32-unknown-unknown --features wasm`.
+//!
+//! Running networks needs the tokio time driver, which is not available in browsers
+//! (it reads `std::time::Instant`), so the API works with configs: validation,
+//! lint findings, structure analysis and DOT source for rendering the topology.
+//!
+//! ```js
+//! const network = new WasmNetwork(yamlText);
+//! const findings = JSON.parse(network.lint());
+//! renderGraphviz(network.toDot());
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::rnn::analysis::doctor;
+use crate::rnn::analysis::structure;
+use crate::rnn::common::network_cfg::NetworkCfg;
+
+/// The network id used in lint findings
+static NETWORK_ID: &str = "M0";
+
+#[wasm_bindgen]
+pub struct WasmNetwork {
+    config: NetworkCfg,
+}
+
+#[wasm_bindgen]
+impl WasmNetwork {
+    /// Parse and validate the config in yaml or json format.
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: &str) -> Result<WasmNetwork, JsError> {
+        let config: NetworkCfg = serde_yaml::from_str(config)?;
+        config
+            .validate()
+            .map_err(|err| JsError::new(&err.to_string()))?;
+        Ok(WasmNetwork { config })
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.config.inputs
+    }
+
+    pub fn outputs(&self) -> usize {
+        self.config.outputs
+    }
+
+    pub fn neurons(&self) -> usize {
+        self.config.neurons.len()
+    }
+
+    pub fn links(&self) -> usize {
+        self.config.links.len()
+    }
+
+    /// Findings of the config lint as json array.
+    pub fn lint(&self) -> Result<String, JsError> {
+        Ok(serde_json::to_string(&doctor::lint(
+            &self.config,
+            NETWORK_ID,
+        ))?)
+    }
+
+    /// Structure report (components, cycles, unreachable neurons) as json.
+    pub fn structure(&self) -> Result<String, JsError> {
+        let report =
+            structure::analyze(&self.config).map_err(|err| JsError::new(&err.to_string()))?;
+        Ok(serde_json::to_string(&report)?)
+    }
+
+    #[wasm_bindgen(js_name = toDot)]
+    pub fn to_dot(&self) -> String {
+        self.config.to_dot()
+    }
+
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> Result<String, JsError> {
+        Ok(serde_json::to_string(&self.config)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rnn::common::input_cfg::InputCfg;
+    use crate::rnn::common::network_cfg::{LabelMap, LinkCfg, NeuronCfg, NeuronKind};
+
+    /// I0 -> Z0 -> O0
+    fn config_fixture() -> NetworkCfg {
+        NetworkCfg {
+            inputs: 1,
+            outputs: 1,
+            neurons: vec![NeuronCfg {
+                id: String::from("M0Z0"),
+                bias: 1,
+                input_configs: vec![InputCfg::new(3, 3, 1).unwrap()],
+                lineage: None,
+                kind: NeuronKind::Excitatory,
+            }],
+            links: vec![
+                LinkCfg::Input {
+                    input_port: 0,
+                    dst_id: String::from("M0Z0"),
+                    dst_synapse_idx: 0,
+                },
+                LinkCfg::Output {
+                    src_id: String::from("M0Z0"),
+                    output_port: 0,
+                },
+            ],
+            lineage: None,
+            composites: vec![],
+            labels: LabelMap::default(),
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn config_should_be_described_for_browser() {
+        let yaml = serde_yaml::to_string(&config_fixture()).unwrap();
+
+        let network = WasmNetwork::new(&yaml).unwrap();
+
+        assert_eq!((network.inputs(), network.outputs()), (1, 1));
+        assert_eq!((network.neurons(), network.links()), (1, 2));
+        assert!(network.to_dot().starts_with("digraph"));
+        let config: NetworkCfg = serde_json::from_str(&network.to_json().unwrap()).unwrap();
+        assert_eq!(config, config_fixture());
+        assert!(network.structure().is_ok());
+    }
+}
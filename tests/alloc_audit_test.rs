@@ -0,0 +1,68 @@
+#![cfg(feature = "alloc-audit")]
+
+use std::{sync::Arc, time::Duration};
+
+use librunen::rnn::{
+    common::{
+        alloc_audit::{AllocationAudit, CountingAllocator},
+        input_cfg::InputCfg,
+        network_cfg::NeuronKind,
+    },
+    layouts::network::Network,
+};
+use tokio::time::{sleep, timeout};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+static SIGNALS: u64 = 100;
+
+/// The limit guards against regressions of the current implementation,
+/// it should be lowered with hot path improvements.
+static MAX_ALLOCS_PER_SIGNAL: u64 = 4;
+
+#[tokio::test]
+async fn signal_processing_should_not_exceed_allocations_limit() {
+    let net = Arc::new(Network::new().unwrap());
+    let src_id = net
+        .create_neuron(
+            net.clone(),
+            0,
+            vec![InputCfg::new(3, 3, 1).unwrap()],
+            NeuronKind::Excitatory,
+        )
+        .await
+        .unwrap()
+        .get_id();
+    let dst_id = net
+        .create_neuron(
+            net.clone(),
+            0,
+            vec![InputCfg::new(3, 3, 1).unwrap()],
+            NeuronKind::Excitatory,
+        )
+        .await
+        .unwrap()
+        .get_id();
+    assert!(net.connect_neurons(&src_id, &dst_id, 0).await.is_ok());
+    assert!(net.setup_input(0, &src_id, 0).await.is_ok());
+    assert!(net.setup_output(0, &dst_id).await.is_ok());
+    let mut receiver = net.subscribe_output(0).await.unwrap();
+
+    let audit = AllocationAudit::start();
+    for _ in 0..SIGNALS {
+        assert!(net.input(1, 0).await.is_ok());
+        assert!(timeout(Duration::from_millis(100), receiver.recv())
+            .await
+            .is_ok());
+    }
+    sleep(Duration::from_millis(1)).await;
+
+    assert!(audit.allocations() > 0);
+    // every input signal is processed by two neurons
+    println!(
+        "{:.1} allocations per signal",
+        audit.allocs_per_signal(SIGNALS * 2)
+    );
+    audit.assert_max_allocs_per_signal(SIGNALS * 2, MAX_ALLOCS_PER_SIGNAL);
+}
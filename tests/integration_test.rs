@@ -1,7 +1,7 @@
 use std::{sync::Arc, time::Duration};
 
 use librunen::rnn::{
-    common::{input_cfg::InputCfg, status::Status},
+    common::{input_cfg::InputCfg, network_cfg::NeuronKind, status::Status},
     layouts::network::Network,
 };
 use tokio::time::sleep;
@@ -21,11 +21,20 @@ async fn test_signal_propagation() {
         InputCfg::new(2, 2, 1).unwrap(),
     ];
 
-    let neuron0 = net.create_neuron(net.clone(), 1, vec![]).await.unwrap();
+    let neuron0 = net
+        .create_neuron(net.clone(), 1, vec![], NeuronKind::Excitatory)
+        .await
+        .unwrap();
     let id0 = neuron0.get_id();
-    let neuron1 = net.create_neuron(net.clone(), 1, config1).await.unwrap();
+    let neuron1 = net
+        .create_neuron(net.clone(), 1, config1, NeuronKind::Excitatory)
+        .await
+        .unwrap();
     let id1 = neuron1.get_id();
-    let neuron2 = net.create_neuron(net.clone(), 1, config2).await.unwrap();
+    let neuron2 = net
+        .create_neuron(net.clone(), 1, config2, NeuronKind::Excitatory)
+        .await
+        .unwrap();
     let id2 = neuron2.get_id();
 
     // create inter neuron links